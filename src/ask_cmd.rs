@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::io;
+
+use kalosm::language::*;
+use surrealdb::engine::local::RocksDb;
+use surrealdb::Surreal;
+use tracing::{debug, error, info};
+
+use crate::note::NoteManager;
+use crate::settings::Settings;
+
+const TOP_K_CHUNKS: usize = 6;
+
+/// Handles `noorg ask`: a local, private "chat with your vault" query. This
+/// reuses the same BERT-embedded `DocumentTable` `SimilarNotesObserver`
+/// builds for the write-time "Similar Notes" section, but retrieves at
+/// chunk level (not whole-document) so the LLM prompt is stuffed with the
+/// most relevant passages rather than entire notes.
+pub async fn handle(
+    question: &str,
+    save: bool,
+    settings: &Settings,
+    note_manager: &NoteManager,
+) -> io::Result<()> {
+    debug!("Answering question: {}", question);
+
+    let bert = Bert::new().await.map_err(|e| {
+        error!("Failed to initialize BERT model: {}", e);
+        io::Error::new(io::ErrorKind::Other, e)
+    })?;
+
+    let db = Surreal::new::<RocksDb>("./db/embeddings.db")
+        .await
+        .map_err(|e| {
+            error!("Failed to connect to embeddings database: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+    db.use_ns("test").use_db("test").await.map_err(|e| {
+        error!("Failed to select database namespace: {}", e);
+        io::Error::new(io::ErrorKind::Other, e)
+    })?;
+
+    let document_table = db
+        .document_table_builder("documents")
+        .with_chunker(SemanticChunker::new())
+        .at("./db/embeddings.db")
+        .build()
+        .await
+        .map_err(|e| {
+            error!("Failed to open document table: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+
+    let question_embedding = bert.embed(question).await.map_err(|e| {
+        error!("Failed to embed question: {}", e);
+        io::Error::new(io::ErrorKind::Other, e)
+    })?;
+
+    let chunks = document_table
+        .select_nearest(question_embedding, TOP_K_CHUNKS)
+        .await
+        .map_err(|e| {
+            error!("Failed to retrieve context chunks: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+
+    if chunks.is_empty() {
+        println!(
+            "No indexed notes to answer from yet. Run `noorg sync` with the similar_notes observer enabled first."
+        );
+        return Ok(());
+    }
+
+    let mut context = String::new();
+    let mut sources: Vec<String> = Vec::new();
+    for chunk in &chunks {
+        let title = chunk.record.title().to_string();
+        context.push_str(&format!("### {}\n{}\n\n", title, chunk.record.body()));
+        if !sources.contains(&title) {
+            sources.push(title);
+        }
+    }
+
+    let prompt = format!(
+        "Answer the question using ONLY the context below. If the context doesn't contain \
+         the answer, say so instead of guessing.\n\n{}\nQuestion: {}\nAnswer:",
+        context, question
+    );
+
+    let llm = Llama::new_chat().await.map_err(|e| {
+        error!("Failed to initialize LLM: {}", e);
+        io::Error::new(io::ErrorKind::Other, e)
+    })?;
+
+    let citations = format_citations(&sources, &settings.file_type);
+
+    if save {
+        info!("🤔 Generating answer...");
+        let answer = llm
+            .stream_text(&prompt)
+            .with_max_length(512)
+            .await
+            .map_err(|e| {
+                error!("Failed to generate answer: {}", e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?
+            .all_text()
+            .await;
+
+        let content = format!("{}\n\n{}", answer.trim(), citations);
+        let mut frontmatter = HashMap::new();
+        frontmatter.insert("question".to_string(), question.to_string());
+
+        let title = format!("ask-{}", slugify(question));
+        note_manager.add_note(title.clone(), content, frontmatter).await?;
+        info!("✨ Answer saved to note '{}'", title);
+    } else {
+        info!("🤔 Thinking...");
+        let stream = llm
+            .stream_text(&prompt)
+            .with_max_length(512)
+            .await
+            .map_err(|e| {
+                error!("Failed to generate answer: {}", e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+
+        stream.to_std_out().await.map_err(|e| {
+            error!("Failed to stream answer: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+
+        println!("\n\n{}", citations);
+    }
+
+    Ok(())
+}
+
+/// Renders a "Sources" section in the same `./{title}.{file_type}` link
+/// format `SimilarNotesObserver::append_references` uses, so answers and
+/// "Similar Notes" sections look consistent.
+fn format_citations(sources: &[String], file_type: &str) -> String {
+    if sources.is_empty() {
+        return String::new();
+    }
+
+    let mut out = String::from("## Sources\n\n");
+    for title in sources {
+        out.push_str(&format!("- [{}](./{}.{})\n", title, title, file_type));
+    }
+    out
+}
+
+fn slugify(question: &str) -> String {
+    question
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .take(8)
+        .collect::<Vec<_>>()
+        .join("-")
+}
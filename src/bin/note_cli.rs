@@ -1,31 +1,72 @@
 use clap::Parser;
 use noorg::{
-    cli::Cli, handlers::handle_command, logging::init_logging, observer_registry::ObserverRegistry,
-    script_loader::ScriptLoader, settings::Settings,
+    cli::Cli, handlers::handle_command, host_api::HostApi, logging::init_logging_with_settings,
+    note::NoteManager, observer_registry::ObserverRegistry,
+    postprocessor_registry::PostprocessorRegistry, script_loader::ScriptLoader, settings::Settings,
+    signal,
 };
 use std::{io, sync::Arc};
+use tokio::runtime::Handle;
 
 #[tokio::main]
 async fn main() -> io::Result<()> {
     // Parse CLI args first to get debug flag
     let cli = Cli::parse();
 
-    // Initialize logging before any other operations
-    init_logging(cli.debug);
+    let mut settings = match Settings::new() {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("Failed to load settings: {}", e);
+            std::process::exit(1);
+        }
+    };
+    cli.config_override.apply(&mut settings);
 
-    let settings = Settings::new();
+    // Initialize logging once settings (log level/filter/terminal toggle) are known
+    init_logging_with_settings(cli.debug, Some(&settings));
     let script_loader = ScriptLoader::new(settings.scripts_dir.clone(), settings.clone());
 
-    // Load observers asynchronously
-    let observers = script_loader.load_observers(&settings.enabled_observers)?;
+    // Build the host API scripts get at construction time: the registries it
+    // closes over are still empty here, but they're shared by `Arc` so the
+    // observers/postprocessors loaded below land in the same ones.
     let observer_registry = Arc::new(ObserverRegistry::new());
+    let postprocessor_registry = Arc::new(PostprocessorRegistry::new());
+    let note_manager = NoteManager::new_with_postprocessors(
+        settings.clone(),
+        observer_registry.clone(),
+        postprocessor_registry.clone(),
+    )
+    .await?;
+    let host_api = HostApi::new(note_manager, observer_registry.clone(), Handle::current());
+
+    // Load observers asynchronously
+    let loaded = script_loader.load_observers(&settings.enabled_observers, host_api)?;
 
     // Register observers
-    for observer in observers {
+    for observer in loaded.observers {
         observer_registry.register(observer).await;
     }
+    observer_registry.set_cmd_owners(loaded.cmd_owners).await;
+
+    // Load and register postprocessors
+    let postprocessors = script_loader.load_postprocessors()?;
+    for postprocessor in postprocessors {
+        postprocessor_registry.register(postprocessor).await;
+    }
+
+    // Install SIGINT/SIGTERM handling before `Watch` can start, so Ctrl-C
+    // always triggers a graceful drain instead of killing the process
+    // mid-sync.
+    let stop_signal = signal::install();
 
-    handle_command(cli.command, settings, observer_registry, None).await?;
+    handle_command(
+        cli.command,
+        settings,
+        observer_registry,
+        postprocessor_registry,
+        Some(stop_signal),
+    )
+    .await?;
 
     Ok(())
 }
@@ -0,0 +1,14 @@
+use noorg::note_picker::NotePicker;
+use noorg::settings::Settings;
+
+fn main() {
+    let settings = match Settings::new() {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("Failed to load settings: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    NotePicker::show(settings);
+}
@@ -1,8 +1,9 @@
 use image::io::Reader as ImageReader;
 use noorg::{
-    cli::Command, handlers::handle_command, logging::init_logging,
-    observer_registry::ObserverRegistry, script_loader::ScriptLoader, settings::Settings,
-    window_manager,
+    cli::Command, editor::open_note_file, handlers::handle_command, host_api::HostApi,
+    logging::init_logging, note::NoteManager, observer_registry::ObserverRegistry,
+    postprocessor_registry::PostprocessorRegistry, script_loader::ScriptLoader,
+    settings::Settings, utils::get_fs_path, window_manager,
 };
 use std::env;
 use std::io::Cursor;
@@ -10,6 +11,7 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::{io, sync::Arc};
 use tao::event_loop::{ControlFlow, EventLoop};
+use tokio::runtime::Handle;
 use tokio::sync::mpsc;
 use tokio::sync::Mutex;
 use tracing::{error, info};
@@ -22,6 +24,7 @@ use tray_icon::{
 enum TrayCommand {
     ToggleWatch,
     AddNote,
+    OpenNote,
     Quit,
     UpdateWatchStatus(bool),
     OpenSettings,
@@ -31,6 +34,7 @@ enum TrayCommand {
 struct MenuItems {
     watch_item: MenuItem,
     add_note_item: MenuItem,
+    open_note_item: MenuItem,
     settings_item: MenuItem,
     info_item: MenuItem,
     quit_item: MenuItem,
@@ -91,6 +95,15 @@ fn get_cli_path() -> PathBuf {
     path
 }
 
+fn get_note_picker_path() -> PathBuf {
+    let mut path = get_bin_path();
+    #[cfg(target_os = "windows")]
+    path.push("note_picker.exe");
+    #[cfg(not(target_os = "windows"))]
+    path.push("note_picker");
+    path
+}
+
 fn get_resources_path() -> PathBuf {
     let mut path = get_base_path();
     path.push("resources");
@@ -111,6 +124,7 @@ async fn main() -> io::Result<()> {
     let menu_items = MenuItems {
         watch_item: MenuItem::new("🔴 Start Watching", true, None),
         add_note_item: MenuItem::new("Add Note", true, None),
+        open_note_item: MenuItem::new("🔎 Open Note", true, None),
         settings_item: MenuItem::new("⚙️ Settings", true, None),
         info_item: MenuItem::new("ℹ️ Show Info", true, None),
         quit_item: MenuItem::new("Quit", true, None),
@@ -119,18 +133,21 @@ async fn main() -> io::Result<()> {
     // Set up menu event handlers
     let watch_id = menu_items.watch_item.id().clone();
     let add_id = menu_items.add_note_item.id().clone();
+    let open_note_id = menu_items.open_note_item.id().clone();
     let settings_id = menu_items.settings_item.id().clone();
     let info_id = menu_items.info_item.id().clone();
     let quit_id = menu_items.quit_item.id().clone();
 
     menu.append(&menu_items.watch_item).unwrap();
     menu.append(&menu_items.add_note_item).unwrap();
+    menu.append(&menu_items.open_note_item).unwrap();
     menu.append(&menu_items.settings_item).unwrap();
     menu.append(&menu_items.info_item).unwrap();
     menu.append(&menu_items.quit_item).unwrap();
 
     // Register menu event handlers
     let tx_watch_clone = tx_watch.clone();
+    let tx_open_note = tx_watch.clone();
     let tx_settings = tx_watch.clone();
     let tx_info = tx_watch.clone();
 
@@ -140,6 +157,8 @@ async fn main() -> io::Result<()> {
             tx_watch_clone.send(TrayCommand::ToggleWatch)
         } else if *menu_id == add_id {
             tx_add.send(TrayCommand::AddNote)
+        } else if *menu_id == open_note_id {
+            tx_open_note.send(TrayCommand::OpenNote)
         } else if *menu_id == settings_id {
             tx_settings.send(TrayCommand::OpenSettings)
         } else if *menu_id == info_id {
@@ -179,28 +198,54 @@ async fn main() -> io::Result<()> {
         .unwrap();
 
     // Create settings wrapped in Arc<Mutex>
-    let settings = Arc::new(Mutex::new(Settings::new()));
+    let settings = match Settings::new() {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("Failed to load settings: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let settings = Arc::new(Mutex::new(settings));
 
     // Create script loader with settings
     let settings_guard = settings.lock().await;
     let script_loader =
         ScriptLoader::new(settings_guard.scripts_dir.clone(), settings_guard.clone());
 
+    // Create observer/postprocessor registries and the host API scripts get
+    // at construction time; the registries are still empty here, but they're
+    // shared by `Arc` so the observers/postprocessors loaded below land in
+    // the same ones.
+    let observer_registry = Arc::new(ObserverRegistry::new());
+    let postprocessor_registry = Arc::new(PostprocessorRegistry::new());
+    let note_manager = NoteManager::new_with_postprocessors(
+        settings_guard.clone(),
+        observer_registry.clone(),
+        postprocessor_registry.clone(),
+    )
+    .await?;
+    let host_api = HostApi::new(note_manager, observer_registry.clone(), Handle::current());
+
     // Load observers
-    let observers = script_loader.load_observers(&settings_guard.enabled_observers)?;
+    let loaded = script_loader.load_observers(&settings_guard.enabled_observers, host_api)?;
     drop(settings_guard); // Release the lock
 
-    // Create observer registry
-    let observer_registry = Arc::new(ObserverRegistry::new());
-
     // Load and register observers
-    for observer in observers {
+    for observer in loaded.observers {
         observer_registry.register(observer).await;
     }
+    observer_registry.set_cmd_owners(loaded.cmd_owners).await;
+
+    // Load and register postprocessors
+    let postprocessors = script_loader.load_postprocessors()?;
+    for postprocessor in postprocessors {
+        postprocessor_registry.register(postprocessor).await;
+    }
 
     // Command handler
     let settings_clone = Arc::clone(&settings);
     let observer_registry_clone = Arc::clone(&observer_registry);
+    let postprocessor_registry_clone = Arc::clone(&postprocessor_registry);
     let is_watching = Arc::new(AtomicBool::new(false));
     let stop_signal = Arc::new(AtomicBool::new(false));
 
@@ -228,6 +273,7 @@ async fn main() -> io::Result<()> {
 
                         let settings = settings_clone.clone();
                         let observer_registry = Arc::clone(&observer_registry_clone);
+                        let postprocessor_registry = Arc::clone(&postprocessor_registry_clone);
                         let is_watching_clone = Arc::clone(&is_watching);
                         let tx = tx_watch.clone();
                         let stop_signal = Arc::clone(&stop_signal);
@@ -242,6 +288,7 @@ async fn main() -> io::Result<()> {
                                         Command::Watch,
                                         settings.clone(),
                                         observer_registry,
+                                        postprocessor_registry,
                                         Some(Arc::clone(&stop_signal)),
                                     )
                                     .await
@@ -274,6 +321,7 @@ async fn main() -> io::Result<()> {
                     if let Some(title) = show_input("New Note", "Enter note title") {
                         let settings = settings_clone.clone();
                         let observer_registry = Arc::clone(&observer_registry_clone);
+                        let postprocessor_registry = Arc::clone(&postprocessor_registry_clone);
                         let title_clone = title.clone();
 
                         std::thread::spawn(move || {
@@ -288,6 +336,7 @@ async fn main() -> io::Result<()> {
                                         },
                                         settings.clone(),
                                         observer_registry,
+                                        postprocessor_registry,
                                         None,
                                     )
                                     .await
@@ -299,6 +348,61 @@ async fn main() -> io::Result<()> {
                         });
                     }
                 }
+                TrayCommand::OpenNote => {
+                    let settings = settings_clone.clone();
+                    let observer_registry = Arc::clone(&observer_registry_clone);
+                    let postprocessor_registry = Arc::clone(&postprocessor_registry_clone);
+
+                    std::thread::spawn(move || {
+                        let note_picker = get_note_picker_path();
+                        if !note_picker.exists() {
+                            error!("note_picker binary not found at {:?}", note_picker);
+                            show_error(
+                                "Failed to open note picker",
+                                &format!("note_picker binary not found at {:?}", note_picker),
+                            );
+                            return;
+                        }
+
+                        let output = match std::process::Command::new(&note_picker).output() {
+                            Ok(output) => output,
+                            Err(e) => {
+                                error!("Failed to launch note picker: {}", e);
+                                show_error("Failed to open note picker", &e.to_string());
+                                return;
+                            }
+                        };
+
+                        let title = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                        if title.is_empty() {
+                            info!("Note picker closed without a selection");
+                            return;
+                        }
+
+                        tokio::runtime::Runtime::new().unwrap().block_on(async {
+                            let settings = settings.lock().await;
+                            let path = get_fs_path(&title, &settings);
+
+                            if let Err(e) = open_note_file(&path, &settings) {
+                                error!("Failed to open note '{}': {}", title, e);
+                                show_error("Failed to open note", &e.to_string());
+                                return;
+                            }
+
+                            if let Err(e) = handle_command(
+                                Command::Sync,
+                                settings.clone(),
+                                observer_registry,
+                                postprocessor_registry,
+                                None,
+                            )
+                            .await
+                            {
+                                error!("Failed to sync notes after editing '{}': {}", title, e);
+                            }
+                        });
+                    });
+                }
                 TrayCommand::Quit => {
                     info!("Quitting...");
                     std::process::exit(0);
@@ -311,12 +415,12 @@ async fn main() -> io::Result<()> {
                     let settings = settings_clone.clone();
                     std::thread::spawn(move || {
                         let rt = tokio::runtime::Runtime::new().unwrap();
-                        let settings_guard = rt.block_on(async {
+                        let (settings_guard, reindex_progress) = rt.block_on(async {
                             let settings = settings.lock().await;
-                            settings.clone()
+                            (settings.clone(), noorg::reindex::progress().await)
                         });
 
-                        let message = format!(
+                        let mut message = format!(
                             "Watched Directory: {}\n\
                              File Type: {}\n\
                              Active Observers: {}",
@@ -325,6 +429,13 @@ async fn main() -> io::Result<()> {
                             settings_guard.enabled_observers.join(", ")
                         );
 
+                        if let Ok(Some((completed, total))) = reindex_progress {
+                            message.push_str(&format!(
+                                "\nReindex Progress: {}/{} notes embedded",
+                                completed, total
+                            ));
+                        }
+
                         rfd::MessageDialog::new()
                             .set_title("Note Watcher Info")
                             .set_description(&message)
@@ -1,8 +1,11 @@
-use clap::{arg, Parser, Subcommand};
+use clap::{arg, Args, Parser, Subcommand};
+use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use tracing::{debug, error};
 
+use crate::settings::{Merge, Settings};
+
 #[derive(Parser, Debug, Clone)]
 #[clap(author, version, about)]
 pub struct Cli {
@@ -12,6 +15,60 @@ pub struct Cli {
     /// Enable debug logging
     #[arg(long, global = true, help = "Enable verbose debug output")]
     pub debug: bool,
+
+    #[clap(flatten)]
+    pub config_override: ConfigOverride,
+}
+
+/// Global flags that override `config.toml` (and any vault `.norg.toml`)
+/// for a single invocation, mirroring the global-override pattern used by
+/// tools like jj - useful for scripting and CI, where mutating the user's
+/// config file isn't an option. Applied on top of the loaded `Settings` via
+/// [`crate::settings::Merge`] after `Settings::new()` returns.
+#[derive(Args, Debug, Clone, Default)]
+pub struct ConfigOverride {
+    /// Override the notes directory
+    #[arg(long, global = true, value_name = "PATH")]
+    pub note_dir: Option<String>,
+    /// Override the scripts directory
+    #[arg(long, global = true, value_name = "PATH")]
+    pub scripts_dir: Option<String>,
+    /// Override the note file extension (e.g. "md", "org")
+    #[arg(long, global = true, value_name = "EXT")]
+    pub file_type: Option<String>,
+    /// Override the enabled observers (comma-separated list)
+    #[arg(long, global = true, value_name = "LIST", value_delimiter = ',')]
+    pub observers: Option<Vec<String>>,
+    /// Disable timestamp insertion for this invocation
+    #[arg(long, global = true)]
+    pub no_timestamps: bool,
+}
+
+impl ConfigOverride {
+    /// Applies these overrides onto `settings` in place. `no_timestamps` is
+    /// applied directly rather than through `Merge`, since `Merge` treats
+    /// `false` as "not set" and so can't otherwise force the flag off.
+    pub fn apply(&self, settings: &mut Settings) {
+        let mut overrides = Settings::default();
+        if let Some(note_dir) = &self.note_dir {
+            overrides.note_dir = note_dir.clone();
+        }
+        if let Some(scripts_dir) = &self.scripts_dir {
+            overrides.scripts_dir = scripts_dir.clone();
+        }
+        if let Some(file_type) = &self.file_type {
+            overrides.file_type = file_type.clone();
+        }
+        if let Some(observers) = &self.observers {
+            overrides.enabled_observers = observers.clone();
+        }
+
+        settings.merge(overrides);
+
+        if self.no_timestamps {
+            settings.timestamps = false;
+        }
+    }
 }
 
 #[derive(Debug, Clone, Subcommand)]
@@ -28,6 +85,12 @@ pub enum Command {
         #[arg(short, long, value_parser = parse_key_val, help = "Filter by key:value (e.g. tags:rust)")]
         filter: Vec<(String, String)>,
     },
+    /// Print notes as an indented tree built from `parent`/`position` frontmatter
+    Tree {
+        /// Filter notes by frontmatter key-value pairs
+        #[arg(short, long, value_parser = parse_key_val, help = "Filter by key:value (e.g. tags:rust)")]
+        filter: Vec<(String, String)>,
+    },
     /// Add a new note
     Add {
         /// Title of the note
@@ -46,6 +109,15 @@ pub enum Command {
         #[arg(short, long)]
         title: String,
     },
+    /// Rename a note, rewriting other notes' links to it
+    Rename {
+        /// Current title of the note
+        #[arg(short, long)]
+        title: String,
+        /// New title for the note
+        #[arg(short, long)]
+        new_title: String,
+    },
     #[clap(name = "observers")]
     ListObservers,
     /// Sync all notes with observers
@@ -61,6 +133,103 @@ pub enum Command {
         sql: bool,
     },
     Watch,
+    /// Gossip note hashes with peer `noorg` instances over UDP, pulling and
+    /// applying whatever they have that this vault doesn't
+    Gossip,
+    /// Find notes by meaning using the similar-notes embedding index
+    Search {
+        /// Natural-language query to search for
+        #[arg(short, long)]
+        query: String,
+        /// Maximum number of results to return
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// Rebuild the semantic search embedding index over the whole vault,
+    /// resuming an interrupted run instead of starting over
+    Reindex,
+    /// Ask a question about your vault using local retrieval + a local LLM
+    Ask {
+        /// Question to ask
+        question: String,
+        /// Save the answer as a new note instead of printing it
+        #[arg(long)]
+        save: bool,
+    },
+    /// View, set, or edit the resolved `config.toml`
+    Config {
+        #[clap(subcommand)]
+        action: ConfigCommand,
+    },
+    /// Run a command registered by a Lua/Python observer's `register_commands()`
+    #[clap(name = "run")]
+    Run {
+        /// Name the observer registered the command under
+        name: String,
+        /// Arguments passed through to the command's handler
+        #[arg(trailing_var_arg = true)]
+        args: Vec<String>,
+    },
+    /// Start or stop time tracking on a note
+    Track {
+        #[clap(subcommand)]
+        action: TrackCommand,
+    },
+    /// Scaffold a new vault at `path` (defaults to the current directory)
+    Init {
+        /// Directory to initialize (defaults to the current directory)
+        path: Option<PathBuf>,
+        /// Note file extension for the new vault (defaults to "md")
+        #[arg(long)]
+        file_type: Option<String>,
+        /// Reinitialize an already-initialized vault
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum TrackCommand {
+    /// Open a tracking interval on a note
+    Start {
+        /// Title of the note to track
+        #[arg(short, long)]
+        title: String,
+        /// When tracking started (defaults to now) - accepts offsets like
+        /// `-15 minutes`, `-1d`, or `yesterday 17:20`
+        #[arg(long)]
+        at: Option<String>,
+    },
+    /// Close the open tracking interval on a note
+    Stop {
+        /// Title of the note to stop tracking
+        #[arg(short, long)]
+        title: String,
+        /// When tracking stopped (defaults to now) - accepts the same
+        /// offsets as `track start --at`
+        #[arg(long)]
+        at: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum ConfigCommand {
+    /// Print the resolved config file and data directory paths
+    Path,
+    /// Print the value of a config key (dotted path, e.g. `similar_notes.excluded_notes`)
+    Get {
+        /// Dotted key to look up
+        key: String,
+    },
+    /// Set a config key to a value and write it back to `config.toml`
+    Set {
+        /// Dotted key to set
+        key: String,
+        /// New value, parsed as TOML where possible (falls back to a plain string)
+        value: String,
+    },
+    /// Open `config.toml` in `$VISUAL`/`$EDITOR`
+    Edit,
 }
 
 /// Helper function to parse key-value pairs.
@@ -115,4 +284,32 @@ mod tests {
         assert!(parse_key_val("empty_value:").is_err());
         assert!(parse_key_val(":").is_err());
     }
+
+    #[test]
+    fn test_config_override_apply() {
+        let mut settings = Settings {
+            note_dir: "/vault/notes".to_string(),
+            file_type: "md".to_string(),
+            timestamps: true,
+            enabled_observers: vec!["timestamp".to_string()],
+            ..Settings::default()
+        };
+
+        let override_flags = ConfigOverride {
+            note_dir: Some("/tmp/vault".to_string()),
+            observers: Some(vec!["sqlite".to_string(), "toc".to_string()]),
+            no_timestamps: true,
+            ..ConfigOverride::default()
+        };
+
+        override_flags.apply(&mut settings);
+
+        assert_eq!(settings.note_dir, "/tmp/vault");
+        assert_eq!(settings.file_type, "md"); // untouched by the override
+        assert_eq!(
+            settings.enabled_observers,
+            vec!["sqlite".to_string(), "toc".to_string()]
+        );
+        assert!(!settings.timestamps);
+    }
 }
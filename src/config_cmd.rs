@@ -0,0 +1,143 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::{debug, info};
+
+use crate::cli::ConfigCommand;
+use crate::settings::Settings;
+
+/// Handles the `noorg config` subcommand: viewing, editing, and patching
+/// `config.toml` without the user needing to know where it lives or
+/// hand-edit TOML.
+pub fn handle(action: ConfigCommand) -> io::Result<()> {
+    match action {
+        ConfigCommand::Path => show_path(),
+        ConfigCommand::Get { key } => get(&key),
+        ConfigCommand::Set { key, value } => set(&key, &value),
+        ConfigCommand::Edit => edit(),
+    }
+}
+
+fn show_path() -> io::Result<()> {
+    let config_path = Settings::config_path()?;
+    let data_dir = Settings::get_data_dir();
+    println!("config: {}", config_path.display());
+    println!("data:   {}", data_dir.display());
+    Ok(())
+}
+
+fn get(key: &str) -> io::Result<()> {
+    let config_path = Settings::config_path()?;
+    let value = read_toml(&config_path)?;
+    match navigate(&value, key) {
+        Some(v) => println!("{}", v),
+        None => println!("(not set)"),
+    }
+    Ok(())
+}
+
+/// Parses `raw_value` as a TOML literal where possible (so `true`, `42`, and
+/// `["a", "b"]` round-trip as their real types), falling back to a plain
+/// string for anything that doesn't parse (e.g. `noorg config set note_dir
+/// /tmp/vault`).
+fn set(key: &str, raw_value: &str) -> io::Result<()> {
+    let config_path = Settings::config_path()?;
+    let mut value = read_toml(&config_path)?;
+
+    let parsed = raw_value
+        .parse::<toml::Value>()
+        .unwrap_or_else(|_| toml::Value::String(raw_value.to_string()));
+    set_path(&mut value, key, parsed)?;
+
+    // Round-trip through `Settings` so a bad key or type mismatch is caught
+    // now, rather than silently corrupting the config.
+    value.clone().try_into::<Settings>().map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("setting '{}' would produce an invalid config: {}", key, e),
+        )
+    })?;
+
+    let serialized = toml::to_string_pretty(&value)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    write_atomically(&config_path, &serialized)?;
+
+    info!("Set {} = {}", key, raw_value);
+    Ok(())
+}
+
+fn edit() -> io::Result<()> {
+    let config_path = Settings::config_path()?;
+
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| default_editor().to_string());
+
+    debug!("Opening {:?} with {}", config_path, editor);
+    let status = Command::new(&editor).arg(&config_path).status()?;
+
+    if !status.success() {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!("editor '{}' exited with a non-zero status", editor),
+        ));
+    }
+
+    Ok(())
+}
+
+fn default_editor() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "notepad"
+    } else {
+        "vi"
+    }
+}
+
+fn read_toml(path: &Path) -> io::Result<toml::Value> {
+    let content = fs::read_to_string(path)?;
+    content
+        .parse::<toml::Value>()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+}
+
+fn navigate<'a>(value: &'a toml::Value, key: &str) -> Option<&'a toml::Value> {
+    let mut current = value;
+    for part in key.split('.') {
+        current = current.as_table()?.get(part)?;
+    }
+    Some(current)
+}
+
+fn set_path(value: &mut toml::Value, key: &str, new_value: toml::Value) -> io::Result<()> {
+    let parts: Vec<&str> = key.split('.').collect();
+    let mut current = value;
+
+    for part in &parts[..parts.len() - 1] {
+        let table = current.as_table_mut().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{}' is not a table", part),
+            )
+        })?;
+        if !table.contains_key(*part) {
+            table.insert(part.to_string(), toml::Value::Table(Default::default()));
+        }
+        current = table.get_mut(*part).unwrap();
+    }
+
+    let table = current.as_table_mut().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "target key is not a table")
+    })?;
+    table.insert(parts[parts.len() - 1].to_string(), new_value);
+    Ok(())
+}
+
+fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    let tmp_path = path.with_extension("toml.tmp");
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use rusqlite::Connection;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info};
+
+use crate::settings::Settings;
+
+/// An ordered, idempotent schema change. `version` must be applied in
+/// ascending order; each migration runs once, tracked via `PRAGMA user_version`.
+struct Migration {
+    version: i64,
+    sql: &'static str,
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        sql: "CREATE TABLE IF NOT EXISTS notes (
+                id INTEGER PRIMARY KEY,
+                title TEXT UNIQUE NOT NULL,
+                path TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS frontmatter (
+                file_id INTEGER,
+                key TEXT,
+                value TEXT,
+                PRIMARY KEY (file_id, key),
+                FOREIGN KEY (file_id) REFERENCES notes(id)
+            );",
+    },
+    Migration {
+        version: 2,
+        sql: "CREATE TABLE IF NOT EXISTS schema_migrations (
+                version INTEGER PRIMARY KEY,
+                applied_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );",
+    },
+    Migration {
+        version: 3,
+        sql: "CREATE VIRTUAL TABLE IF NOT EXISTS notes_fts USING fts5(title, body, content='');",
+    },
+];
+
+/// Exposed for `SqliteObserver::register_functions`, which needs a second,
+/// independent connection to the same database to run lookups from inside a
+/// scalar function callback without re-entering the shared `Arc<Mutex<Connection>>`.
+pub(crate) fn db_path(settings: &Settings) -> PathBuf {
+    let data_dir = Settings::get_data_dir().join("sqlite");
+    std::fs::create_dir_all(&data_dir).ok();
+    data_dir.join("frontmatter.db")
+}
+
+/// Opens the shared SQLite connection at [`db_path`], running any
+/// migrations that haven't yet been applied (tracked via `PRAGMA user_version`).
+pub fn open_connection(settings: &Settings) -> io::Result<Arc<Mutex<Connection>>> {
+    let path = db_path(settings);
+    debug!("Opening shared database connection at {:?}", path);
+
+    let mut conn = Connection::open(&path).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    run_migrations(&mut conn)?;
+
+    Ok(Arc::new(Mutex::new(conn)))
+}
+
+fn run_migrations(conn: &mut Connection) -> io::Result<()> {
+    let current_version: i64 = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+    for migration in MIGRATIONS {
+        if migration.version <= current_version {
+            continue;
+        }
+
+        debug!("Applying migration {}", migration.version);
+        let tx = conn
+            .transaction()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        tx.execute_batch(migration.sql)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        tx.execute_batch(&format!("PRAGMA user_version = {}", migration.version))
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        tx.commit().map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        info!("✨ Applied schema migration {}", migration.version);
+    }
+
+    Ok(())
+}
@@ -2,34 +2,56 @@ use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use std::env;
 use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::thread;
 use std::time::Duration;
 use tempfile::NamedTempFile;
 use tracing::{debug, error, info, warn};
 
-use crate::settings::Settings;
+use crate::error::{Error, Result};
+use crate::settings::{EditorCommand, Settings};
 
-pub fn open_editor(initial_content: &str, settings: &Settings) -> io::Result<String> {
+/// Resolves the command to spawn for editing a note: `settings.editor` takes
+/// precedence (so a vault can pin e.g. `"nvim +star"` or `["code", "--wait"]`
+/// in its config), then `$VISUAL`, then `$EDITOR`, then a scan of common
+/// editors, matching the precedence jj and meli use for composing content.
+fn resolve_editor(settings: &Settings) -> (String, Vec<String>) {
+    if let Some(editor) = &settings.editor {
+        debug!("Using configured editor: {:?}", editor);
+        return editor.program_and_args();
+    }
+
+    for var in ["VISUAL", "EDITOR"] {
+        if let Ok(value) = env::var(var) {
+            if !value.trim().is_empty() {
+                debug!("Using ${} as editor: {}", var, value);
+                return EditorCommand::Shell(value).program_and_args();
+            }
+        }
+    }
+
+    debug!("No editor configured, checking common editors");
+    for editor in ["nvim", "vim", "nano"] {
+        if command_exists(editor) {
+            debug!("Found editor: {}", editor);
+            return (editor.to_string(), Vec::new());
+        }
+    }
+
+    warn!("No common editors found, defaulting to vim");
+    ("vim".to_string(), Vec::new())
+}
+
+pub fn open_editor(initial_content: &str, settings: &Settings) -> Result<String> {
     debug!(
         "Opening editor with {} bytes of initial content",
         initial_content.len()
     );
 
-    let editor = env::var("EDITOR").unwrap_or_else(|_| {
-        debug!("No EDITOR environment variable found, checking common editors");
-        for editor in ["nvim", "vim", "nano"] {
-            if command_exists(editor) {
-                debug!("Found editor: {}", editor);
-                return editor.to_string();
-            }
-        }
-        warn!("No common editors found, defaulting to vim");
-        "vim".to_string()
-    });
+    let (program, args) = resolve_editor(settings);
 
-    if editor.to_lowercase() == "obsidian" {
+    if program.to_lowercase() == "obsidian" {
         debug!("Using Obsidian as editor");
         return open_in_obsidian(initial_content, settings);
     }
@@ -37,48 +59,101 @@ pub fn open_editor(initial_content: &str, settings: &Settings) -> io::Result<Str
     debug!("Creating temporary file for editing");
     let temp_file = NamedTempFile::new().map_err(|e| {
         error!("Failed to create temporary file: {}", e);
-        io::Error::new(io::ErrorKind::Other, e)
+        Error::Io {
+            path: env::temp_dir(),
+            source: e,
+        }
     })?;
 
     if !initial_content.is_empty() {
         debug!("Writing initial content to temporary file");
         fs::write(&temp_file, initial_content).map_err(|e| {
             error!("Failed to write initial content: {}", e);
-            e
+            Error::Io {
+                path: temp_file.path().to_path_buf(),
+                source: e,
+            }
         })?;
     }
 
-    info!("🖊️ Opening {} editor", editor);
-    let result = Command::new(&editor)
+    info!("🖊️ Opening {} editor", program);
+    let result = Command::new(&program)
+        .args(&args)
         .arg(temp_file.path())
         .status()
         .map_err(|e| {
-            error!("Failed to open editor '{}': {}", editor, e);
-            io::Error::new(
-                io::ErrorKind::NotFound,
-                format!("Failed to open editor '{}': {}. Please ensure it's installed or set a different editor using the EDITOR environment variable.", editor, e)
-            )
+            error!("Failed to open editor '{}': {}", program, e);
+            Error::Subprocess {
+                program: program.clone(),
+                source: e,
+            }
         })?;
 
     if !result.success() {
-        error!("Editor '{}' returned non-zero status", editor);
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            format!("Editor '{}' returned non-zero status", editor),
-        ));
+        error!("Editor '{}' returned non-zero status", program);
+        return Err(Error::EditorExit {
+            editor: program.clone(),
+            code: result.code(),
+        });
     }
 
     debug!("Reading edited content from temporary file");
     let content = fs::read_to_string(temp_file.path()).map_err(|e| {
         error!("Failed to read edited content: {}", e);
-        e
+        Error::Io {
+            path: temp_file.path().to_path_buf(),
+            source: e,
+        }
     })?;
 
+    if is_unchanged(initial_content, &content) {
+        info!("Editor closed with no meaningful changes, aborting");
+        return Err(Error::Aborted);
+    }
+
     info!("✨ Editor closed successfully");
     Ok(content)
 }
 
-fn open_in_obsidian(initial_content: &str, settings: &Settings) -> io::Result<String> {
+/// Opens an existing note file directly in the user's configured editor and
+/// waits for it to exit. Unlike [`open_editor`], which drafts into a
+/// throwaway temp file for `Command::Add`, this operates on the note's real
+/// path in place - used by the tray's fuzzy picker, where the note already
+/// exists on disk and any edits should land straight back in it.
+pub fn open_note_file(path: &Path, settings: &Settings) -> Result<()> {
+    let (program, args) = resolve_editor(settings);
+
+    info!("🖊️ Opening {} in {}", path.display(), program);
+    let result = Command::new(&program)
+        .args(&args)
+        .arg(path)
+        .status()
+        .map_err(|e| {
+            error!("Failed to open editor '{}': {}", program, e);
+            Error::Subprocess {
+                program: program.clone(),
+                source: e,
+            }
+        })?;
+
+    if !result.success() {
+        error!("Editor '{}' returned non-zero status", program);
+        return Err(Error::EditorExit {
+            editor: program.clone(),
+            code: result.code(),
+        });
+    }
+
+    Ok(())
+}
+
+/// True if the editor buffer is empty after trimming, or identical to the
+/// content the editor was opened with.
+fn is_unchanged(initial_content: &str, content: &str) -> bool {
+    content.trim().is_empty() || content == initial_content
+}
+
+fn open_in_obsidian(initial_content: &str, settings: &Settings) -> Result<String> {
     debug!("Opening note in Obsidian");
     let notes_dir = settings.obsidian_vault_path.clone().unwrap_or_else(|| {
         warn!("No Obsidian vault path found in config, using default path");
@@ -89,17 +164,20 @@ fn open_in_obsidian(initial_content: &str, settings: &Settings) -> io::Result<St
     let notes_path = PathBuf::from(&notes_dir);
     if !notes_path.exists() {
         error!("Obsidian vault directory not found: {}", notes_dir);
-        return Err(io::Error::new(
-            io::ErrorKind::NotFound,
-            format!("Obsidian vault directory not found: {}", notes_dir),
-        ));
+        return Err(Error::Io {
+            path: notes_path,
+            source: io::Error::new(io::ErrorKind::NotFound, "Obsidian vault directory not found"),
+        });
     }
 
     debug!("Creating temporary directory for Obsidian");
     let temp_dir = notes_path.join("_temp");
     fs::create_dir_all(&temp_dir).map_err(|e| {
         error!("Failed to create temp directory: {}", e);
-        e
+        Error::Io {
+            path: temp_dir.clone(),
+            source: e,
+        }
     })?;
 
     let temp_filename = format!("temp_{}.md", chrono::Utc::now().timestamp());
@@ -110,7 +188,10 @@ fn open_in_obsidian(initial_content: &str, settings: &Settings) -> io::Result<St
         debug!("Writing initial content to temporary file");
         fs::write(&temp_path, initial_content).map_err(|e| {
             error!("Failed to write initial content: {}", e);
-            e
+            Error::Io {
+                path: temp_path.clone(),
+                source: e,
+            }
         })?;
     }
 
@@ -126,15 +207,18 @@ fn open_in_obsidian(initial_content: &str, settings: &Settings) -> io::Result<St
     }
     .map_err(|e| {
         error!("Failed to launch Obsidian: {}", e);
-        e
+        Error::Subprocess {
+            program: "obsidian://open".to_string(),
+            source: e,
+        }
     })?;
 
     if !launch_status.success() {
         error!("Failed to launch Obsidian");
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            "Failed to launch Obsidian",
-        ));
+        return Err(Error::EditorExit {
+            editor: "obsidian".to_string(),
+            code: launch_status.code(),
+        });
     }
 
     debug!("Waiting for Obsidian to start...");
@@ -142,7 +226,10 @@ fn open_in_obsidian(initial_content: &str, settings: &Settings) -> io::Result<St
 
     let absolute_path = temp_path.canonicalize().map_err(|e| {
         error!("Failed to get absolute path: {}", e);
-        e
+        Error::Io {
+            path: temp_path.clone(),
+            source: e,
+        }
     })?;
     let path_str = absolute_path.to_string_lossy();
     let encoded_path = utf8_percent_encode(&path_str, NON_ALPHANUMERIC).to_string();
@@ -161,25 +248,34 @@ fn open_in_obsidian(initial_content: &str, settings: &Settings) -> io::Result<St
     }
     .map_err(|e| {
         error!("Failed to open note in Obsidian: {}", e);
-        e
+        Error::Subprocess {
+            program: obsidian_url.clone(),
+            source: e,
+        }
     })?;
 
     if !status.success() {
         error!("Failed to open note in Obsidian");
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            "Failed to open note in Obsidian",
-        ));
+        return Err(Error::EditorExit {
+            editor: "obsidian".to_string(),
+            code: status.code(),
+        });
     }
 
     info!("📝 Note opened in Obsidian. Press Enter when you're done editing...");
     let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
+    io::stdin().read_line(&mut input).map_err(|e| Error::Io {
+        path: temp_path.clone(),
+        source: e,
+    })?;
 
     debug!("Reading edited content");
     let content = fs::read_to_string(&temp_path).map_err(|e| {
         error!("Failed to read edited content: {}", e);
-        e
+        Error::Io {
+            path: temp_path.clone(),
+            source: e,
+        }
     })?;
 
     debug!("Cleaning up temporary files");
@@ -190,6 +286,11 @@ fn open_in_obsidian(initial_content: &str, settings: &Settings) -> io::Result<St
         warn!("Failed to remove temporary directory: {}", e);
     }
 
+    if is_unchanged(initial_content, &content) {
+        info!("Obsidian note closed with no meaningful changes, aborting");
+        return Err(Error::Aborted);
+    }
+
     info!("✨ Successfully saved changes from Obsidian");
     Ok(content)
 }
@@ -0,0 +1,66 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Crate-level error for the editor/subprocess layer (`editor.rs`,
+/// `window_manager.rs`), which previously collapsed every failure into a
+/// stringly-typed `io::Error`, losing which path or which subprocess failed.
+#[derive(Debug)]
+pub enum Error {
+    /// A filesystem operation on a specific path failed.
+    Io { path: PathBuf, source: io::Error },
+    /// Spawning or waiting on a subprocess failed.
+    Subprocess { program: String, source: io::Error },
+    /// The editor process exited with a non-zero (or signal-terminated) status.
+    EditorExit { editor: String, code: Option<i32> },
+    /// The operation couldn't proceed because the watcher is mid-sync on the
+    /// target file, so the caller should treat this as transient contention
+    /// rather than a hard failure.
+    Busy,
+    /// The user closed the editor without making a meaningful edit (buffer
+    /// was empty, or unchanged from `initial_content`).
+    Aborted,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => {
+                write!(f, "I/O error on {}: {}", path.display(), source)
+            }
+            Error::Subprocess { program, source } => {
+                write!(f, "failed to run '{}': {}", program, source)
+            }
+            Error::EditorExit { editor, code } => match code {
+                Some(code) => write!(f, "editor '{}' exited with status {}", editor, code),
+                None => write!(f, "editor '{}' was terminated by a signal", editor),
+            },
+            Error::Busy => write!(f, "the note is currently being synced by the watcher"),
+            Error::Aborted => write!(f, "editing was aborted: no changes were made"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Subprocess { source, .. } => Some(source),
+            Error::EditorExit { .. } | Error::Busy | Error::Aborted => None,
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Io { ref source, .. } => io::Error::new(source.kind(), err.to_string()),
+            Error::Subprocess { ref source, .. } => io::Error::new(source.kind(), err.to_string()),
+            Error::EditorExit { .. } => io::Error::new(io::ErrorKind::Other, err.to_string()),
+            Error::Busy => io::Error::new(io::ErrorKind::WouldBlock, err.to_string()),
+            Error::Aborted => io::Error::new(io::ErrorKind::Interrupted, err.to_string()),
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -1,5 +1,5 @@
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::io;
 use std::pin::Pin;
@@ -19,19 +19,144 @@ pub enum NoteEvent {
         content: String,
         file_path: String,
         frontmatter: HashMap<String, String>,
+        /// Frontmatter as it was before this update, when known, so
+        /// observer dispatch can diff for changed keys (see
+        /// [`NoteEvent::changed_keys`]).
+        #[serde(default)]
+        previous_frontmatter: Option<HashMap<String, String>>,
     },
     Synced {
         title: String,
         content: String,
         file_path: String,
         frontmatter: HashMap<String, String>,
+        #[serde(default)]
+        previous_frontmatter: Option<HashMap<String, String>>,
     },
 }
 
-#[derive(Debug, Clone)]
+impl NoteEvent {
+    /// The event's current frontmatter, regardless of variant.
+    pub fn frontmatter(&self) -> &HashMap<String, String> {
+        match self {
+            NoteEvent::Created { frontmatter, .. }
+            | NoteEvent::Updated { frontmatter, .. }
+            | NoteEvent::Synced { frontmatter, .. } => frontmatter,
+        }
+    }
+
+    /// The event's content, regardless of variant.
+    pub fn content(&self) -> &str {
+        match self {
+            NoteEvent::Created { content, .. }
+            | NoteEvent::Updated { content, .. }
+            | NoteEvent::Synced { content, .. } => content,
+        }
+    }
+
+    /// The set of keys whose value changed, was added, or was removed
+    /// between `previous_frontmatter` (if any) and the event's current
+    /// frontmatter. `Created` events and updates with no known prior
+    /// frontmatter report every current key as changed, since there's
+    /// nothing to diff against.
+    pub fn changed_keys(&self) -> HashSet<String> {
+        let previous = match self {
+            NoteEvent::Updated {
+                previous_frontmatter,
+                ..
+            }
+            | NoteEvent::Synced {
+                previous_frontmatter,
+                ..
+            } => previous_frontmatter.as_ref(),
+            NoteEvent::Created { .. } => None,
+        };
+
+        let current = self.frontmatter();
+
+        match previous {
+            None => current.keys().cloned().collect(),
+            Some(previous) => {
+                let mut changed = HashSet::new();
+                for (key, value) in current {
+                    if previous.get(key) != Some(value) {
+                        changed.insert(key.clone());
+                    }
+                }
+                for key in previous.keys() {
+                    if !current.contains_key(key) {
+                        changed.insert(key.clone());
+                    }
+                }
+                changed
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct ObserverResult {
     pub metadata: Option<HashMap<String, String>>,
     pub content: Option<String>,
+    /// `CreateNote`/`DeleteNote`/`Message` actions collected from
+    /// [`ObserverAction`]s an observer emitted alongside its `UpdateSelf`
+    /// (which is folded into `metadata`/`content` above instead of kept
+    /// here - see [`ObserverResult::from_actions`]).
+    pub actions: Vec<ObserverAction>,
+}
+
+impl ObserverResult {
+    /// Builds an `ObserverResult` from a script's list of
+    /// [`ObserverAction`]s, folding any `UpdateSelf` entries into
+    /// `metadata`/`content` (later ones win, same as a plain Rust observer
+    /// returning a single result) and keeping the rest for
+    /// `NoteManager` to apply.
+    pub fn from_actions(actions: Vec<ObserverAction>) -> Self {
+        let mut result = ObserverResult::default();
+        for action in actions {
+            match action {
+                ObserverAction::UpdateSelf { metadata, content } => {
+                    if metadata.is_some() {
+                        result.metadata = metadata;
+                    }
+                    if content.is_some() {
+                        result.content = content;
+                    }
+                }
+                other => result.actions.push(other),
+            }
+        }
+        result
+    }
+}
+
+/// A single action an observer asks `NoteManager` to perform, returned by
+/// scripts as either a bare `{metadata, content}` object (treated as a
+/// single `UpdateSelf`, for backward compatibility with the original
+/// observer protocol) or a JSON array of these tagged by `type`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ObserverAction {
+    /// Mutate the note that triggered the event - the original (and still
+    /// default) observer behavior.
+    UpdateSelf {
+        #[serde(default)]
+        metadata: Option<HashMap<String, String>>,
+        #[serde(default)]
+        content: Option<String>,
+    },
+    /// Create a new, separate note.
+    CreateNote {
+        title: String,
+        content: String,
+        #[serde(default)]
+        frontmatter: HashMap<String, String>,
+    },
+    /// Delete another note by title.
+    DeleteNote { title: String },
+    /// Surface a message to the user at the given log level
+    /// (`error`/`warn`/`info`/`debug`; anything else logs as `info`).
+    Message { level: String, text: String },
 }
 
 pub trait NoteObserver: Send + Sync + 'static {
@@ -45,4 +170,73 @@ pub trait NoteObserver: Send + Sync + 'static {
         0
     }
     fn as_any(&self) -> &dyn Any;
+
+    /// Names of observers whose metadata this observer must see first -
+    /// e.g. `sqlite` depends on `tag_index` so it persists the
+    /// fully-merged metadata rather than an earlier draft. Used by
+    /// [`crate::observer_registry::ObserverRegistry::notify`] to compute a
+    /// topological dispatch order; a name with no registered observer is
+    /// simply ignored. Defaults to no dependencies.
+    fn depends_on(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// The frontmatter keys this observer cares about, used by
+    /// [`crate::observer_registry::ObserverRegistry::notify`] to skip
+    /// observers whose interests weren't touched by an event. `None` (the
+    /// default) means "every event", for observers that depend on content
+    /// or don't scope themselves to specific keys.
+    fn interested_keys(&self) -> Option<HashSet<String>> {
+        None
+    }
+
+    /// Runs a command this observer registered via its `cmd_owners` entry in
+    /// `ScriptLoader::load_observers` (see [`crate::observer_registry::ObserverRegistry::dispatch_command`]).
+    /// Rust observers don't expose commands, so the default rejects every name.
+    fn on_command(
+        &self,
+        name: &str,
+        _args: Vec<String>,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Option<ObserverResult>>> + Send + '_>> {
+        let message = format!(
+            "observer '{}' does not support command '{}'",
+            self.name(),
+            name
+        );
+        Box::pin(async move { Err(io::Error::new(io::ErrorKind::Unsupported, message)) })
+    }
+
+    /// Declares whether this observer only reads an event (no `metadata`/
+    /// `content` mutation in its returned `ObserverResult`) - e.g. indexers,
+    /// exporters, and link-extractors that record information elsewhere
+    /// without touching the note itself. Read-only observers for a given
+    /// event are dispatched concurrently since they can't interfere with
+    /// each other's input; anything that mutates content/metadata (the
+    /// default) still runs in sequence so later observers see earlier ones'
+    /// changes.
+    fn read_only(&self) -> bool {
+        false
+    }
+
+    /// Returns `Some(self)` for index-style observers that implement
+    /// [`Bucketable`], letting [`crate::observer_registry::ObserverRegistry::reindex`]
+    /// find them without widening every observer's storage type to know
+    /// about bucketing.
+    fn as_bucketable(&self) -> Option<&dyn Bucketable> {
+        None
+    }
+}
+
+/// Implemented by index-style observers (`tag_index` today; a future date
+/// index, say) to support a full rebuild from scratch via
+/// [`crate::observer_registry::ObserverRegistry::reindex`], instead of only
+/// ever mutating incrementally off live events. Given one note, `bucket_note`
+/// contributes that note's entries to whichever buckets it belongs in (one
+/// per tag, one per month, ...); `write_buckets` then folds every note's
+/// buckets - already merged by the registry - into the final index and
+/// writes it once, atomically.
+pub trait Bucketable: NoteObserver {
+    fn bucket_note(&self, event: &NoteEvent) -> HashMap<String, Vec<serde_json::Value>>;
+
+    fn write_buckets(&self, buckets: HashMap<String, Vec<serde_json::Value>>) -> io::Result<()>;
 }
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+const SNIPPET_CHARS: usize = 120;
+
+/// One term's token positions within a single note's searchable text, used
+/// by [`SearchIndex::search`]'s proximity ranking.
+#[derive(Debug, Clone, Default)]
+struct Posting {
+    positions: Vec<usize>,
+}
+
+/// The best match found so far for one query term against one note: how far
+/// off the closest indexed term was, whether that match was exact/prefix
+/// rather than fuzzy, and where it occurred.
+#[derive(Debug, Clone)]
+struct MatchedTerm {
+    distance: usize,
+    exact: bool,
+    positions: Vec<usize>,
+}
+
+/// The bucket-sort key a note is ranked by: each field is compared in turn,
+/// only falling through to the next one on a tie. Higher is better for
+/// `distinct_terms` and `exact_matches`; lower is better for `total_typos`
+/// and `proximity`.
+#[derive(Debug, Clone, Copy)]
+struct RankKey {
+    distinct_terms: usize,
+    total_typos: usize,
+    proximity: usize,
+    exact_matches: usize,
+}
+
+fn rank_order(a: &RankKey, b: &RankKey) -> std::cmp::Ordering {
+    b.distinct_terms
+        .cmp(&a.distinct_terms)
+        .then_with(|| a.total_typos.cmp(&b.total_typos))
+        .then_with(|| a.proximity.cmp(&b.proximity))
+        .then_with(|| b.exact_matches.cmp(&a.exact_matches))
+}
+
+/// One ranked search result: the matching note's title and a short excerpt
+/// of its indexed text.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub title: String,
+    pub snippet: String,
+}
+
+/// In-memory inverted index over note title/frontmatter/body text, used by
+/// [`crate::note::NoteManager::search`] for typo-tolerant ranked retrieval.
+/// Built fresh from the notes directory rather than persisted to disk -
+/// cheap enough to rebuild lazily on first search and again after every
+/// `sync_notes`, and simpler than keeping a sidecar file in sync.
+///
+/// Ranking is a bucket sort of successive tie-breakers rather than a single
+/// blended score, so "matched more of the query's words" always outranks
+/// "had a smaller typo", which in turn always outranks "words appeared
+/// closer together":
+/// 1. number of distinct query terms matched (more is better)
+/// 2. total Levenshtein distance across those matches (fewer edits is
+///    better; terms under 5 characters must match exactly)
+/// 3. how tightly the matched terms cluster in the document (smaller gap
+///    between their first occurrences is better)
+/// 4. how many of those matches were exact/prefix rather than fuzzy (more
+///    is better)
+#[derive(Default)]
+pub struct SearchIndex {
+    postings: HashMap<String, HashMap<String, Posting>>,
+    snippets: HashMap<String, String>,
+}
+
+impl SearchIndex {
+    /// Builds an index from `documents`, each a note's `(title,
+    /// searchable_text)` pair. `searchable_text` is expected to already fold
+    /// in whatever should be searchable - title, frontmatter values, body -
+    /// since the index itself doesn't know about note structure.
+    pub fn build(documents: &[(String, String)]) -> Self {
+        let mut postings: HashMap<String, HashMap<String, Posting>> = HashMap::new();
+        let mut snippets = HashMap::new();
+
+        for (title, text) in documents {
+            for (position, token) in tokenize(text).into_iter().enumerate() {
+                postings
+                    .entry(token)
+                    .or_default()
+                    .entry(title.clone())
+                    .or_default()
+                    .positions
+                    .push(position);
+            }
+            snippets.insert(title.clone(), snippet_of(text));
+        }
+
+        Self { postings, snippets }
+    }
+
+    /// Returns up to `limit` notes matching `query`, ranked as described on
+    /// [`SearchIndex`]. Query terms under 5 characters only match exactly;
+    /// 5-8 character terms tolerate a 1-edit typo; 9+ character terms
+    /// tolerate 2.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let query_terms = tokenize(query);
+        if query_terms.is_empty() {
+            return Vec::new();
+        }
+
+        let mut matches: HashMap<String, HashMap<String, MatchedTerm>> = HashMap::new();
+
+        for query_term in &query_terms {
+            let budget = typo_budget(query_term);
+            for (indexed_term, postings_by_title) in &self.postings {
+                let distance = if indexed_term == query_term {
+                    0
+                } else if budget > 0 {
+                    match levenshtein_within(query_term, indexed_term, budget) {
+                        Some(distance) => distance,
+                        None => continue,
+                    }
+                } else {
+                    continue;
+                };
+                let exact = distance == 0 || indexed_term.starts_with(query_term.as_str());
+
+                for (title, posting) in postings_by_title {
+                    let per_title = matches.entry(title.clone()).or_default();
+                    let is_better = per_title
+                        .get(query_term)
+                        .map_or(true, |existing| distance < existing.distance);
+                    if is_better {
+                        per_title.insert(
+                            query_term.clone(),
+                            MatchedTerm {
+                                distance,
+                                exact,
+                                positions: posting.positions.clone(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        let mut ranked: Vec<(String, RankKey)> = matches
+            .into_iter()
+            .map(|(title, term_matches)| {
+                let key = RankKey {
+                    distinct_terms: term_matches.len(),
+                    total_typos: term_matches.values().map(|m| m.distance).sum(),
+                    exact_matches: term_matches.values().filter(|m| m.exact).count(),
+                    proximity: max_gap(term_matches.values()),
+                };
+                (title, key)
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| rank_order(&a.1, &b.1));
+
+        ranked
+            .into_iter()
+            .take(limit)
+            .map(|(title, _)| SearchHit {
+                snippet: self.snippets.get(&title).cloned().unwrap_or_default(),
+                title,
+            })
+            .collect()
+    }
+}
+
+/// The spread between the earliest first occurrence of each matched term -
+/// smaller means the query's words appeared closer together in the
+/// document. A single matched term, or none, has no spread.
+fn max_gap<'a>(term_matches: impl Iterator<Item = &'a MatchedTerm>) -> usize {
+    let first_positions: Vec<usize> = term_matches
+        .filter_map(|m| m.positions.iter().copied().min())
+        .collect();
+    match (first_positions.iter().min(), first_positions.iter().max()) {
+        (Some(min), Some(max)) => max - min,
+        _ => 0,
+    }
+}
+
+/// The maximum Levenshtein distance a query term of this length is allowed
+/// to fuzzy-match with: exact-only below 5 characters, 1 edit from 5-8, 2
+/// edits from 9 up.
+fn typo_budget(term: &str) -> usize {
+    match term.chars().count() {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    }
+}
+
+/// Levenshtein distance between `a` and `b`, bailing out early with `None`
+/// as soon as it's certain the result would exceed `max_distance` - both the
+/// length-difference check up front and the per-row minimum check keep this
+/// cheap even though the index is scanned one indexed term at a time.
+fn levenshtein_within(a: &str, b: &str, max_distance: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.len().abs_diff(b.len()) > max_distance {
+        return None;
+    }
+
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current_row = vec![i + 1];
+        let mut row_min = current_row[0];
+        for (j, b_char) in b.iter().enumerate() {
+            let cost = if a_char == b_char { 0 } else { 1 };
+            let value = (previous_row[j + 1] + 1)
+                .min(current_row[j] + 1)
+                .min(previous_row[j] + cost);
+            row_min = row_min.min(value);
+            current_row.push(value);
+        }
+        if row_min > max_distance {
+            return None;
+        }
+        previous_row = current_row;
+    }
+
+    let distance = previous_row[b.len()];
+    (distance <= max_distance).then_some(distance)
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| term.to_string())
+        .collect()
+}
+
+/// A short excerpt of `text` for display alongside a search hit.
+fn snippet_of(text: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.chars().count() <= SNIPPET_CHARS {
+        return trimmed.to_string();
+    }
+    let truncated: String = trimmed.chars().take(SNIPPET_CHARS).collect();
+    format!("{}...", truncated.trim_end())
+}
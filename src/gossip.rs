@@ -0,0 +1,333 @@
+use std::collections::HashSet;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use tokio::net::UdpSocket;
+use tracing::{debug, error, info, warn};
+
+use crate::note::NoteManager;
+use crate::observer_registry::ObserverRegistry;
+use crate::postprocessor_registry::PostprocessorRegistry;
+use crate::settings::Settings;
+
+/// A note's hash and recency as advertised to a peer - the unit a
+/// `GossipMessage::Digest` carries. Recency is `updated_at` rather than
+/// `created_at`, since `created_at` never changes after a note's first save.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NoteDigest {
+    title: String,
+    hash: String,
+    updated_at: String,
+}
+
+/// Wire format exchanged over the gossip UDP socket, JSON-encoded per
+/// datagram. A `Digest` advertises what a peer has; a mismatch triggers a
+/// `Request` for the full body, answered with a `Response`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum GossipMessage {
+    Digest(Vec<NoteDigest>),
+    Request { title: String },
+    Response {
+        title: String,
+        content: String,
+        frontmatter: std::collections::HashMap<String, String>,
+    },
+}
+
+/// Simple membership table: peers are learned automatically from incoming
+/// datagrams and dropped if a send to them fails, rather than through an
+/// explicit join/leave sub-protocol.
+struct PeerTable {
+    peers: Mutex<HashSet<String>>,
+}
+
+impl PeerTable {
+    fn new(seeds: &[String]) -> Self {
+        PeerTable {
+            peers: Mutex::new(seeds.iter().cloned().collect()),
+        }
+    }
+
+    fn add(&self, peer: String) {
+        if self.peers.lock().unwrap().insert(peer.clone()) {
+            debug!("Gossip: learned new peer {}", peer);
+        }
+    }
+
+    fn remove(&self, peer: &str) {
+        if self.peers.lock().unwrap().remove(peer) {
+            warn!("Gossip: dropping unreachable peer {}", peer);
+        }
+    }
+
+    /// All peers if there are three or fewer, otherwise a random third of
+    /// them (at least one).
+    fn sample_targets(&self) -> Vec<String> {
+        let peers: Vec<String> = self.peers.lock().unwrap().iter().cloned().collect();
+        if peers.len() <= 3 {
+            return peers;
+        }
+
+        let sample_size = (peers.len() / 3).max(1);
+        peers
+            .choose_multiple(&mut rand::thread_rng(), sample_size)
+            .cloned()
+            .collect()
+    }
+}
+
+async fn send_digest(socket: &UdpSocket, peer_table: &PeerTable, note_manager: &NoteManager) {
+    let digest: Vec<NoteDigest> = match note_manager.local_digest() {
+        Ok(entries) => entries
+            .into_iter()
+            .map(|(title, hash, updated_at)| NoteDigest {
+                title,
+                hash,
+                updated_at,
+            })
+            .collect(),
+        Err(e) => {
+            error!("Gossip: failed to build local digest: {}", e);
+            return;
+        }
+    };
+
+    let message = GossipMessage::Digest(digest);
+    let payload = match serde_json::to_vec(&message) {
+        Ok(payload) => payload,
+        Err(e) => {
+            error!("Gossip: failed to encode digest: {}", e);
+            return;
+        }
+    };
+
+    for peer in peer_table.sample_targets() {
+        if let Err(e) = socket.send_to(&payload, &peer).await {
+            warn!("Gossip: failed to send digest to {}: {}", peer, e);
+            peer_table.remove(&peer);
+        }
+    }
+}
+
+async fn handle_digest(
+    socket: &UdpSocket,
+    peer: &str,
+    digests: Vec<NoteDigest>,
+    note_manager: &NoteManager,
+) {
+    let local: std::collections::HashMap<String, (String, String)> = match note_manager
+        .local_digest()
+    {
+        Ok(entries) => entries
+            .into_iter()
+            .map(|(title, hash, updated_at)| (title, (hash, updated_at)))
+            .collect(),
+        Err(e) => {
+            error!("Gossip: failed to read local digest: {}", e);
+            return;
+        }
+    };
+
+    for remote in digests {
+        let wants_it = match local.get(&remote.title) {
+            Some((hash, updated_at)) => hash != &remote.hash && updated_at <= &remote.updated_at,
+            None => true,
+        };
+
+        if !wants_it {
+            continue;
+        }
+
+        let message = GossipMessage::Request {
+            title: remote.title.clone(),
+        };
+        match serde_json::to_vec(&message) {
+            Ok(payload) => {
+                if let Err(e) = socket.send_to(&payload, peer).await {
+                    warn!("Gossip: failed to request '{}' from {}: {}", remote.title, peer, e);
+                }
+            }
+            Err(e) => error!("Gossip: failed to encode request: {}", e),
+        }
+    }
+}
+
+/// Rejects titles containing path-traversal or separator characters before
+/// they ever reach a path-building helper. `title` arrives over an
+/// unauthenticated UDP socket in `handle_request`/`handle_response`, so this
+/// runs here in addition to the sanitization `get_absolute_note_path` already
+/// does itself, purely to log and drop the attempt at the untrusted boundary
+/// rather than silently rewriting it.
+fn is_safe_title(title: &str) -> bool {
+    !title.contains(['/', '\\']) && !title.contains("..")
+}
+
+async fn handle_request(socket: &UdpSocket, peer: &str, title: &str, note_manager: &NoteManager) {
+    if !is_safe_title(title) {
+        warn!("Gossip: peer {} requested unsafe title '{}', dropping", peer, title);
+        return;
+    }
+
+    let note = match note_manager.get_note(title) {
+        Ok(Some(note)) => note,
+        Ok(None) => {
+            debug!("Gossip: peer {} requested unknown note '{}'", peer, title);
+            return;
+        }
+        Err(e) => {
+            error!("Gossip: failed to read '{}' for peer {}: {}", title, peer, e);
+            return;
+        }
+    };
+
+    let message = GossipMessage::Response {
+        title: title.to_string(),
+        content: note.0,
+        frontmatter: note.1,
+    };
+    match serde_json::to_vec(&message) {
+        Ok(payload) => {
+            if let Err(e) = socket.send_to(&payload, peer).await {
+                warn!("Gossip: failed to send '{}' to {}: {}", title, peer, e);
+            }
+        }
+        Err(e) => error!("Gossip: failed to encode response: {}", e),
+    }
+}
+
+async fn handle_response(
+    peer: &str,
+    title: String,
+    content: String,
+    frontmatter: std::collections::HashMap<String, String>,
+    note_manager: &NoteManager,
+) {
+    if !is_safe_title(&title) {
+        warn!("Gossip: peer {} sent unsafe title '{}', dropping", peer, title);
+        return;
+    }
+
+    let remote_updated_at = frontmatter.get("updated_at").cloned().unwrap_or_default();
+    let local = match note_manager.get_note(&title) {
+        Ok(local) => local,
+        Err(e) => {
+            error!("Gossip: failed to read local copy of '{}': {}", title, e);
+            return;
+        }
+    };
+
+    let conflict = match &local {
+        Some((local_content, local_frontmatter)) => {
+            let local_updated_at = local_frontmatter
+                .get("updated_at")
+                .cloned()
+                .unwrap_or_default();
+            let local_hash = NoteManager::calculate_content_hash(local_content);
+            let remote_hash = NoteManager::calculate_content_hash(&content);
+            local_hash != remote_hash && local_updated_at == remote_updated_at
+        }
+        None => false,
+    };
+
+    let result = if conflict {
+        note_manager
+            .save_conflict_note(&title, content, frontmatter, peer)
+            .await
+    } else {
+        note_manager.apply_remote_note(&title, content, frontmatter).await
+    };
+
+    if let Err(e) = result {
+        error!("Gossip: failed to apply note '{}' from {}: {}", title, peer, e);
+    }
+}
+
+async fn handle_datagram(
+    socket: &UdpSocket,
+    peer: &str,
+    buf: &[u8],
+    peer_table: &PeerTable,
+    note_manager: &NoteManager,
+) {
+    peer_table.add(peer.to_string());
+
+    let message: GossipMessage = match serde_json::from_slice(buf) {
+        Ok(message) => message,
+        Err(e) => {
+            warn!("Gossip: dropping malformed datagram from {}: {}", peer, e);
+            return;
+        }
+    };
+
+    match message {
+        GossipMessage::Digest(digests) => {
+            handle_digest(socket, peer, digests, note_manager).await
+        }
+        GossipMessage::Request { title } => {
+            handle_request(socket, peer, &title, note_manager).await
+        }
+        GossipMessage::Response {
+            title,
+            content,
+            frontmatter,
+        } => handle_response(peer, title, content, frontmatter, note_manager).await,
+    }
+}
+
+/// Runs the gossip daemon: periodically advertises this vault's note
+/// digests to a sample of known peers, and answers/applies whatever comes
+/// back in over the same UDP socket, writing converged notes through the
+/// regular observer pipeline.
+pub async fn run(
+    settings: Settings,
+    observer_registry: Arc<ObserverRegistry>,
+    postprocessor_registry: Arc<PostprocessorRegistry>,
+    stop_signal: Arc<AtomicBool>,
+) -> io::Result<()> {
+    let note_manager = NoteManager::new_with_postprocessors(
+        settings.clone(),
+        observer_registry.clone(),
+        postprocessor_registry.clone(),
+    )
+    .await?;
+
+    let socket = UdpSocket::bind(&settings.gossip.bind_addr).await?;
+    info!("📡 Gossip listening on {}", settings.gossip.bind_addr);
+
+    let peer_table = Arc::new(PeerTable::new(&settings.gossip.peers));
+    let interval = Duration::from_secs(settings.gossip.interval_secs);
+    let stop_poll_interval = Duration::from_millis(100);
+    let mut buf = [0u8; 65536];
+
+    let mut next_advertise = tokio::time::Instant::now();
+
+    loop {
+        if stop_signal.load(Ordering::SeqCst) {
+            info!("Gossip stop signal received, shutting down");
+            break;
+        }
+
+        if tokio::time::Instant::now() >= next_advertise {
+            send_digest(&socket, &peer_table, &note_manager).await;
+            next_advertise = tokio::time::Instant::now() + interval;
+        }
+
+        tokio::select! {
+            result = socket.recv_from(&mut buf) => {
+                match result {
+                    Ok((len, addr)) => {
+                        handle_datagram(&socket, &addr.to_string(), &buf[..len], &peer_table, &note_manager).await;
+                    }
+                    Err(e) => error!("Gossip: failed to receive datagram: {}", e),
+                }
+            }
+            _ = tokio::time::sleep(stop_poll_interval) => continue,
+        }
+    }
+
+    Ok(())
+}
@@ -4,11 +4,16 @@ use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
-use crate::cli::Command;
+use crate::cli::{Command, TrackCommand};
 use crate::editor::open_editor;
+use crate::error::Error as NoorgError;
 use crate::note::NoteManager;
+use crate::observers::similar_notes::SimilarNotesObserver;
 use crate::observers::sqlite_store::SqliteObserver;
+use crate::observers::time_track::TimeTrackObserver;
+use crate::postprocessor_registry::PostprocessorRegistry;
 use crate::settings::Settings;
+use crate::gossip;
 use crate::watcher::watch_directory;
 use crate::{observer_registry::ObserverRegistry, observers};
 
@@ -16,10 +21,16 @@ pub async fn handle_command(
     command: Command,
     settings: Settings,
     observer_registry: Arc<ObserverRegistry>,
+    postprocessor_registry: Arc<PostprocessorRegistry>,
     stop_signal: Option<Arc<AtomicBool>>,
 ) -> io::Result<()> {
     debug!("Initializing note manager");
-    let note_manager = NoteManager::new(settings.clone(), observer_registry.clone()).await?;
+    let note_manager = NoteManager::new_with_postprocessors(
+        settings.clone(),
+        observer_registry.clone(),
+        postprocessor_registry.clone(),
+    )
+    .await?;
 
     match command {
         Command::List { from, to, filter } => {
@@ -48,6 +59,11 @@ pub async fn handle_command(
             );
             note_manager.list_notes_with_filter(from_date, to_date, filters)?;
         }
+        Command::Tree { filter } => {
+            debug!("Handling tree command with filters: {:?}", filter);
+            let filters: HashMap<String, String> = filter.into_iter().collect();
+            note_manager.list_tree(filters)?;
+        }
         Command::Add {
             title,
             body,
@@ -62,7 +78,14 @@ pub async fn handle_command(
                 }
                 None => {
                     info!("Opening editor for note content...");
-                    open_editor("", &settings)?
+                    match open_editor("", &settings) {
+                        Ok(content) => content,
+                        Err(NoorgError::Aborted) => {
+                            warn!("Note creation cancelled - editor buffer was empty or unchanged");
+                            return Ok(());
+                        }
+                        Err(e) => return Err(e.into()),
+                    }
                 }
             };
 
@@ -97,9 +120,14 @@ pub async fn handle_command(
         }
         Command::Delete { title } => {
             debug!("Handling delete command for note '{}'", title);
-            note_manager.delete_note(&title)?;
+            note_manager.delete_note(&title).await?;
             info!("🗑️ Note '{}' deleted successfully", title);
         }
+        Command::Rename { title, new_title } => {
+            debug!("Handling rename command: '{}' -> '{}'", title, new_title);
+            note_manager.rename_note(&title, &new_title).await?;
+            info!("🔀 Note '{}' renamed to '{}'", title, new_title);
+        }
         Command::Sync => {
             info!("🔄 Syncing all notes with observers...");
             note_manager.sync_notes().await?;
@@ -137,9 +165,99 @@ pub async fn handle_command(
                 info!("📊 Found {} notes", results.rows.len());
             }
         }
+        Command::Search { query, limit } => {
+            debug!("Handling search command: {}", query);
+            let observers = observer_registry.get_observers().await;
+            let similar_notes_observer = observers
+                .iter()
+                .find(|o| o.name() == "similar_notes")
+                .and_then(|o| o.as_any().downcast_ref::<SimilarNotesObserver>())
+                .ok_or_else(|| {
+                    error!("similar_notes observer not found in registry");
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        "similar_notes observer not found in registry (enable it in enabled_observers)",
+                    )
+                })?;
+
+            let results = similar_notes_observer.search(&query, limit).await?;
+            if results.is_empty() {
+                info!("No matching notes found");
+            } else {
+                for (rank, (title, path, score)) in results.iter().enumerate() {
+                    println!("{}. {} ({}) - {:.1}%", rank + 1, title, path, score * 100.0);
+                }
+                info!("🔎 Found {} notes", results.len());
+            }
+        }
+        Command::Reindex => {
+            info!("🔄 Reindexing vault...");
+            crate::reindex::run(&settings, observer_registry.clone()).await?;
+        }
+        Command::Ask { question, save } => {
+            debug!("Handling ask command: {}", question);
+            crate::ask_cmd::handle(&question, save, &settings, &note_manager).await?;
+        }
         Command::Watch => {
             let stop = stop_signal.unwrap_or_else(|| Arc::new(AtomicBool::new(false)));
-            watch_directory(settings, observer_registry, stop).await?
+            watch_directory(settings, observer_registry, postprocessor_registry, stop).await?
+        }
+        Command::Gossip => {
+            let stop = stop_signal.unwrap_or_else(|| Arc::new(AtomicBool::new(false)));
+            gossip::run(settings, observer_registry, postprocessor_registry, stop).await?
+        }
+        Command::Run { name, args } => {
+            debug!("Handling run command: {} {:?}", name, args);
+            match observer_registry.dispatch_command(&name, args).await {
+                Ok(Some(result)) => {
+                    if let Some(content) = result.content {
+                        println!("{}", content);
+                    }
+                    if let Some(metadata) = result.metadata {
+                        for (key, value) in metadata {
+                            println!("{}: {}", key, value);
+                        }
+                    }
+                }
+                Ok(None) => info!("Command '{}' completed with no output", name),
+                Err(e) => error!("Command '{}' failed: {}", name, e),
+            }
+        }
+        Command::Track { action } => {
+            debug!("Handling track command");
+            let observers = observer_registry.get_observers().await;
+            let time_track_observer = observers
+                .iter()
+                .find(|o| o.name() == "time_track")
+                .and_then(|o| o.as_any().downcast_ref::<TimeTrackObserver>())
+                .ok_or_else(|| {
+                    error!("time_track observer not found in registry");
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        "time_track observer not found in registry (enable it in enabled_observers)",
+                    )
+                })?;
+
+            match action {
+                TrackCommand::Start { title, at } => {
+                    time_track_observer.start(&title, at.as_deref()).await?;
+                }
+                TrackCommand::Stop { title, at } => {
+                    time_track_observer.stop(&title, at.as_deref()).await?;
+                }
+            }
+        }
+        Command::Config { action } => {
+            debug!("Handling config command");
+            crate::config_cmd::handle(action)?;
+        }
+        Command::Init {
+            path,
+            file_type,
+            force,
+        } => {
+            debug!("Handling init command");
+            crate::init_cmd::handle(path, file_type, force)?;
         }
     }
 
@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use rusqlite::{params, Connection};
+use tracing::{debug, error, info, warn};
+
+use crate::settings::Settings;
+
+/// Backs the content-hash cache `NoteManager::should_process_note` uses to
+/// skip unchanged notes during a sync. Modeled on cargo's
+/// `CacheStore`/`CacheManager` split: one trait, swappable backends,
+/// selected via `settings.hash_cache_backend`.
+pub trait HashCacheStore: Send + Sync {
+    /// The hash last recorded for `title`, if any.
+    fn get(&self, title: &str) -> io::Result<Option<String>>;
+    /// Records `title`'s new hash. Backends may buffer this until `flush`.
+    fn put(&self, title: &str, hash: String) -> io::Result<()>;
+    /// Persists any writes buffered by `put`. A no-op for backends that
+    /// write through immediately.
+    fn flush(&self) -> io::Result<()>;
+}
+
+/// Builds the configured `HashCacheStore` - `settings.hash_cache_backend ==
+/// "sqlite"` gets the indexed store, `"binary"` gets the versioned
+/// `bitcode`-encoded store, anything else falls back to the original
+/// single-file JSON cache.
+pub fn build(settings: &Settings) -> io::Result<Box<dyn HashCacheStore>> {
+    match settings.hash_cache_backend.as_str() {
+        "sqlite" => Ok(Box::new(SqliteHashCacheStore::new()?)),
+        "binary" => Ok(Box::new(BinaryHashCacheStore::new(
+            settings.hash_cache_compress,
+        ))),
+        _ => Ok(Box::new(JsonHashCacheStore::new())),
+    }
+}
+
+fn cache_path(file_name: &str) -> PathBuf {
+    Settings::get_data_dir().join(file_name)
+}
+
+/// The original cache: the whole `title -> hash` map is deserialized and
+/// rewritten on every `get`/`put`, so a sync over N notes does O(N) I/O per
+/// note. Kept as the default - no extra dependency, fine for small vaults.
+pub struct JsonHashCacheStore {
+    path: PathBuf,
+}
+
+impl JsonHashCacheStore {
+    pub fn new() -> Self {
+        Self {
+            path: cache_path("content_hashes.json"),
+        }
+    }
+
+    fn read_all(&self) -> HashMap<String, String> {
+        if let Some(parent) = self.path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                error!("Failed to create hash cache directory: {}", e);
+                return HashMap::new();
+            }
+        }
+
+        if !self.path.exists() {
+            debug!("No existing hash cache found");
+            return HashMap::new();
+        }
+
+        match fs::read_to_string(&self.path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                error!("Failed to parse hash cache: {}", e);
+                HashMap::new()
+            }),
+            Err(e) => {
+                error!("Failed to read hash cache file: {}", e);
+                HashMap::new()
+            }
+        }
+    }
+
+    fn write_all(&self, cache: &HashMap<String, String>) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let json = serde_json::to_string_pretty(cache)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        fs::write(&self.path, json)
+    }
+}
+
+impl HashCacheStore for JsonHashCacheStore {
+    fn get(&self, title: &str) -> io::Result<Option<String>> {
+        Ok(self.read_all().remove(title))
+    }
+
+    fn put(&self, title: &str, hash: String) -> io::Result<()> {
+        let mut cache = self.read_all();
+        cache.insert(title.to_string(), hash);
+        self.write_all(&cache)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A single indexed `SELECT` per lookup; writes are buffered in memory and
+/// flushed as one `INSERT ... ON CONFLICT DO UPDATE` transaction, so a full
+/// sync's hash-cache cost is O(1) per note plus a single commit instead of
+/// rewriting the whole cache file N times.
+pub struct SqliteHashCacheStore {
+    conn: Mutex<Connection>,
+    pending: Mutex<HashMap<String, String>>,
+}
+
+impl SqliteHashCacheStore {
+    pub fn new() -> io::Result<Self> {
+        let path = cache_path("content_hashes.db");
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(&path).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS content_hashes (
+                title TEXT PRIMARY KEY,
+                hash TEXT NOT NULL
+            );",
+        )
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        debug!("SqliteHashCacheStore opened at {:?}", path);
+        Ok(Self {
+            conn: Mutex::new(conn),
+            pending: Mutex::new(HashMap::new()),
+        })
+    }
+}
+
+impl HashCacheStore for SqliteHashCacheStore {
+    fn get(&self, title: &str) -> io::Result<Option<String>> {
+        if let Some(hash) = self.pending.lock().unwrap().get(title) {
+            return Ok(Some(hash.clone()));
+        }
+
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT hash FROM content_hashes WHERE title = ?1",
+            params![title],
+            |row| row.get(0),
+        )
+        .map(Some)
+        .or_else(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => Ok(None),
+            e => Err(io::Error::new(io::ErrorKind::Other, e)),
+        })
+    }
+
+    fn put(&self, title: &str, hash: String) -> io::Result<()> {
+        self.pending.lock().unwrap().insert(title.to_string(), hash);
+        Ok(())
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        let mut pending = self.pending.lock().unwrap();
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn
+            .transaction()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        for (title, hash) in pending.drain() {
+            tx.execute(
+                "INSERT INTO content_hashes (title, hash) VALUES (?1, ?2)
+                 ON CONFLICT(title) DO UPDATE SET hash = excluded.hash",
+                params![title, hash],
+            )
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        }
+
+        tx.commit()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        debug!("Flushed hash cache writes in a single transaction");
+        Ok(())
+    }
+}
+
+/// Bumped whenever the on-disk encoding or hashing scheme changes. A cache
+/// file stamped with a different version is discarded rather than read, so
+/// e.g. switching `calculate_content_hash` to a keyed hash can't be misread
+/// as "unchanged" against entries hashed the old way.
+const CACHE_VERSION: u32 = 1;
+
+/// A `bitcode`-encoded cache, optionally wrapped in `zstd` compression -
+/// smaller and faster to (de)serialize than the JSON store, at the cost of
+/// not being human-readable. Like `JsonHashCacheStore`, the whole map is
+/// read and rewritten per call; reach for `SqliteHashCacheStore` if per-note
+/// cost matters more than file size.
+pub struct BinaryHashCacheStore {
+    path: PathBuf,
+    compress: bool,
+}
+
+impl BinaryHashCacheStore {
+    pub fn new(compress: bool) -> Self {
+        Self {
+            path: cache_path("content_hashes.bin"),
+            compress,
+        }
+    }
+
+    fn read_all(&self) -> HashMap<String, String> {
+        if let Some(parent) = self.path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                error!("Failed to create hash cache directory: {}", e);
+                return HashMap::new();
+            }
+        }
+
+        if !self.path.exists() {
+            debug!("No existing hash cache found");
+            return HashMap::new();
+        }
+
+        let raw = match fs::read(&self.path) {
+            Ok(raw) => raw,
+            Err(e) => {
+                error!("Failed to read hash cache file: {}", e);
+                return HashMap::new();
+            }
+        };
+
+        if raw.len() < 4 {
+            warn!("Hash cache file is too short to contain a version stamp, discarding");
+            return HashMap::new();
+        }
+
+        let (version_bytes, body) = raw.split_at(4);
+        let version = u32::from_le_bytes(version_bytes.try_into().unwrap());
+        if version != CACHE_VERSION {
+            info!(
+                "Hash cache version {} does not match compiled-in version {}, discarding",
+                version, CACHE_VERSION
+            );
+            return HashMap::new();
+        }
+
+        let decoded = if self.compress {
+            match zstd::stream::decode_all(body) {
+                Ok(decoded) => decoded,
+                Err(e) => {
+                    error!("Failed to decompress hash cache: {}", e);
+                    return HashMap::new();
+                }
+            }
+        } else {
+            body.to_vec()
+        };
+
+        bitcode::decode(&decoded).unwrap_or_else(|e| {
+            error!("Failed to decode hash cache: {}", e);
+            HashMap::new()
+        })
+    }
+
+    fn write_all(&self, cache: &HashMap<String, String>) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let encoded = bitcode::encode(cache);
+        let body = if self.compress {
+            zstd::stream::encode_all(encoded.as_slice(), 0)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+        } else {
+            encoded
+        };
+
+        let mut out = Vec::with_capacity(4 + body.len());
+        out.extend_from_slice(&CACHE_VERSION.to_le_bytes());
+        out.extend_from_slice(&body);
+        fs::write(&self.path, out)
+    }
+}
+
+impl HashCacheStore for BinaryHashCacheStore {
+    fn get(&self, title: &str) -> io::Result<Option<String>> {
+        Ok(self.read_all().remove(title))
+    }
+
+    fn put(&self, title: &str, hash: String) -> io::Result<()> {
+        let mut cache = self.read_all();
+        cache.insert(title.to_string(), hash);
+        self.write_all(&cache)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
+
+use tokio::runtime::Handle;
+use tracing::error;
+
+use crate::note::NoteManager;
+use crate::observer_registry::ObserverRegistry;
+use crate::observers::sqlite_store::{QueryResult, SqliteObserver};
+
+/// Read-only store/note access exposed to Lua/Python observer scripts as a
+/// `noorg` host object (`noorg.query`, `noorg.get_note`, `noorg.list`), built
+/// once and shared by every `LuaObserver`/`PythonObserver` in the vault.
+/// Scripts run their `on_event`/`process_event` body on a blocking thread
+/// (see `script_loader`), so `query` bridges back to the async SQLite path
+/// via `Handle::block_on` rather than requiring `await`; `get_note` and
+/// `list` call synchronous `NoteManager` methods directly.
+#[derive(Clone)]
+pub struct HostApi {
+    note_manager: NoteManager,
+    observer_registry: Arc<ObserverRegistry>,
+    runtime: Handle,
+}
+
+impl HostApi {
+    pub fn new(note_manager: NoteManager, observer_registry: Arc<ObserverRegistry>, runtime: Handle) -> Self {
+        Self {
+            note_manager,
+            observer_registry,
+            runtime,
+        }
+    }
+
+    /// `noorg.get_note(title) -> {content, metadata}`.
+    pub fn get_note(&self, title: &str) -> io::Result<Option<(String, HashMap<String, String>)>> {
+        self.note_manager.get_note(title)
+    }
+
+    /// `noorg.list(filter) -> titles`.
+    pub fn list(&self, filters: HashMap<String, String>) -> io::Result<Vec<String>> {
+        self.note_manager.list_titles(&filters)
+    }
+
+    /// `noorg.query(sql) -> rows`, run against the SQLite observer's index
+    /// (if the vault has it enabled).
+    pub fn query(&self, sql: &str) -> io::Result<QueryResult> {
+        let observer_registry = self.observer_registry.clone();
+        let sql = sql.to_string();
+        self.runtime.block_on(async move {
+            let observers = observer_registry.get_observers().await;
+            let sqlite_observer = observers
+                .iter()
+                .find(|o| o.name() == "sqlite")
+                .and_then(|o| o.as_any().downcast_ref::<SqliteObserver>())
+                .ok_or_else(|| {
+                    error!("noorg.query called but the sqlite observer isn't enabled");
+                    io::Error::new(io::ErrorKind::NotFound, "sqlite observer not enabled")
+                })?;
+
+            sqlite_observer.query(&sql).await
+        })
+    }
+}
@@ -0,0 +1,60 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::Path;
+use tracing::{debug, warn};
+
+use crate::settings::Settings;
+
+/// Default glob patterns that are always ignored, regardless of `.noorgignore`
+/// contents, so Obsidian's own scratch directory never triggers a sync loop.
+const DEFAULT_IGNORES: &[&str] = &["_temp/", "_temp/**"];
+
+/// Compiled gitignore-style matcher for the notes vault. Combines a
+/// `.noorgignore` file at the vault root with a configurable list of
+/// patterns from `Settings`, using last-match-wins semantics (including
+/// `!` negation), same as `.gitignore`.
+pub struct IgnoreFilter {
+    matcher: Gitignore,
+}
+
+impl IgnoreFilter {
+    pub fn load(settings: &Settings) -> Self {
+        let note_dir = Path::new(&settings.note_dir);
+        let mut builder = GitignoreBuilder::new(note_dir);
+
+        for pattern in DEFAULT_IGNORES {
+            if let Err(e) = builder.add_line(None, pattern) {
+                warn!("Invalid built-in ignore pattern '{}': {}", pattern, e);
+            }
+        }
+
+        for pattern in &settings.ignore_patterns {
+            if let Err(e) = builder.add_line(None, pattern) {
+                warn!("Invalid ignore pattern '{}' in settings: {}", pattern, e);
+            }
+        }
+
+        let ignore_file = note_dir.join(".noorgignore");
+        if ignore_file.exists() {
+            debug!("Loading ignore patterns from {:?}", ignore_file);
+            if let Some(e) = builder.add(&ignore_file) {
+                warn!("Failed to load {:?}: {}", ignore_file, e);
+            }
+        }
+
+        let matcher = match builder.build() {
+            Ok(matcher) => matcher,
+            Err(e) => {
+                warn!("Failed to compile ignore patterns, ignoring none: {}", e);
+                GitignoreBuilder::new(note_dir).build().unwrap()
+            }
+        };
+
+        Self { matcher }
+    }
+
+    /// Returns true if `path` should be skipped by the watcher.
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let is_dir = path.is_dir();
+        self.matcher.matched(path, is_dir).is_ignore()
+    }
+}
@@ -0,0 +1,54 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use tracing::{debug, info};
+
+use crate::settings::Settings;
+
+/// Handles `noorg init`: scaffolds a notes dir, scripts dir, and a
+/// vault-local `.norg.toml` override at `path`, so a vault is self-contained
+/// instead of relying on the implicit auto-creation under the global
+/// config's `note_dir`.
+pub fn handle(path: Option<PathBuf>, file_type: Option<String>, force: bool) -> io::Result<()> {
+    let vault_dir = path.unwrap_or_else(|| PathBuf::from("."));
+    let vault_config_path = vault_dir.join(".norg.toml");
+
+    if vault_config_path.exists() && !force {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+                "vault already initialized at {} (pass --force to reinitialize)",
+                vault_config_path.display()
+            ),
+        ));
+    }
+
+    let notes_dir = vault_dir.join("notes");
+    let scripts_dir = vault_dir.join("scripts");
+
+    debug!("Creating notes directory at {:?}", notes_dir);
+    fs::create_dir_all(&notes_dir)?;
+
+    debug!("Copying default scripts into {:?}", scripts_dir);
+    Settings::copy_default_scripts(&scripts_dir)?;
+
+    let vault_settings = Settings {
+        note_dir: notes_dir.to_string_lossy().into_owned(),
+        scripts_dir: scripts_dir.to_string_lossy().into_owned(),
+        file_type: file_type.unwrap_or_else(|| "md".to_string()),
+        ..Settings::default()
+    };
+
+    let config_str = toml::to_string_pretty(&vault_settings)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    fs::write(&vault_config_path, config_str)?;
+
+    info!(
+        "✨ Initialized vault at {} (notes: {:?}, scripts: {:?})",
+        vault_dir.display(),
+        notes_dir,
+        scripts_dir
+    );
+    Ok(())
+}
@@ -1,55 +1,77 @@
+use directories::ProjectDirs;
 use std::fs;
+use std::path::PathBuf;
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
+use crate::settings::Settings;
+
+/// Resolves the log directory via `ProjectDirs` so logging works the same on
+/// Linux/Windows/macOS instead of the previous hardcoded `~/Library/Logs/noorg`.
+fn log_dir() -> PathBuf {
+    ProjectDirs::from("", "norg", "norg")
+        .map(|proj_dirs| proj_dirs.data_dir().join("logs"))
+        .unwrap_or_else(|| PathBuf::from("./logs"))
+}
+
+/// Builds the default `EnvFilter` from `Settings.log_level`/`log_filter`, layering
+/// `RUST_LOG` on top when it's set so ad-hoc overrides keep working.
+fn build_filter(debug: bool, settings: Option<&Settings>) -> EnvFilter {
+    let base = settings
+        .map(|s| s.log_filter.clone())
+        .filter(|f| !f.is_empty())
+        .unwrap_or_else(|| {
+            let level = settings
+                .map(|s| s.log_level.clone())
+                .filter(|l| !l.is_empty())
+                .unwrap_or_else(|| if debug { "debug".to_string() } else { "info".to_string() });
+            level
+        });
+
+    match std::env::var("RUST_LOG") {
+        Ok(rust_log) if !rust_log.is_empty() => EnvFilter::new(format!("{},{}", base, rust_log)),
+        _ => EnvFilter::new(base),
+    }
+}
+
+/// Initializes logging. `settings` is optional so binaries that run before
+/// `Settings::new()` succeeds (or don't need it, like a minimal CLI probe)
+/// can still get sane defaults.
 pub fn init_logging(debug: bool) {
-    // Determine the log directory
-    let log_dir = dirs::home_dir()
-        .unwrap_or_else(|| std::path::PathBuf::from("."))
-        .join("Library")
-        .join("Logs")
-        .join("noorg");
-
-    // Ensure the log directory exists
+    init_logging_with_settings(debug, None)
+}
+
+pub fn init_logging_with_settings(debug: bool, settings: Option<&Settings>) {
+    let log_dir = log_dir();
     fs::create_dir_all(&log_dir).expect("Failed to create log directory");
 
-    // Set up file appender
     let file_appender = RollingFileAppender::new(Rotation::DAILY, log_dir.clone(), "note_app.log");
 
-    // Create the file layer
     let file_layer = fmt::layer()
         .with_file(true)
         .with_line_number(true)
         .with_thread_ids(true)
         .with_target(true)
         .with_writer(file_appender)
-        .with_filter(if debug {
-            EnvFilter::new("debug")
-        } else {
-            EnvFilter::new("info")
-        });
+        .with_filter(build_filter(debug, settings));
 
-    // Create the terminal layer
-    let terminal_layer = fmt::layer()
-        .with_file(true)
-        .with_line_number(true)
-        .with_thread_ids(true)
-        .with_target(true)
-        .with_filter(if debug {
-            EnvFilter::new("debug")
-        } else {
-            EnvFilter::new("info")
-        });
+    let terminal_enabled = settings.map(|s| s.log_terminal).unwrap_or(true);
+
+    let registry = tracing_subscriber::registry().with(file_layer);
+
+    if terminal_enabled {
+        let terminal_layer = fmt::layer()
+            .with_file(true)
+            .with_line_number(true)
+            .with_thread_ids(true)
+            .with_target(true)
+            .with_filter(build_filter(debug, settings));
 
-    // Combine both layers
-    tracing_subscriber::registry()
-        .with(terminal_layer)
-        .with(file_layer)
-        .init();
+        registry.with(terminal_layer).init();
+    } else {
+        registry.init();
+    }
 
     tracing::info!("Logging initialized");
-    tracing::info!(
-        "Log file location: {}",
-        log_dir.join("note_app.log").display()
-    );
+    tracing::info!("Log file location: {}", log_dir.join("note_app.log").display());
 }
@@ -54,6 +54,52 @@ pub fn merge_metadata(existing: &mut HashMap<String, String>, new: HashMap<Strin
 
                 existing.insert(key, combined.join(", "));
             }
+            "backlinks" => {
+                debug!("Merging backlinks field");
+                let existing_items: Vec<String> = existing
+                    .get("backlinks")
+                    .map(|t| {
+                        trace!("Existing backlinks: {}", t);
+                        t.split(',').map(|s| s.trim().to_string()).collect()
+                    })
+                    .unwrap_or_default();
+
+                let new_items: Vec<String> =
+                    value.split(',').map(|s| s.trim().to_string()).collect();
+                trace!("New backlinks: {:?}", new_items);
+
+                let mut combined: Vec<String> =
+                    existing_items.into_iter().chain(new_items).collect();
+
+                combined.sort();
+                combined.dedup();
+                trace!("Combined and deduplicated backlinks: {:?}", combined);
+
+                existing.insert(key, combined.join(", "));
+            }
+            "tracked" => {
+                debug!("Merging tracked field");
+                let existing_items: Vec<String> = existing
+                    .get("tracked")
+                    .map(|t| {
+                        trace!("Existing tracked intervals: {}", t);
+                        t.split(',').map(|s| s.trim().to_string()).collect()
+                    })
+                    .unwrap_or_default();
+
+                let new_items: Vec<String> =
+                    value.split(',').map(|s| s.trim().to_string()).collect();
+                trace!("New tracked intervals: {:?}", new_items);
+
+                let mut combined: Vec<String> =
+                    existing_items.into_iter().chain(new_items).collect();
+
+                combined.sort();
+                combined.dedup();
+                trace!("Combined and deduplicated tracked intervals: {:?}", combined);
+
+                existing.insert(key, combined.join(", "));
+            }
             "created_at" => {
                 debug!("Processing created_at field");
                 if !existing.contains_key(&key) {
@@ -1,19 +1,31 @@
 use chrono::{DateTime, FixedOffset, Local};
 use directories::ProjectDirs;
+use futures::future::join_all;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
-use crate::event::NoteEvent;
+use crate::event::{NoteEvent, ObserverAction};
+use crate::fuzzy_search::{SearchHit, SearchIndex};
+use crate::hash_cache::{self, HashCacheStore};
 use crate::metadata::merge_metadata;
 use crate::observer_registry::ObserverRegistry;
+use crate::observers::backlinks::BacklinksObserver;
+use crate::observers::search::SearchObserver;
+use crate::observers::sqlite_store::SqliteObserver;
+use crate::postprocessor::NoteContext;
+use crate::postprocessor_registry::PostprocessorRegistry;
+use crate::reference_parser::{rewrite_references, slugify, ReferenceParser};
 use crate::settings::Settings;
-use crate::utils::get_absolute_note_path;
+use crate::snapshot::{Generation, GenerationDiff, SnapshotStore};
+use crate::utils::{get_absolute_note_path, get_fs_path, get_note_title_from_path};
+use crate::version_store::{VersionEntry, VersionStore};
 
 #[derive(Debug, Deserialize, Serialize)]
 struct Frontmatter {
@@ -233,16 +245,37 @@ impl Note {
     }
 }
 
+#[derive(Clone)]
 pub struct NoteManager {
     settings: Settings,
     notes_dir: String,
     observer_registry: Arc<ObserverRegistry>,
+    postprocessor_registry: Arc<PostprocessorRegistry>,
+    /// Lazily built by [`Self::search`] on first use and rebuilt after every
+    /// [`Self::sync_notes`]; `None` means it hasn't been built yet.
+    search_index: RwLock<Option<SearchIndex>>,
+    snapshots: Arc<SnapshotStore>,
+    hash_cache: Arc<dyn HashCacheStore>,
+    version_store: Arc<VersionStore>,
 }
 
 impl NoteManager {
     pub async fn new(
         settings: Settings,
         observer_registry: Arc<ObserverRegistry>,
+    ) -> io::Result<Self> {
+        Self::new_with_postprocessors(
+            settings,
+            observer_registry,
+            Arc::new(PostprocessorRegistry::new()),
+        )
+        .await
+    }
+
+    pub async fn new_with_postprocessors(
+        settings: Settings,
+        observer_registry: Arc<ObserverRegistry>,
+        postprocessor_registry: Arc<PostprocessorRegistry>,
     ) -> io::Result<Self> {
         debug!("Initializing NoteManager");
         let notes_dir = settings.note_dir.clone();
@@ -251,10 +284,18 @@ impl NoteManager {
             e
         })?;
 
+        let hash_cache: Arc<dyn HashCacheStore> = Arc::from(hash_cache::build(&settings)?);
+        let version_store = Arc::new(VersionStore::new()?);
+
         Ok(NoteManager {
             settings,
             notes_dir,
             observer_registry,
+            postprocessor_registry,
+            search_index: RwLock::new(None),
+            snapshots: Arc::new(SnapshotStore::new()),
+            hash_cache,
+            version_store,
         })
     }
     pub fn title_to_filename(&self, title: &str) -> String {
@@ -301,7 +342,7 @@ impl NoteManager {
         };
 
         let active_observers: Vec<_> = observers
-            .iter()
+            .into_iter()
             .filter(|o| !skip_observers.contains(&o.name()))
             .collect();
 
@@ -317,63 +358,24 @@ impl NoteManager {
             title: title.clone(),
             content: content.clone(),
             file_path: get_absolute_note_path(&title, &self.settings),
-            frontmatter: frontmatter_fields.clone(),
+            frontmatter: frontmatter_fields,
         };
 
-        let mut combined_metadata = frontmatter_fields;
-        let mut final_content = content;
-
-        for observer in &observers {
-            if observer.name() == "sqlite" {
-                debug!("Skipping SQLite observer for now (will run last)");
-                continue;
-            }
-
-            info!("🔵 Running observer: {}", observer.name());
-            match observer.on_event_boxed(event.clone()).await {
-                Ok(Some(result)) => {
-                    if let Some(metadata) = result.metadata {
-                        debug!("Observer returned metadata: {:?}", metadata);
-                        merge_metadata(&mut combined_metadata, metadata);
-                    }
-                    if let Some(new_content) = result.content {
-                        debug!("Observer modified content");
-                        final_content = new_content;
-                    }
-                }
-                Ok(None) => debug!("No changes from observer: {}", observer.name()),
-                Err(e) => error!("Error from observer {}: {}", observer.name(), e),
-            }
-        }
+        let (combined_metadata, final_content, pending_actions) =
+            self.dispatch_observers(active_observers, event).await?;
 
         note.content = final_content;
-        note.frontmatter.fields = combined_metadata.clone();
+        note.frontmatter.fields = combined_metadata;
         note.save(&self.settings).await?;
 
-        if let Some(sqlite_observer) = observers.iter().find(|o| o.name() == "sqlite") {
-            debug!("Running SQLite observer");
-            match sqlite_observer.on_event_boxed(event).await {
-                Ok(Some(result)) => {
-                    if let Some(new_content) = result.content {
-                        info!("✨ SQLite observer modified content");
-                        let updated_note =
-                            Note::new(title.clone(), new_content, combined_metadata.clone()).await;
-                        updated_note.save(&self.settings).await?;
-                    }
-                }
-                Ok(None) => debug!("No changes from SQLite observer"),
-                Err(e) => {
-                    error!("SQLite observer error: {}", e);
-                    return Err(e);
-                }
-            }
-        }
+        self.apply_actions(pending_actions).await?;
+        self.regenerate_backlinks().await?;
 
         info!("✨ Note added successfully: {}", title);
         Ok(())
     }
 
-    pub fn delete_note(&self, title: &str) -> io::Result<()> {
+    pub async fn delete_note(&self, title: &str) -> io::Result<()> {
         let filename = format!(
             "{}/{}.{}",
             self.notes_dir,
@@ -388,12 +390,465 @@ impl NoteManager {
                 e
             })?;
             info!("🗑️ Note '{}' deleted successfully", title);
+
+            // There's no `NoteEvent::Deleted` yet, so purge the search index
+            // directly the same way the SQLite observer is singled out
+            // elsewhere in this file.
+            let observers = self.observer_registry.get_observers().await;
+            if let Some(search_observer) = observers
+                .iter()
+                .find(|o| o.name() == "search")
+                .and_then(|o| o.as_any().downcast_ref::<SearchObserver>())
+            {
+                search_observer.remove(title).await?;
+            }
         } else {
             warn!("Note '{}' not found", title);
         }
         Ok(())
     }
 
+    /// Renames a note, moving its file via the `title_to_filename` scheme
+    /// and rewriting every other note's `[[wikilink]]`/`#hashtag` references
+    /// to it so they don't dangle. Errors out rather than overwriting if a
+    /// note already exists at `new_title`. `created_at`/`timestamp` and the
+    /// rest of the frontmatter carry over unchanged, only `title` is
+    /// updated. Fires a `NoteEvent::Updated` for the renamed note afterward
+    /// so observers (including SQLite) see its new title.
+    pub async fn rename_note(&self, old_title: &str, new_title: &str) -> io::Result<()> {
+        let old_filename = format!(
+            "{}/{}.{}",
+            self.notes_dir,
+            self.title_to_filename(old_title),
+            self.settings.file_type
+        );
+        let new_filename = format!(
+            "{}/{}.{}",
+            self.notes_dir,
+            self.title_to_filename(new_title),
+            self.settings.file_type
+        );
+
+        if !Path::new(&old_filename).exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("note '{}' does not exist", old_title),
+            ));
+        }
+        if Path::new(&new_filename).exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("a note already exists at '{}'", new_title),
+            ));
+        }
+
+        let (content, previous_frontmatter) = Note::from_file(Path::new(&old_filename))?
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("could not parse note '{}'", old_title),
+                )
+            })?;
+
+        let mut frontmatter = previous_frontmatter.clone();
+        frontmatter.insert("title".to_string(), new_title.to_string());
+
+        let renamed_note =
+            Note::new(new_title.to_string(), content.clone(), frontmatter.clone()).await;
+        let saved_path = renamed_note.save(&self.settings).await?;
+        fs::remove_file(&old_filename).map_err(|e| {
+            error!(
+                "Failed to remove old note file '{}' after rename: {}",
+                old_filename, e
+            );
+            e
+        })?;
+        info!("🔀 Renamed note '{}' to '{}'", old_title, new_title);
+
+        for entry in fs::read_dir(&self.notes_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(self.settings.file_type.as_str())
+            {
+                continue;
+            }
+            let title = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(title) => title.to_string(),
+                None => continue,
+            };
+            if title == self.title_to_filename(new_title) {
+                continue;
+            }
+            if let Ok(Some((other_content, other_frontmatter))) = Note::from_file(&path) {
+                let rewritten = rewrite_references(&other_content, old_title, new_title);
+                if rewritten != other_content {
+                    debug!("Updating references to '{}' in '{}'", old_title, title);
+                    let updated_note = Note::new(title, rewritten, other_frontmatter).await;
+                    updated_note.save(&self.settings).await?;
+                }
+            }
+        }
+
+        let observers = self.observer_registry.get_observers().await;
+        if let Some(backlinks_observer) = observers
+            .iter()
+            .find(|o| o.name() == "backlinks")
+            .and_then(|o| o.as_any().downcast_ref::<BacklinksObserver>())
+        {
+            backlinks_observer.rename(old_title, new_title)?;
+        }
+
+        self.observer_registry
+            .notify(NoteEvent::Updated {
+                title: new_title.to_string(),
+                content,
+                file_path: saved_path,
+                frontmatter,
+                previous_frontmatter: Some(previous_frontmatter),
+            })
+            .await?;
+
+        self.regenerate_backlinks().await?;
+        self.refresh_search_index().await?;
+
+        Ok(())
+    }
+
+    /// Reads a note's current content and frontmatter from disk by title -
+    /// the read-only counterpart to `add_note`/`delete_note`'s by-title
+    /// interface, backing the script host API's `noorg.get_note`.
+    pub fn get_note(&self, title: &str) -> io::Result<Option<(String, HashMap<String, String>)>> {
+        let path = get_fs_path(title, &self.settings);
+        if !path.exists() {
+            debug!("get_note: no note found for title '{}'", title);
+            return Ok(None);
+        }
+        Note::from_file(&path)
+    }
+
+    /// Builds a `(title, content_hash, updated_at)` triple for every note -
+    /// the local side of the comparison `gossip::handle_digest` makes
+    /// against a peer's advertised digests. `updated_at` is the recency
+    /// signal gossip uses to decide which side of a conflicting pair is
+    /// newer - `created_at` is deliberately immutable after a note's first
+    /// save (see `TimestampObserver`'s "Preserving existing created_at") and
+    /// so never reflects subsequent edits.
+    pub fn local_digest(&self) -> io::Result<Vec<(String, String, String)>> {
+        let mut digest = Vec::new();
+        for entry in fs::read_dir(&self.notes_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(self.settings.file_type.as_str()) {
+                continue;
+            }
+            let title = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(title) => title.to_string(),
+                None => continue,
+            };
+            if let Ok(Some((content, frontmatter))) = Note::from_file(&path) {
+                let hash = Self::calculate_content_hash(&content);
+                let updated_at = frontmatter.get("updated_at").cloned().unwrap_or_default();
+                digest.push((title, hash, updated_at));
+            }
+        }
+        Ok(digest)
+    }
+
+    /// Writes a note body received from a gossip peer and feeds it into the
+    /// observer pipeline as a `NoteEvent::Synced`, mirroring `rename_note`'s
+    /// lightweight `notify`-only pattern rather than the full
+    /// `sync_notes`/`apply_actions` machinery, since a remote note has
+    /// already been through its origin's own sync run.
+    pub async fn apply_remote_note(
+        &self,
+        title: &str,
+        content: String,
+        frontmatter: HashMap<String, String>,
+    ) -> io::Result<()> {
+        let previous_frontmatter = self.get_note(title)?.map(|(_, fm)| fm);
+
+        let note = Note::new(title.to_string(), content.clone(), frontmatter.clone()).await;
+        let file_path = note.save(&self.settings).await?;
+
+        self.observer_registry
+            .notify(NoteEvent::Synced {
+                title: title.to_string(),
+                content,
+                file_path,
+                frontmatter,
+                previous_frontmatter,
+            })
+            .await?;
+
+        info!("📥 Applied remote note '{}' from gossip", title);
+        Ok(())
+    }
+
+    /// Saves a note body that gossip couldn't reconcile (equal timestamps,
+    /// differing hashes) as a separate note rather than overwriting either
+    /// side, so the conflict is surfaced in frontmatter instead of silently
+    /// dropping one peer's edits.
+    pub async fn save_conflict_note(
+        &self,
+        title: &str,
+        content: String,
+        mut frontmatter: HashMap<String, String>,
+        peer: &str,
+    ) -> io::Result<()> {
+        let conflict_title = format!("{} (conflict from {})", title, peer);
+        frontmatter.insert("conflict_of".to_string(), title.to_string());
+        frontmatter.insert("conflict_peer".to_string(), peer.to_string());
+
+        let note = Note::new(conflict_title.clone(), content, frontmatter).await;
+        note.save(&self.settings).await?;
+
+        warn!(
+            "⚠️ Conflicting edit for '{}' from peer '{}' saved as '{}'",
+            title, peer, conflict_title
+        );
+        Ok(())
+    }
+
+    /// Appends `content`'s version to `title`'s append-only history (see
+    /// `crate::version_store::VersionStore`), deduplicating by content hash.
+    /// Called right before a sync writes a note's final content, so past
+    /// versions can be listed and restored without a full VCS.
+    fn record_version(&self, title: &str, content: &str) -> io::Result<()> {
+        let hash = Self::calculate_content_hash(content);
+        let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S %z").to_string();
+        self.version_store.record(title, &hash, content, &timestamp)
+    }
+
+    /// `title`'s recorded versions, oldest first.
+    pub fn note_history(&self, title: &str) -> Vec<VersionEntry> {
+        self.version_store.history(title)
+    }
+
+    /// Retrieves a past version of a note's content by hash, without
+    /// restoring it.
+    pub fn get_version(&self, hash: &str) -> io::Result<Option<String>> {
+        self.version_store.get(hash)
+    }
+
+    /// Restores `title` to a previously recorded version, writing it as the
+    /// note's current content and feeding a `NoteEvent::Synced` into the
+    /// observer pipeline - mirroring `rename_note`'s lightweight
+    /// `notify`-only pattern rather than the full sync machinery, since the
+    /// restored body has already been through its original sync run.
+    pub async fn restore_version(&self, title: &str, hash: &str) -> io::Result<()> {
+        if !self.version_store.history(title).iter().any(|entry| entry.hash == hash) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no version '{}' recorded for note '{}'", hash, title),
+            ));
+        }
+
+        let content = self.version_store.get(hash)?.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("blob '{}' missing from version store", hash),
+            )
+        })?;
+
+        let previous_frontmatter = self.get_note(title)?.map(|(_, fm)| fm);
+        let frontmatter = previous_frontmatter.clone().unwrap_or_default();
+
+        let note = Note::new(title.to_string(), content.clone(), frontmatter.clone()).await;
+        let file_path = note.save(&self.settings).await?;
+
+        self.observer_registry
+            .notify(NoteEvent::Synced {
+                title: title.to_string(),
+                content,
+                file_path,
+                frontmatter,
+                previous_frontmatter,
+            })
+            .await?;
+
+        info!("⏪ Restored note '{}' to version {}", title, hash);
+        Ok(())
+    }
+
+    /// Drops blobs no longer referenced by any note's history. Returns the
+    /// number removed.
+    pub fn gc_versions(&self) -> io::Result<usize> {
+        self.version_store.gc()
+    }
+
+    /// Lists titles of notes whose frontmatter matches every entry in
+    /// `filters` (an empty map matches every note), backing the script host
+    /// API's `noorg.list`.
+    pub fn list_titles(&self, filters: &HashMap<String, String>) -> io::Result<Vec<String>> {
+        let mut titles = Vec::new();
+        for entry in fs::read_dir(&self.notes_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(self.settings.file_type.as_str()) {
+                continue;
+            }
+
+            if let Ok(Some((_, frontmatter))) = Note::from_file(&path) {
+                let matches = filters
+                    .iter()
+                    .all(|(key, value)| frontmatter.get(key).map_or(false, |v| v == value));
+                if matches {
+                    titles.push(get_note_title_from_path(&path.to_string_lossy()));
+                }
+            }
+        }
+        Ok(titles)
+    }
+
+    /// Shared observer dispatch for `add_note`/`sync_notes`/
+    /// `sync_single_note` - the same `interested_keys()` filtering and
+    /// `depends_on()`-driven topological order `ObserverRegistry::notify`
+    /// computes for its own (minor) call sites, instead of the old "run
+    /// everyone but sqlite, then run sqlite last" hard-coding. Observers
+    /// adjacent in the resulting order that are both `read_only()` still run
+    /// concurrently, since they can't interfere with each other; anything
+    /// that mutates content/metadata runs alone so later observers see its
+    /// result. `combined_metadata`/`final_content` start from `event`'s own
+    /// frontmatter/content, and each observer receives `event` carrying the
+    /// current `combined_metadata`/`final_content`; returned metadata is
+    /// merged last-writer-wins per key - matching `notify`'s merge - and
+    /// returned content replaces `final_content`, since `notify` drops it.
+    async fn dispatch_observers(
+        &self,
+        active_observers: Vec<Arc<Box<dyn crate::event::NoteObserver>>>,
+        event: NoteEvent,
+    ) -> io::Result<(HashMap<String, String>, String, Vec<ObserverAction>)> {
+        let changed_keys = event.changed_keys();
+        let order = crate::observer_registry::topological_order(&active_observers)?;
+        let effective: Vec<usize> = order
+            .into_iter()
+            .filter(|&idx| match active_observers[idx].interested_keys() {
+                Some(keys) => !keys.is_disjoint(&changed_keys),
+                None => true,
+            })
+            .collect();
+
+        let mut combined_metadata = event.frontmatter().clone();
+        let mut final_content = event.content().to_string();
+        let mut pending_actions = Vec::new();
+
+        let current_event = |metadata: &HashMap<String, String>, body: &str| {
+            let mut current = event.clone();
+            match &mut current {
+                NoteEvent::Created { frontmatter, content, .. }
+                | NoteEvent::Updated { frontmatter, content, .. }
+                | NoteEvent::Synced { frontmatter, content, .. } => {
+                    *frontmatter = metadata.clone();
+                    *content = body.to_string();
+                }
+            }
+            current
+        };
+
+        let mut i = 0;
+        while i < effective.len() {
+            let idx = effective[i];
+            if active_observers[idx].read_only() {
+                let mut j = i + 1;
+                while j < effective.len() && active_observers[effective[j]].read_only() {
+                    j += 1;
+                }
+
+                let snapshot = current_event(&combined_metadata, &final_content);
+                let results = join_all(effective[i..j].iter().map(|&idx| {
+                    let observer = Arc::clone(&active_observers[idx]);
+                    let event = snapshot.clone();
+                    async move {
+                        info!("🔵 Running observer: {}", observer.name());
+                        (observer.name(), observer.on_event_boxed(event).await)
+                    }
+                }))
+                .await;
+
+                for (name, result) in results {
+                    match result {
+                        Ok(Some(result)) => {
+                            if let Some(metadata) = result.metadata {
+                                debug!("Observer returned metadata: {:?}", metadata);
+                                merge_metadata(&mut combined_metadata, metadata);
+                            }
+                            if let Some(new_content) = result.content {
+                                debug!("Observer modified content");
+                                final_content = new_content;
+                            }
+                            pending_actions.extend(result.actions);
+                        }
+                        Ok(None) => debug!("No changes from observer: {}", name),
+                        Err(e) => error!("Error from observer {}: {}", name, e),
+                    }
+                }
+
+                i = j;
+            } else {
+                let observer = &active_observers[idx];
+                info!("🔵 Running observer: {}", observer.name());
+                match observer
+                    .on_event_boxed(current_event(&combined_metadata, &final_content))
+                    .await
+                {
+                    Ok(Some(result)) => {
+                        if let Some(metadata) = result.metadata {
+                            debug!("Observer returned metadata: {:?}", metadata);
+                            merge_metadata(&mut combined_metadata, metadata);
+                        }
+                        if let Some(new_content) = result.content {
+                            debug!("Observer modified content");
+                            final_content = new_content;
+                        }
+                        pending_actions.extend(result.actions);
+                    }
+                    Ok(None) => debug!("No changes from observer: {}", observer.name()),
+                    Err(e) => {
+                        error!("Error from observer {}: {}", observer.name(), e);
+                        if observer.name() == "sqlite" {
+                            return Err(e);
+                        }
+                    }
+                }
+                i += 1;
+            }
+        }
+
+        Ok((combined_metadata, final_content, pending_actions))
+    }
+
+    /// Applies the non-`UpdateSelf` actions observers emitted alongside the
+    /// note-mutating event: `CreateNote`/`DeleteNote` recurse back into this
+    /// manager's own add/delete paths, `Message` is just logged at its given
+    /// level. Lets, e.g., a daily-note observer spawn linked notes or a
+    /// linter surface warnings.
+    async fn apply_actions(&self, actions: Vec<ObserverAction>) -> io::Result<()> {
+        for action in actions {
+            match action {
+                ObserverAction::CreateNote {
+                    title,
+                    content,
+                    frontmatter,
+                } => {
+                    info!("🔗 Observer requested new note: {}", title);
+                    Box::pin(self.add_note(title, content, frontmatter)).await?;
+                }
+                ObserverAction::DeleteNote { title } => {
+                    info!("🔗 Observer requested note deletion: {}", title);
+                    self.delete_note(&title).await?;
+                }
+                ObserverAction::Message { level, text } => match level.to_lowercase().as_str() {
+                    "error" => error!("📣 {}", text),
+                    "warn" | "warning" => warn!("📣 {}", text),
+                    "debug" => debug!("📣 {}", text),
+                    _ => info!("📣 {}", text),
+                },
+                ObserverAction::UpdateSelf { .. } => {
+                    // Already folded into `result.metadata`/`result.content`
+                    // by `ObserverResult::from_actions`.
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn list_notes_with_filter(
         &self,
         from: Option<DateTime<FixedOffset>>,
@@ -458,6 +913,145 @@ impl NoteManager {
         Ok(())
     }
 
+    /// Prints notes matching `filters` as an indented tree built from each
+    /// note's `parent` frontmatter field (a title or slug of another note),
+    /// ordered within each parent by its `position` frontmatter field - an
+    /// outline-style view on top of the flat file-per-note storage. Notes
+    /// with no `parent`, or whose `parent` doesn't resolve to another
+    /// matching note, are roots. The graph is walked iteratively (no
+    /// recursion) and a note that transitively parents itself is detected
+    /// and demoted to a root with a warning rather than looping forever.
+    /// Missing or unparsable `position` values are stable-sorted after
+    /// valid ones and back-filled with the next sequential integer.
+    pub fn list_tree(&self, filters: HashMap<String, String>) -> io::Result<()> {
+        debug!("Listing note tree with filters: {:?}", filters);
+
+        let mut titles = Vec::new();
+        let mut parents: HashMap<String, Option<String>> = HashMap::new();
+        let mut positions: HashMap<String, Option<i64>> = HashMap::new();
+
+        for entry in fs::read_dir(&self.notes_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(self.settings.file_type.as_str())
+            {
+                continue;
+            }
+
+            if let Ok(Some((_, frontmatter))) = Note::from_file(&path) {
+                let title = get_note_title_from_path(&path.to_string_lossy());
+                let matches_filters = filters
+                    .iter()
+                    .all(|(key, value)| frontmatter.get(key).map_or(false, |v| v == value));
+                if !matches_filters {
+                    continue;
+                }
+
+                let position = frontmatter.get("position").and_then(|p| p.trim().parse().ok());
+                positions.insert(title.clone(), position);
+                parents.insert(title.clone(), frontmatter.get("parent").cloned());
+                titles.push(title);
+            }
+        }
+
+        let slug_to_title: HashMap<String, String> = titles
+            .iter()
+            .map(|title| (slugify(title), title.clone()))
+            .collect();
+
+        let mut effective_parent: HashMap<String, Option<String>> = titles
+            .iter()
+            .map(|title| {
+                let resolved = parents
+                    .get(title)
+                    .cloned()
+                    .flatten()
+                    .and_then(|parent| slug_to_title.get(&slugify(&parent)).cloned())
+                    .filter(|parent| parent != title);
+                (title.clone(), resolved)
+            })
+            .collect();
+
+        for title in &titles {
+            let mut visited = HashSet::new();
+            visited.insert(title.clone());
+            let mut current = effective_parent.get(title).cloned().flatten();
+            let mut cyclic = false;
+            while let Some(parent) = current {
+                if parent == *title {
+                    cyclic = true;
+                    break;
+                }
+                if !visited.insert(parent.clone()) {
+                    // A cycle further up that doesn't include `title`.
+                    break;
+                }
+                current = effective_parent.get(&parent).cloned().flatten();
+            }
+            if cyclic {
+                warn!(
+                    "Note '{}' transitively parents itself; treating it as a root",
+                    title
+                );
+                effective_parent.insert(title.clone(), None);
+            }
+        }
+
+        let mut children: HashMap<Option<String>, Vec<(String, Option<i64>)>> = HashMap::new();
+        for title in &titles {
+            let parent = effective_parent.get(title).cloned().flatten();
+            let position = positions.get(title).cloned().flatten();
+            children.entry(parent).or_default().push((title.clone(), position));
+        }
+
+        let children: HashMap<Option<String>, Vec<String>> = children
+            .into_iter()
+            .map(|(parent, siblings)| (parent, resolve_sibling_order(siblings)))
+            .collect();
+
+        info!("Notes:");
+        let mut stack: Vec<(String, usize)> = children
+            .get(&None)
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|title| (title, 0))
+            .rev()
+            .collect();
+
+        let mut printed_any = false;
+        while let Some((title, depth)) = stack.pop() {
+            info!("{}- {}", "  ".repeat(depth), title);
+            printed_any = true;
+            if let Some(siblings) = children.get(&Some(title.clone())) {
+                for child in siblings.iter().rev() {
+                    stack.push((child.clone(), depth + 1));
+                }
+            }
+        }
+
+        if !printed_any {
+            info!("No notes found matching the specified filters");
+        }
+
+        Ok(())
+    }
+
+    /// Typo-tolerant ranked full-text search over every note's title,
+    /// frontmatter values, and body - see [`SearchIndex`] for how matches are
+    /// ranked. Builds the index on first call and reuses it afterwards;
+    /// [`Self::sync_notes`] rebuilds it so results stay current.
+    pub async fn search(&self, query: &str, limit: usize) -> io::Result<Vec<SearchHit>> {
+        if self.search_index.read().await.is_none() {
+            self.refresh_search_index().await?;
+        }
+
+        let index = self.search_index.read().await;
+        Ok(index
+            .as_ref()
+            .map(|index| index.search(query, limit))
+            .unwrap_or_default())
+    }
+
     pub fn parse_date_string(date_str: &str) -> Result<DateTime<FixedOffset>, String> {
         let formats = ["%Y-%m-%d", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M:%S %z"];
         for format in formats {
@@ -522,6 +1116,7 @@ impl NoteManager {
                         let active_observers: Vec<_> = observers
                             .iter()
                             .filter(|o| !skip_observers.contains(&o.name()))
+                            .cloned()
                             .collect();
 
                         info!(
@@ -532,69 +1127,29 @@ impl NoteManager {
                             info!("ℹ️ Skipping observers: {}", skip_observers.join(", "));
                         }
 
+                        let previous_frontmatter =
+                            self.take_previous_frontmatter(title, &current_frontmatter);
                         let event = NoteEvent::Synced {
                             title: title.to_string(),
                             content: content.clone(),
                             file_path: get_absolute_note_path(title, &self.settings),
                             frontmatter: current_frontmatter.clone(),
+                            previous_frontmatter,
                         };
 
-                        let mut combined_metadata = current_frontmatter;
-                        let mut final_content = content.clone();
+                        let (combined_metadata, final_content, pending_actions) =
+                            self.dispatch_observers(active_observers, event).await?;
 
-                        // Process all non-SQLite observers first
-                        for observer in &active_observers {
-                            if observer.name() == "sqlite" {
-                                continue;
-                            }
-
-                            info!("🔵 Running observer: {}", observer.name());
-                            match observer.on_event_boxed(event.clone()).await {
-                                Ok(Some(result)) => {
-                                    if let Some(metadata) = result.metadata {
-                                        debug!("Observer returned metadata: {:?}", metadata);
-                                        merge_metadata(&mut combined_metadata, metadata);
-                                    }
-                                    if let Some(new_content) = result.content {
-                                        debug!("Observer modified content");
-                                        final_content = new_content;
-                                    }
-                                }
-                                Ok(None) => debug!("No changes from observer: {}", observer.name()),
-                                Err(e) => error!("Error from observer {}: {}", observer.name(), e),
-                            }
+                        if let Err(e) = self.record_version(title, &final_content) {
+                            error!("Failed to record version history for '{}': {}", title, e);
                         }
 
                         let updated_note =
-                            Note::new(title.to_string(), final_content, combined_metadata.clone())
+                            Note::new(title.to_string(), final_content, combined_metadata)
                                 .await;
                         updated_note.save(&self.settings).await?;
 
-                        // Run SQLite observer last
-                        if let Some(sqlite_observer) =
-                            observers.iter().find(|o| o.name() == "sqlite")
-                        {
-                            info!("🔵 Running SQLite observer");
-                            match sqlite_observer.on_event_boxed(event).await {
-                                Ok(Some(result)) => {
-                                    if let Some(new_content) = result.content {
-                                        info!("✨ SQLite observer modified content");
-                                        let updated_note = Note::new(
-                                            title.to_string(),
-                                            new_content,
-                                            combined_metadata.clone(),
-                                        )
-                                        .await;
-                                        updated_note.save(&self.settings).await?;
-                                    }
-                                }
-                                Ok(None) => debug!("No changes from SQLite observer"),
-                                Err(e) => {
-                                    error!("SQLite observer error: {}", e);
-                                    return Err(e);
-                                }
-                            }
-                        }
+                        self.apply_actions(pending_actions).await?;
 
                         info!("✨ Note sync completed for: {}", title);
                     }
@@ -609,10 +1164,217 @@ impl NoteManager {
             }
         }
 
+        self.flush_hash_cache();
+        self.rerender_dirty_notes(&observers).await?;
+        self.regenerate_backlinks().await?;
+        self.refresh_search_index().await?;
+
         info!("🎉 All notes synced successfully");
         Ok(())
     }
 
+    /// Re-runs `process_sql_blocks` on any note the SQLite observer flagged
+    /// as dirty during this sync - i.e. a note whose SQL blocks reference a
+    /// table that changed while syncing a *different* note, so its rendered
+    /// tables would otherwise stay stale until it's next touched directly.
+    async fn rerender_dirty_notes(
+        &self,
+        observers: &[Arc<Box<dyn crate::event::NoteObserver>>],
+    ) -> io::Result<()> {
+        let Some(sqlite_observer) = observers
+            .iter()
+            .find(|o| o.name() == "sqlite")
+            .and_then(|o| o.as_any().downcast_ref::<SqliteObserver>())
+        else {
+            return Ok(());
+        };
+
+        let dirty_titles = sqlite_observer.dirty_notes();
+        if dirty_titles.is_empty() {
+            return Ok(());
+        }
+
+        info!(
+            "🔄 Re-rendering {} note(s) with invalidated SQL blocks",
+            dirty_titles.len()
+        );
+
+        for title in dirty_titles {
+            let path = get_absolute_note_path(&title, &self.settings);
+            match Note::from_file(Path::new(&path)) {
+                Ok(Some((content, frontmatter))) => {
+                    let note_dir = Path::new(&path)
+                        .parent()
+                        .unwrap_or_else(|| Path::new("."));
+                    match sqlite_observer.process_sql_blocks(&content, note_dir).await {
+                        Ok(new_content) => {
+                            let updated_note =
+                                Note::new(title.clone(), new_content, frontmatter).await;
+                            if let Err(e) = updated_note.save(&self.settings).await {
+                                error!("Failed to save re-rendered note '{}': {}", title, e);
+                            }
+                        }
+                        Err(e) => {
+                            error!("Failed to re-render SQL blocks for '{}': {}", title, e)
+                        }
+                    }
+                }
+                Ok(None) => warn!("Could not parse note for re-render: {}", title),
+                Err(e) => error!("Error reading note '{}' for re-render: {}", title, e),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rebuilds every note's `Backlinks:` subsection under `## References`
+    /// from the other notes' `[[wikilinks]]` and `#hashtag`-style
+    /// references. This needs a full second pass over the vault, since a
+    /// note's list of referrers can only be known once every other note's
+    /// outbound references have been parsed.
+    async fn regenerate_backlinks(&self) -> io::Result<()> {
+        let entries = fs::read_dir(&self.notes_dir).map_err(|e| {
+            error!("Failed to read notes directory for backlinks: {}", e);
+            e
+        })?;
+
+        let mut notes: Vec<(String, String, HashMap<String, String>)> = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(self.settings.file_type.as_str())
+            {
+                continue;
+            }
+            let title = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(title) => title.to_string(),
+                None => continue,
+            };
+            if let Ok(Some((content, frontmatter))) = Note::from_file(&path) {
+                notes.push((title, content, frontmatter));
+            }
+        }
+
+        let slug_to_title: HashMap<String, String> = notes
+            .iter()
+            .map(|(title, ..)| (slugify(title), title.clone()))
+            .collect();
+
+        let mut backlinks: HashMap<String, Vec<String>> = HashMap::new();
+        for (title, content, _) in &notes {
+            for reference in ReferenceParser::extract_references(content, title) {
+                if let Some(target_title) = slug_to_title.get(&reference) {
+                    backlinks
+                        .entry(target_title.clone())
+                        .or_default()
+                        .push(title.clone());
+                }
+            }
+        }
+        for referrers in backlinks.values_mut() {
+            referrers.sort();
+            referrers.dedup();
+        }
+
+        for (title, content, frontmatter) in notes {
+            let entries: Vec<(String, String)> = backlinks
+                .get(&title)
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|referrer| {
+                    let path = format!("./{}.{}", referrer, self.settings.file_type);
+                    (referrer, path)
+                })
+                .collect();
+
+            let new_content = regenerate_backlinks_section(&content, &entries);
+            if new_content != content {
+                debug!("Updating backlinks for '{}'", title);
+                let updated_note = Note::new(title, new_content, frontmatter).await;
+                updated_note.save(&self.settings).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rebuilds the in-memory fuzzy search index from every note on disk,
+    /// replacing whatever was cached - see [`Self::search`].
+    async fn refresh_search_index(&self) -> io::Result<()> {
+        let index = self.build_search_index().await?;
+        *self.search_index.write().await = Some(index);
+        Ok(())
+    }
+
+    async fn build_search_index(&self) -> io::Result<SearchIndex> {
+        let entries = fs::read_dir(&self.notes_dir).map_err(|e| {
+            error!("Failed to read notes directory for search index: {}", e);
+            e
+        })?;
+
+        let mut documents = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(self.settings.file_type.as_str())
+            {
+                continue;
+            }
+            let title = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(title) => title.to_string(),
+                None => continue,
+            };
+            if let Ok(Some((content, frontmatter))) = Note::from_file(&path) {
+                let mut searchable = title.clone();
+                for value in frontmatter.values() {
+                    searchable.push(' ');
+                    searchable.push_str(value);
+                }
+                searchable.push(' ');
+                searchable.push_str(&content);
+                documents.push((title, searchable));
+            }
+        }
+
+        Ok(SearchIndex::build(&documents))
+    }
+
+    /// Builds a new snapshot generation from every note currently on disk,
+    /// diffing it against the most recent generation - see [`SnapshotStore::snapshot`].
+    pub fn snapshot(&self) -> io::Result<(Generation, GenerationDiff)> {
+        let mut notes = Vec::new();
+        for entry in fs::read_dir(&self.notes_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(self.settings.file_type.as_str())
+            {
+                continue;
+            }
+            let title = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(title) => title.to_string(),
+                None => continue,
+            };
+            if let Ok(Some((content, _))) = Note::from_file(&path) {
+                notes.push((title, content));
+            }
+        }
+
+        self.snapshots.snapshot(&notes)
+    }
+
+    /// Every stored snapshot generation, oldest first.
+    pub fn list_generations(&self) -> io::Result<Vec<Generation>> {
+        self.snapshots.list_generations()
+    }
+
+    /// Per-note added/changed/deleted titles between two snapshot generations.
+    pub fn diff_generations(&self, a: u64, b: u64) -> io::Result<GenerationDiff> {
+        self.snapshots.diff_generations(a, b)
+    }
+
+    /// Recovers `title`'s body as it was in snapshot `generation`.
+    pub fn restore_note(&self, title: &str, generation: u64) -> io::Result<Option<String>> {
+        self.snapshots.restore_note(title, generation)
+    }
+
     pub async fn sync_single_note(&self, title: &str, skip_hash_check: bool) -> io::Result<()> {
         let path = get_absolute_note_path(title, &self.settings);
         debug!("Syncing note at path: {}", path);
@@ -628,6 +1390,21 @@ impl NoteManager {
                         return Ok(());
                     }
 
+                    let mut postprocess_ctx = NoteContext {
+                        title: title.to_string(),
+                        destination: PathBuf::from(&path),
+                        frontmatter: current_frontmatter.clone(),
+                        content: content.clone(),
+                    };
+
+                    if !self.postprocessor_registry.run(&mut postprocess_ctx).await? {
+                        info!("⏭️ Note '{}' skipped by postprocessor pipeline", title);
+                        return Ok(());
+                    }
+
+                    let content = postprocess_ctx.content;
+                    let current_frontmatter = postprocess_ctx.frontmatter;
+
                     let skip_observers: Vec<String> = if current_frontmatter
                         .get("skip_observers")
                         .map_or(false, |s| s.trim() == "all")
@@ -651,6 +1428,7 @@ impl NoteManager {
                     let active_observers: Vec<_> = observers
                         .iter()
                         .filter(|o| !skip_observers.contains(&o.name().to_string()))
+                        .cloned()
                         .collect();
 
                     info!(
@@ -662,68 +1440,31 @@ impl NoteManager {
                     }
 
                     // Create a sync event for observers
+                    let previous_frontmatter =
+                        self.take_previous_frontmatter(title, &current_frontmatter);
                     let event = NoteEvent::Synced {
                         title: title.to_string(),
                         content: content.clone(),
                         file_path: get_absolute_note_path(title, &self.settings),
                         frontmatter: current_frontmatter.clone(),
+                        previous_frontmatter,
                     };
 
-                    let mut combined_metadata = current_frontmatter;
-                    let mut final_content = content.clone();
-
-                    // Process all non-SQLite observers first
-                    for observer in &active_observers {
-                        if observer.name() == "sqlite" {
-                            continue;
-                        }
+                    let (combined_metadata, final_content, pending_actions) =
+                        self.dispatch_observers(active_observers, event).await?;
+                    debug!("Final content: {}", final_content);
 
-                        info!("🔵 Running observer: {}", observer.name());
-                        match observer.on_event_boxed(event.clone()).await {
-                            Ok(Some(result)) => {
-                                if let Some(metadata) = result.metadata {
-                                    info!("✅ Observer returned metadata: {:?}", metadata);
-                                    merge_metadata(&mut combined_metadata, metadata);
-                                }
-                                if let Some(new_content) = result.content {
-                                    info!("✅ Observer modified content");
-                                    final_content = new_content;
-                                }
-                            }
-                            Ok(None) => info!("ℹ️ No changes from observer: {}", observer.name()),
-                            Err(e) => error!("Error from observer {}: {}", observer.name(), e),
-                        }
+                    if let Err(e) = self.record_version(title, &final_content) {
+                        error!("Failed to record version history for '{}': {}", title, e);
                     }
-                    debug!("Final content: {}", final_content);
 
                     let updated_note =
-                        Note::new(title.to_string(), final_content, combined_metadata.clone())
+                        Note::new(title.to_string(), final_content, combined_metadata)
                             .await;
                     updated_note.save(&self.settings).await?;
 
-                    // Run SQLite observer last
-                    if let Some(sqlite_observer) = observers.iter().find(|o| o.name() == "sqlite") {
-                        info!("🔵 Running SQLite observer");
-                        match sqlite_observer.on_event_boxed(event).await {
-                            Ok(Some(result)) => {
-                                if let Some(new_content) = result.content {
-                                    info!("✨ SQLite observer modified content");
-                                    let updated_note = Note::new(
-                                        title.to_string(),
-                                        new_content,
-                                        combined_metadata.clone(),
-                                    )
-                                    .await;
-                                    updated_note.save(&self.settings).await?;
-                                }
-                            }
-                            Ok(None) => debug!("No changes from SQLite observer"),
-                            Err(e) => {
-                                error!("SQLite observer error: {}", e);
-                                return Err(e);
-                            }
-                        }
-                    }
+                    self.apply_actions(pending_actions).await?;
+                    self.flush_hash_cache();
 
                     info!("✨ Note sync completed for: {}", title);
                 }
@@ -739,24 +1480,23 @@ impl NoteManager {
         Ok(())
     }
 
-    fn calculate_content_hash(content: &str) -> String {
+    pub(crate) fn calculate_content_hash(content: &str) -> String {
         debug!("Calculating content hash");
         let mut hasher = Sha256::new();
         hasher.update(content.as_bytes());
         format!("{:x}", hasher.finalize())
     }
 
-    fn get_hash_cache(&self) -> HashMap<String, String> {
+    fn get_frontmatter_cache(&self) -> HashMap<String, HashMap<String, String>> {
         let cache_path = ProjectDirs::from("", "norg", "norg")
-            .map(|proj_dirs| proj_dirs.data_dir().join("content_hashes.json"))
-            .unwrap_or_else(|| PathBuf::from("./data/content_hashes.json"));
+            .map(|proj_dirs| proj_dirs.data_dir().join("frontmatter_cache.json"))
+            .unwrap_or_else(|| PathBuf::from("./data/frontmatter_cache.json"));
 
-        debug!("Reading hash cache from: {}", cache_path.display());
+        debug!("Reading frontmatter cache from: {}", cache_path.display());
 
-        // Create parent directory if it doesn't exist
         if let Some(parent) = cache_path.parent() {
             if let Err(e) = fs::create_dir_all(parent) {
-                error!("Failed to create hash cache directory: {}", e);
+                error!("Failed to create frontmatter cache directory: {}", e);
                 return HashMap::new();
             }
         }
@@ -764,64 +1504,76 @@ impl NoteManager {
         if cache_path.exists() {
             match fs::read_to_string(&cache_path) {
                 Ok(content) => match serde_json::from_str(&content) {
-                    Ok(cache) => {
-                        debug!("Successfully loaded hash cache");
-                        cache
-                    }
+                    Ok(cache) => cache,
                     Err(e) => {
-                        error!("Failed to parse hash cache: {}", e);
+                        error!("Failed to parse frontmatter cache: {}", e);
                         HashMap::new()
                     }
                 },
                 Err(e) => {
-                    error!("Failed to read hash cache file: {}", e);
+                    error!("Failed to read frontmatter cache file: {}", e);
                     HashMap::new()
                 }
             }
         } else {
-            debug!("No existing hash cache found");
+            debug!("No existing frontmatter cache found");
             HashMap::new()
         }
     }
 
-    fn save_hash_cache(&self, cache: &HashMap<String, String>) -> io::Result<()> {
+    fn save_frontmatter_cache(&self, cache: &HashMap<String, HashMap<String, String>>) {
         let cache_path = ProjectDirs::from("", "norg", "norg")
-            .map(|proj_dirs| proj_dirs.data_dir().join("content_hashes.json"))
-            .unwrap_or_else(|| PathBuf::from("./data/content_hashes.json"));
-
-        debug!("Saving hash cache to: {}", cache_path.display());
+            .map(|proj_dirs| proj_dirs.data_dir().join("frontmatter_cache.json"))
+            .unwrap_or_else(|| PathBuf::from("./data/frontmatter_cache.json"));
 
-        // Create parent directory if it doesn't exist
         if let Some(parent) = cache_path.parent() {
-            fs::create_dir_all(parent)?;
+            if let Err(e) = fs::create_dir_all(parent) {
+                error!("Failed to create frontmatter cache directory: {}", e);
+                return;
+            }
         }
 
-        let json = serde_json::to_string_pretty(cache).map_err(|e| {
-            error!("Failed to serialize hash cache: {}", e);
-            io::Error::new(io::ErrorKind::Other, e)
-        })?;
-
-        fs::write(&cache_path, json).map_err(|e| {
-            error!("Failed to write hash cache: {}", e);
-            e
-        })?;
+        match serde_json::to_string_pretty(cache) {
+            Ok(json) => {
+                if let Err(e) = fs::write(&cache_path, json) {
+                    error!("Failed to write frontmatter cache: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to serialize frontmatter cache: {}", e),
+        }
+    }
 
-        debug!("Hash cache saved successfully");
-        Ok(())
+    /// Returns the frontmatter stored for `title` from the last time this was
+    /// called (or `None` the first time), then records `current` as the new
+    /// baseline - used to populate `NoteEvent::Synced`/`Updated`'s
+    /// `previous_frontmatter` so observer dispatch can diff for changed keys.
+    fn take_previous_frontmatter(
+        &self,
+        title: &str,
+        current: &HashMap<String, String>,
+    ) -> Option<HashMap<String, String>> {
+        let mut cache = self.get_frontmatter_cache();
+        let previous = cache.insert(title.to_string(), current.clone());
+        self.save_frontmatter_cache(&cache);
+        previous
     }
 
     pub async fn should_process_note(&self, title: &str, content: &str) -> bool {
         debug!("Checking if note needs processing: {}", title);
-        let mut hash_cache = self.get_hash_cache();
         let new_hash = Self::calculate_content_hash(content);
 
-        let should_process = match hash_cache.get(title) {
+        let old_hash = self.hash_cache.get(title).unwrap_or_else(|e| {
+            error!("Failed to read hash cache for '{}': {}", title, e);
+            None
+        });
+
+        let should_process = match old_hash {
             Some(old_hash) => {
                 debug!(
                     "Comparing hashes for '{}': old={}, new={}",
                     title, old_hash, new_hash
                 );
-                old_hash != &new_hash
+                old_hash != new_hash
             }
             None => {
                 debug!("No previous hash found for '{}'", title);
@@ -831,9 +1583,8 @@ impl NoteManager {
 
         if should_process {
             debug!("Content changed, updating hash cache");
-            hash_cache.insert(title.to_string(), new_hash);
-            if let Err(e) = self.save_hash_cache(&hash_cache) {
-                error!("Failed to save hash cache: {}", e);
+            if let Err(e) = self.hash_cache.put(title, new_hash) {
+                error!("Failed to update hash cache for '{}': {}", title, e);
             }
         } else {
             debug!("Content unchanged for '{}'", title);
@@ -841,6 +1592,87 @@ impl NoteManager {
 
         should_process
     }
+
+    /// Persists any hash-cache writes `should_process_note` buffered during
+    /// a sync run - a no-op for backends that write through immediately.
+    fn flush_hash_cache(&self) {
+        if let Err(e) = self.hash_cache.flush() {
+            error!("Failed to flush hash cache: {}", e);
+        }
+    }
+}
+
+/// Orders a set of siblings (in the order they were read from disk) by
+/// their parsed `position` frontmatter value, stable-sorting ties and
+/// back-filling missing/unparsable positions with the next sequential
+/// integer after the highest valid one - so siblings without a `position`
+/// keep their relative (read) order and sort after every sibling that has
+/// one.
+fn resolve_sibling_order(siblings: Vec<(String, Option<i64>)>) -> Vec<String> {
+    let mut next_position = siblings
+        .iter()
+        .filter_map(|(_, position)| *position)
+        .max()
+        .map_or(0, |max| max + 1);
+
+    let mut resolved: Vec<(String, i64)> = siblings
+        .into_iter()
+        .map(|(title, position)| {
+            let position = position.unwrap_or_else(|| {
+                let assigned = next_position;
+                next_position += 1;
+                assigned
+            });
+            (title, position)
+        })
+        .collect();
+
+    resolved.sort_by_key(|(_, position)| *position);
+    resolved.into_iter().map(|(title, _)| title).collect()
+}
+
+/// Rewrites `content`'s auto-managed `Backlinks:` subsection (the list
+/// under `## References`, after any `Similar notes:`) to list `backlinks`,
+/// preserving anything else already in `## References`. Adds a `##
+/// References` section if there wasn't one and `backlinks` is non-empty;
+/// drops it entirely if nothing would remain in it.
+fn regenerate_backlinks_section(content: &str, backlinks: &[(String, String)]) -> String {
+    let parts: Vec<&str> = content.splitn(2, "\n## References\n").collect();
+    let main_content = parts[0];
+    let existing_references = parts.get(1).copied().unwrap_or("");
+
+    // Drop any previously-generated Backlinks subsection so it's rebuilt fresh.
+    let existing_references = if existing_references.trim_start().starts_with("Backlinks:\n") {
+        ""
+    } else {
+        existing_references
+            .split("\n\nBacklinks:\n")
+            .next()
+            .unwrap_or("")
+    }
+    .trim_end();
+
+    let mut references_section = existing_references.to_string();
+
+    if !backlinks.is_empty() {
+        let mut backlinks_list = String::from("Backlinks:\n");
+        for (title, path) in backlinks {
+            backlinks_list.push_str(&format!("- [{}]({})\n", title, path));
+        }
+        let backlinks_list = backlinks_list.trim_end();
+
+        references_section = if references_section.is_empty() {
+            backlinks_list.to_string()
+        } else {
+            format!("{}\n\n{}", references_section, backlinks_list)
+        };
+    }
+
+    if references_section.is_empty() {
+        main_content.trim_end().to_string()
+    } else {
+        format!("{}\n\n## References\n{}", main_content.trim_end(), references_section)
+    }
 }
 
 #[cfg(test)]
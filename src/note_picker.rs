@@ -0,0 +1,255 @@
+use iced::widget::{column, container, row, scrollable, text, text_input, Space};
+use iced::{
+    executor, keyboard, subscription, theme, window, Application, Command, Element, Event,
+    Length, Settings as IcedSettings, Subscription, Theme,
+};
+use std::fs;
+use std::path::PathBuf;
+use tracing::error;
+
+use crate::settings::Settings;
+
+/// One entry in the pickable list: a note's title and the path it was read
+/// from, kept together so the preview pane doesn't have to re-derive the
+/// path from the title.
+struct NoteEntry {
+    title: String,
+    path: PathBuf,
+}
+
+pub struct NotePicker {
+    notes: Vec<NoteEntry>,
+    query: String,
+    /// Indices into `notes` that match `query`, ranked best-first.
+    matches: Vec<usize>,
+    selected: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    QueryChanged(String),
+    MoveSelection(i32),
+    Confirm,
+    EventOccurred(Event),
+}
+
+impl Application for NotePicker {
+    type Message = Message;
+    type Theme = Theme;
+    type Executor = executor::Default;
+    type Flags = Settings;
+
+    fn new(settings: Settings) -> (Self, Command<Message>) {
+        let notes = enumerate_notes(&settings);
+        let matches = (0..notes.len()).collect();
+
+        (
+            Self {
+                notes,
+                query: String::new(),
+                matches,
+                selected: 0,
+            },
+            Command::none(),
+        )
+    }
+
+    fn title(&self) -> String {
+        String::from("Open Note")
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        subscription::events().map(Message::EventOccurred)
+    }
+
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::QueryChanged(query) => {
+                self.query = query;
+                self.rerank();
+            }
+            Message::MoveSelection(delta) => {
+                if !self.matches.is_empty() {
+                    let len = self.matches.len() as i32;
+                    let next = (self.selected as i32 + delta).rem_euclid(len);
+                    self.selected = next as usize;
+                }
+            }
+            Message::Confirm => {
+                if let Some(&idx) = self.matches.get(self.selected) {
+                    // Printed to stdout rather than returned from `show`: iced's
+                    // `Application::run` blocks for the lifetime of the window and
+                    // doesn't hand back final state, so the spawning process (the
+                    // tray) reads the pick off this subprocess's stdout instead.
+                    println!("{}", self.notes[idx].title);
+                    return window::close();
+                }
+            }
+            Message::EventOccurred(Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code,
+                ..
+            })) => match key_code {
+                keyboard::KeyCode::Down => return self.update(Message::MoveSelection(1)),
+                keyboard::KeyCode::Up => return self.update(Message::MoveSelection(-1)),
+                keyboard::KeyCode::Escape => return window::close(),
+                _ => {}
+            },
+            Message::EventOccurred(_) => {}
+        }
+        Command::none()
+    }
+
+    fn view(&self) -> Element<Message> {
+        let input = text_input("Type to fuzzy-search note titles...", &self.query)
+            .padding(8)
+            .on_input(Message::QueryChanged)
+            .on_submit(Message::Confirm);
+
+        let list = column(
+            self.matches
+                .iter()
+                .enumerate()
+                .map(|(row_idx, &note_idx)| {
+                    let entry = &self.notes[note_idx];
+                    let label = text(&entry.title).size(14);
+                    let row = container(label).padding(6).width(Length::Fill);
+                    if row_idx == self.selected {
+                        row.style(theme::Container::Box).into()
+                    } else {
+                        row.into()
+                    }
+                })
+                .collect(),
+        )
+        .spacing(2);
+
+        let preview_text = self
+            .matches
+            .get(self.selected)
+            .map(|&idx| {
+                fs::read_to_string(&self.notes[idx].path)
+                    .unwrap_or_else(|e| format!("(could not read note: {})", e))
+            })
+            .unwrap_or_else(|| "(no matching notes)".to_string());
+
+        let preview = container(scrollable(text(preview_text).size(13)))
+            .padding(10)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(theme::Container::Box);
+
+        let body = row![
+            container(scrollable(list)).width(Length::FillPortion(1)),
+            preview.width(Length::FillPortion(2)),
+        ]
+        .spacing(10);
+
+        container(
+            column![input, Space::with_height(10), body]
+                .spacing(5)
+                .padding(15),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+}
+
+impl NotePicker {
+    /// Re-scores every note against the current query and re-sorts
+    /// `matches` best-first, resetting the selection to the top hit.
+    fn rerank(&mut self) {
+        let mut scored: Vec<(usize, i64)> = self
+            .notes
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, entry)| {
+                fuzzy_score(&self.query, &entry.title).map(|score| (idx, score))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.cmp(&a.1));
+        self.matches = scored.into_iter().map(|(idx, _)| idx).collect();
+        self.selected = 0;
+    }
+
+    /// Runs the picker window. If the user confirms a selection its title
+    /// is printed to stdout (see [`Message::Confirm`]); closing the window
+    /// without picking anything prints nothing.
+    pub fn show(settings: Settings) {
+        let iced_settings = IcedSettings {
+            flags: settings,
+            window: window::Settings {
+                size: (800, 500),
+                position: window::Position::Centered,
+                resizable: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        if let Err(e) = <NotePicker as Application>::run(iced_settings) {
+            error!("Failed to run note picker: {}", e);
+        }
+    }
+}
+
+/// Enumerates every note under `settings.note_dir` matching `settings.file_type`,
+/// deriving each title from its file stem the same way `watcher.rs` does.
+fn enumerate_notes(settings: &Settings) -> Vec<NoteEntry> {
+    let mut entries: Vec<NoteEntry> = fs::read_dir(&settings.note_dir)
+        .map(|dir| {
+            dir.filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.extension().and_then(|ext| ext.to_str()) == Some(settings.file_type.as_str())
+                })
+                .filter_map(|path| {
+                    let title = path.file_stem()?.to_str()?.to_string();
+                    Some(NoteEntry { title, path })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    entries.sort_by(|a, b| a.title.cmp(&b.title));
+    entries
+}
+
+/// Scores `candidate` against `query` as a case-insensitive subsequence
+/// match: every query character must appear in order in `candidate`, and
+/// consecutive matches score higher than scattered ones so "not" ranks
+/// "Note Taking" above "Nth Octave Theory". Returns `None` (no match) if
+/// `query` isn't a subsequence of `candidate` at all. An empty query
+/// matches everything with a flat score, preserving alphabetical order.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    let mut score: i64 = 0;
+    let mut query_pos = 0;
+    let mut last_match: Option<usize> = None;
+
+    for (idx, &ch) in candidate.iter().enumerate() {
+        if query_pos < query.len() && ch == query[query_pos] {
+            score += 10;
+            if last_match == Some(idx.wrapping_sub(1)) {
+                score += 15; // bonus for consecutive matches
+            }
+            if idx == 0 || !candidate[idx - 1].is_alphanumeric() {
+                score += 10; // bonus for matching at a word boundary
+            }
+            last_match = Some(idx);
+            query_pos += 1;
+        }
+    }
+
+    if query_pos == query.len() {
+        // Shorter candidates rank slightly higher among equal matches.
+        Some(score - candidate.len() as i64)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,51 @@
+use notify_rust::Notification;
+use tracing::warn;
+
+use crate::settings::Settings;
+
+/// Fires native desktop notifications for watch-mode sync results, gated by
+/// `Settings::notifications_enabled`. A no-op when disabled so call sites
+/// don't need to branch on the setting themselves.
+pub struct SyncNotifier {
+    enabled: bool,
+}
+
+impl SyncNotifier {
+    pub fn new(settings: &Settings) -> Self {
+        Self {
+            enabled: settings.notifications_enabled,
+        }
+    }
+
+    /// Notifies that `title` synced successfully.
+    pub fn notify_synced(&self, title: &str) {
+        if !self.enabled {
+            return;
+        }
+        self.show("noorg", &format!("Synced {}", title));
+    }
+
+    /// Notifies that `title` failed to sync, including the error.
+    pub fn notify_failed(&self, title: &str, error: &str) {
+        if !self.enabled {
+            return;
+        }
+        self.show("noorg: sync failed", &format!("{}: {}", title, error));
+    }
+
+    fn show(&self, summary: &str, body: &str) {
+        if let Err(e) = Notification::new().summary(summary).body(body).show() {
+            warn!("Failed to show desktop notification: {}", e);
+        }
+    }
+}
+
+/// Clears the terminal before a new batch of sync logs, gated by
+/// `Settings::clear_on_sync`. A no-op when disabled.
+pub fn clear_screen_if_enabled(settings: &Settings) {
+    if !settings.clear_on_sync {
+        return;
+    }
+    print!("\x1B[2J\x1B[1;1H");
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+}
@@ -1,13 +1,89 @@
-use crate::event::{NoteEvent, NoteObserver};
+use crate::event::{Bucketable, NoteEvent, NoteObserver, ObserverResult};
 use crate::metadata::merge_metadata;
-use std::collections::HashMap;
+use crate::note::Note;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fs;
 use std::io;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, trace};
 
+/// Orders `observers` so every observer runs after everything its
+/// `depends_on()` names, via Kahn's algorithm; ties among simultaneously
+/// ready observers break by `priority()` (higher first), then by original
+/// registration index, so the order is deterministic. A `depends_on` name
+/// with no matching registered observer is ignored - it can't be waited on.
+///
+/// `pub(crate)` so `Note`'s own dispatch (`add_note`/`sync_notes`/
+/// `sync_single_note`) can compute the same dependency-driven order `notify`
+/// uses, instead of hard-coding "sqlite runs last".
+pub(crate) fn topological_order(observers: &[Arc<Box<dyn NoteObserver>>]) -> io::Result<Vec<usize>> {
+    let name_to_index: HashMap<String, usize> = observers
+        .iter()
+        .enumerate()
+        .map(|(i, o)| (o.name(), i))
+        .collect();
+
+    let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); observers.len()];
+    let mut in_degree: Vec<usize> = vec![0; observers.len()];
+
+    for (i, observer) in observers.iter().enumerate() {
+        for dep_name in observer.depends_on() {
+            if let Some(&dep_index) = name_to_index.get(&dep_name) {
+                dependents[dep_index].push(i);
+                in_degree[i] += 1;
+            }
+        }
+    }
+
+    let mut ready: BinaryHeap<Reverse<(i64, usize)>> = in_degree
+        .iter()
+        .enumerate()
+        .filter(|(_, &degree)| degree == 0)
+        .map(|(i, _)| Reverse((-(observers[i].priority() as i64), i)))
+        .collect();
+
+    let mut order = Vec::with_capacity(observers.len());
+    let mut remaining = in_degree;
+
+    while let Some(Reverse((_, i))) = ready.pop() {
+        order.push(i);
+        for &dependent in &dependents[i] {
+            remaining[dependent] -= 1;
+            if remaining[dependent] == 0 {
+                ready.push(Reverse((-(observers[dependent].priority() as i64), dependent)));
+            }
+        }
+    }
+
+    if order.len() != observers.len() {
+        let stuck: Vec<String> = (0..observers.len())
+            .filter(|i| !order.contains(i))
+            .map(|i| observers[i].name())
+            .collect();
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "observer dependency cycle detected among: {}",
+                stuck.join(", ")
+            ),
+        ));
+    }
+
+    Ok(order)
+}
+
 pub struct ObserverRegistry {
     observers: RwLock<Vec<Arc<Box<dyn NoteObserver>>>>,
+    /// Command name -> index into `observers` of the observer that registered
+    /// it, populated from `ScriptLoader::load_observers`'s `cmd_owners`.
+    cmd_owners: RwLock<HashMap<String, usize>>,
+    /// Held shared by `notify` and exclusively by `reindex`, so a full
+    /// rebuild of a `Bucketable` observer's index never races a concurrent
+    /// live event mutating that same index incrementally.
+    reindex_lock: RwLock<()>,
 }
 
 impl ObserverRegistry {
@@ -15,6 +91,8 @@ impl ObserverRegistry {
         debug!("Creating new ObserverRegistry");
         Self {
             observers: RwLock::new(Vec::new()),
+            cmd_owners: RwLock::new(HashMap::new()),
+            reindex_lock: RwLock::new(()),
         }
     }
 
@@ -26,34 +104,66 @@ impl ObserverRegistry {
         info!("✅ Observer '{}' registered successfully", name);
     }
 
+    /// Records which observer (by its index at registration time) owns each
+    /// script-registered command name, so [`Self::dispatch_command`] can
+    /// route `Command::Run` to it.
+    pub async fn set_cmd_owners(&self, cmd_owners: HashMap<String, usize>) {
+        debug!("Registering {} script command(s)", cmd_owners.len());
+        *self.cmd_owners.write().await = cmd_owners;
+    }
+
+    /// Routes a `Command::Run { name, args }` to the observer that
+    /// registered `name`, via [`NoteObserver::on_command`].
+    pub async fn dispatch_command(
+        &self,
+        name: &str,
+        args: Vec<String>,
+    ) -> io::Result<Option<ObserverResult>> {
+        let owner_index = *self.cmd_owners.read().await.get(name).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no observer registered command '{}'", name),
+            )
+        })?;
+
+        let observers = self.observers.read().await;
+        let observer = observers.get(owner_index).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("the observer owning command '{}' is no longer registered", name),
+            )
+        })?;
+
+        observer.on_command(name, args).await
+    }
+
     pub async fn notify(&self, event: NoteEvent) -> io::Result<HashMap<String, String>> {
         debug!("Starting notification process for event");
         trace!("Event details: {:?}", event);
 
-        let observers = self.observers.read().await;
-        let mut sorted_observers = observers.iter().collect::<Vec<_>>();
+        let _reindex_guard = self.reindex_lock.read().await;
+        let changed_keys = event.changed_keys();
+        trace!("Changed frontmatter keys: {:?}", changed_keys);
 
-        debug!("Sorting observers by priority");
-        sorted_observers.sort_by_key(|o| -o.priority());
+        let observers = self.observers.read().await;
 
-        // Move special observers to end
-        if let Some(pos) = sorted_observers
-            .iter()
-            .position(|o| o.name() == "tag_index")
-        {
-            debug!("Moving tag_index observer to end");
-            let tag_index = sorted_observers.remove(pos);
-            sorted_observers.push(tag_index);
-        }
-        if let Some(pos) = sorted_observers.iter().position(|o| o.name() == "sqlite") {
-            debug!("Moving sqlite observer to end");
-            let sqlite = sorted_observers.remove(pos);
-            sorted_observers.push(sqlite);
-        }
+        debug!("Computing topological dispatch order from declared dependencies");
+        let order = topological_order(&observers)?;
 
         let mut combined_metadata = HashMap::new();
 
-        for observer in sorted_observers {
+        for index in order {
+            let observer = &observers[index];
+            if let Some(interested_keys) = observer.interested_keys() {
+                if interested_keys.is_disjoint(&changed_keys) {
+                    debug!(
+                        "Skipping observer '{}': none of its interested keys changed",
+                        observer.name()
+                    );
+                    continue;
+                }
+            }
+
             info!("🔵 Starting observer: {}", observer.name());
             debug!("Processing event for observer: {}", observer.name());
             trace!("Event details for {}: {:?}", observer.name(), event);
@@ -86,4 +196,126 @@ impl ObserverRegistry {
         trace!("Retrieved {} observers", observers.len());
         result
     }
+
+    /// Hot-swaps a freshly rebuilt script observer into its existing slot (by
+    /// matching `observer.name()`), or appends it if this is a brand-new
+    /// script. Replacing in place keeps every other observer's `cmd_owners`
+    /// index valid, so only the reloaded observer's own commands need
+    /// rewriting.
+    pub async fn reload(&self, observer: Box<dyn NoteObserver>, commands: Vec<String>) -> usize {
+        let name = observer.name();
+        let index = {
+            let mut observers = self.observers.write().await;
+            match observers.iter().position(|o| o.name() == name) {
+                Some(index) => {
+                    observers[index] = Arc::new(observer);
+                    index
+                }
+                None => {
+                    observers.push(Arc::new(observer));
+                    observers.len() - 1
+                }
+            }
+        };
+
+        let mut cmd_owners = self.cmd_owners.write().await;
+        cmd_owners.retain(|_, owner| *owner != index);
+        for command in commands {
+            debug!("'{}' registers command '{}'", name, command);
+            cmd_owners.insert(command, index);
+        }
+
+        info!("🔁 Observer '{}' hot-reloaded", name);
+        index
+    }
+
+    /// Drops the observer matching `name` (its script file was removed) and
+    /// any commands it owned, shifting the indices of everything after it
+    /// down by one so `cmd_owners` stays consistent.
+    pub async fn remove(&self, name: &str) {
+        let removed_index = {
+            let mut observers = self.observers.write().await;
+            match observers.iter().position(|o| o.name() == name) {
+                Some(index) => {
+                    observers.remove(index);
+                    index
+                }
+                None => return,
+            }
+        };
+
+        let mut cmd_owners = self.cmd_owners.write().await;
+        cmd_owners.retain(|_, owner| *owner != removed_index);
+        for owner in cmd_owners.values_mut() {
+            if *owner > removed_index {
+                *owner -= 1;
+            }
+        }
+
+        info!("🗑️ Observer '{}' removed (script deleted)", name);
+    }
+
+    /// Rebuilds `Bucketable` observers' indexes from scratch by walking every
+    /// note under `note_dir`, instead of relying on their per-event
+    /// incremental updates - for when an index got corrupted, hand-edited,
+    /// or its observer was only enabled after notes already existed.
+    /// `observer_name` scopes the rebuild to a single observer; `None`
+    /// rebuilds every `Bucketable` one currently registered. Takes the
+    /// `reindex_lock` exclusively so no live event can race the rebuild.
+    pub async fn reindex(
+        &self,
+        observer_name: Option<&str>,
+        note_dir: &Path,
+        file_type: &str,
+    ) -> io::Result<()> {
+        let _guard = self.reindex_lock.write().await;
+        let observers = self.observers.read().await;
+
+        let targets: Vec<(String, &dyn Bucketable)> = observers
+            .iter()
+            .filter(|o| observer_name.map_or(true, |name| o.name() == name))
+            .filter_map(|o| o.as_bucketable().map(|b| (o.name(), b)))
+            .collect();
+
+        if targets.is_empty() {
+            debug!("reindex: no matching Bucketable observer registered");
+            return Ok(());
+        }
+
+        let mut notes = Vec::new();
+        for entry in fs::read_dir(note_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(file_type) {
+                continue;
+            }
+            let title = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(title) => title.to_string(),
+                None => continue,
+            };
+            if let Ok(Some((content, frontmatter))) = Note::from_file(&path) {
+                notes.push((title, path, content, frontmatter));
+            }
+        }
+
+        for (name, bucketable) in &targets {
+            let mut buckets: HashMap<String, Vec<serde_json::Value>> = HashMap::new();
+            for (title, path, content, frontmatter) in &notes {
+                let event = NoteEvent::Synced {
+                    title: title.clone(),
+                    content: content.clone(),
+                    file_path: path.to_string_lossy().to_string(),
+                    frontmatter: frontmatter.clone(),
+                    previous_frontmatter: None,
+                };
+                for (bucket_key, entries) in bucketable.bucket_note(&event) {
+                    buckets.entry(bucket_key).or_default().extend(entries);
+                }
+            }
+
+            info!("🔁 Rebuilding '{}' index from {} note(s)", name, notes.len());
+            bucketable.write_buckets(buckets)?;
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,350 @@
+use tracing::{debug, info};
+
+use crate::event::{Bucketable, NoteEvent, NoteObserver, ObserverResult};
+use crate::settings::Settings;
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::future::Future;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Maintains a bidirectional link graph over `[[wikilink]]` and local
+/// `[title](path)` references in note bodies, persisted as a `_backlinks.md`
+/// index grouped by target note - the same style as `_tag_index.md`, but
+/// keyed by the note being linked *to* rather than by tag.
+pub struct BacklinksObserver {
+    index_path: String,
+    settings: Arc<Settings>,
+}
+
+impl BacklinksObserver {
+    pub fn new(settings: Arc<Settings>) -> io::Result<Self> {
+        let index_path = Path::new(&settings.note_dir).join("_backlinks.md");
+
+        if !index_path.exists() {
+            let mut file = File::create(&index_path)?;
+            writeln!(file, "# Backlinks\n")?;
+        }
+
+        Ok(Self {
+            index_path: index_path.to_str().unwrap_or("_backlinks.md").to_string(),
+            settings,
+        })
+    }
+
+    fn parse_index(&self) -> io::Result<BTreeMap<String, Vec<(String, String)>>> {
+        let mut content = String::new();
+        File::open(&self.index_path)?.read_to_string(&mut content)?;
+
+        let mut index: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+        let mut current_target = String::new();
+
+        for line in content.lines() {
+            if line.starts_with("## ") {
+                current_target = line[3..].trim().to_string();
+            } else if line.starts_with("- ") && !current_target.is_empty() {
+                if let Some(link_start) = line.find('[') {
+                    if let Some(link_end) = line.find(']') {
+                        if let Some(path_start) = line.find('(') {
+                            if let Some(path_end) = line.find(')') {
+                                let title = line[link_start + 1..link_end].to_string();
+                                let path = line[path_start + 1..path_end].to_string();
+                                index
+                                    .entry(current_target.clone())
+                                    .or_default()
+                                    .push((title, path));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(index)
+    }
+
+    fn write_index(&self, index: &BTreeMap<String, Vec<(String, String)>>) -> io::Result<()> {
+        let mut file = File::create(&self.index_path)?;
+        writeln!(file, "# _backlinks\n")?;
+
+        for (target, entries) in index {
+            writeln!(file, "## {}\n", target)?;
+            for (title, path) in entries {
+                writeln!(file, "- [{}]({})", title, path)?;
+            }
+            writeln!(file)?;
+        }
+
+        Ok(())
+    }
+
+    /// Same as [`Self::write_index`] but writes to a temp file next to the
+    /// index and renames it into place, so a full [`Bucketable::write_buckets`]
+    /// rebuild never leaves readers seeing a half-written index.
+    fn write_index_atomic(&self, index: &BTreeMap<String, Vec<(String, String)>>) -> io::Result<()> {
+        let mut rendered = String::new();
+        rendered.push_str("# _backlinks\n\n");
+        for (target, entries) in index {
+            rendered.push_str(&format!("## {}\n\n", target));
+            for (title, path) in entries {
+                rendered.push_str(&format!("- [{}]({})\n", title, path));
+            }
+            rendered.push('\n');
+        }
+
+        let tmp_path = format!("{}.tmp", self.index_path);
+        fs::write(&tmp_path, rendered)?;
+        fs::rename(&tmp_path, &self.index_path)?;
+        Ok(())
+    }
+
+    /// Removes `title`'s existing outgoing edges and inserts the new ones.
+    fn update_links(&self, title: &str, outlinks: &[String]) -> io::Result<()> {
+        let mut index = self.parse_index()?;
+
+        for entries in index.values_mut() {
+            entries.retain(|(source, _)| source != title);
+        }
+
+        let source_path = format!("./{}.{}", title, self.settings.file_type);
+        for target in outlinks {
+            if target != title {
+                index
+                    .entry(target.clone())
+                    .or_default()
+                    .push((title.to_string(), source_path.clone()));
+            }
+        }
+
+        for entries in index.values_mut() {
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            entries.dedup();
+        }
+        index.retain(|_, entries| !entries.is_empty());
+
+        self.write_index(&index)
+    }
+
+    /// Notes that link to `title`.
+    pub fn backlinks(&self, title: &str) -> io::Result<Vec<(String, String)>> {
+        let index = self.parse_index()?;
+        Ok(index.get(title).cloned().unwrap_or_default())
+    }
+
+    /// Notes `title` links to.
+    pub fn outlinks(&self, title: &str) -> io::Result<Vec<(String, String)>> {
+        let index = self.parse_index()?;
+        let mut result = Vec::new();
+        for (target, entries) in &index {
+            if entries.iter().any(|(source, _)| source == title) {
+                let path = format!("./{}.{}", target, self.settings.file_type);
+                result.push((target.clone(), path));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Rewrites every edge pointing at `old_title` to point at `new_title`
+    /// instead. Called by `NoteManager::rename_note` after the note's file
+    /// has been moved.
+    pub fn rename(&self, old_title: &str, new_title: &str) -> io::Result<()> {
+        let mut index = self.parse_index()?;
+
+        if let Some(entries) = index.remove(old_title) {
+            index.entry(new_title.to_string()).or_default().extend(entries);
+        }
+
+        let new_path = format!("./{}.{}", new_title, self.settings.file_type);
+        for entries in index.values_mut() {
+            for (source, path) in entries.iter_mut() {
+                if source == old_title {
+                    *source = new_title.to_string();
+                    *path = new_path.clone();
+                }
+            }
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+
+        self.write_index(&index)
+    }
+}
+
+/// Extracts `[[wikilink]]`/`[[wikilink|alias]]` and local `[text](path)`
+/// link targets from a note body, resolving each to the referenced note's
+/// title.
+fn extract_outlinks(content: &str, file_type: &str) -> Vec<String> {
+    let mut titles = Vec::new();
+    let mut without_wikilinks = String::with_capacity(content.len());
+    let mut rest = content;
+
+    loop {
+        match rest.find("[[") {
+            Some(start) => {
+                without_wikilinks.push_str(&rest[..start]);
+                let after = &rest[start + 2..];
+                match after.find("]]") {
+                    Some(end) => {
+                        let inner = &after[..end];
+                        let target = inner.split('|').next().unwrap_or(inner).trim();
+                        if !target.is_empty() {
+                            titles.push(target.to_string());
+                        }
+                        rest = &after[end + 2..];
+                    }
+                    None => {
+                        without_wikilinks.push_str(&rest[start..]);
+                        break;
+                    }
+                }
+            }
+            None => {
+                without_wikilinks.push_str(rest);
+                break;
+            }
+        }
+    }
+
+    let mut rest = without_wikilinks.as_str();
+    while let Some(bracket_start) = rest.find('[') {
+        let after_bracket = &rest[bracket_start + 1..];
+        let Some(bracket_end) = after_bracket.find(']') else {
+            break;
+        };
+        let after_text = &after_bracket[bracket_end + 1..];
+
+        if let Some(paren_rest) = after_text.strip_prefix('(') {
+            if let Some(paren_end) = paren_rest.find(')') {
+                let path = &paren_rest[..paren_end];
+                if let Some(title) = note_title_from_link_path(path, file_type) {
+                    titles.push(title);
+                }
+                rest = &paren_rest[paren_end + 1..];
+                continue;
+            }
+        }
+        rest = after_text;
+    }
+
+    titles
+}
+
+/// Resolves a markdown link path to a note title, or `None` for external
+/// links (anything with a scheme) or links to a different file type.
+fn note_title_from_link_path(path: &str, file_type: &str) -> Option<String> {
+    if path.contains("://") {
+        return None;
+    }
+
+    let trimmed = path.trim_start_matches("./");
+    let as_path = Path::new(trimmed);
+    match as_path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext == file_type => {
+            as_path.file_stem()?.to_str().map(|s| s.to_string())
+        }
+        Some(_) => None,
+        None => Some(trimmed.to_string()),
+    }
+}
+
+impl NoteObserver for BacklinksObserver {
+    fn on_event_boxed(
+        &self,
+        event: NoteEvent,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Option<ObserverResult>>> + Send + '_>> {
+        Box::pin(async move {
+            match event {
+                NoteEvent::Created { title, content, .. }
+                | NoteEvent::Updated { title, content, .. }
+                | NoteEvent::Synced { title, content, .. } => {
+                    let outlinks = extract_outlinks(&content, &self.settings.file_type);
+
+                    debug!(
+                        "🔗 Updating backlinks for '{}' ({} outgoing link(s))",
+                        title,
+                        outlinks.len()
+                    );
+                    self.update_links(&title, &outlinks)?;
+                    info!("✅ Backlink graph updated for '{}'", title);
+
+                    let backlinks = self.backlinks(&title)?;
+                    if backlinks.is_empty() {
+                        Ok(None)
+                    } else {
+                        let mut metadata = HashMap::new();
+                        let titles: Vec<String> =
+                            backlinks.into_iter().map(|(title, _)| title).collect();
+                        metadata.insert("backlinks".to_string(), titles.join(", "));
+
+                        Ok(Some(ObserverResult {
+                            metadata: Some(metadata),
+                            content: None,
+                            ..Default::default()
+                        }))
+                    }
+                }
+            }
+        })
+    }
+
+    fn name(&self) -> String {
+        "backlinks".to_string()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn priority(&self) -> i32 {
+        -99 // Run after metadata generation but before storage, like tag_index
+    }
+
+    fn as_bucketable(&self) -> Option<&dyn Bucketable> {
+        Some(self)
+    }
+}
+
+impl Bucketable for BacklinksObserver {
+    fn bucket_note(&self, event: &NoteEvent) -> HashMap<String, Vec<serde_json::Value>> {
+        let mut buckets = HashMap::new();
+
+        let (title, content) = match event {
+            NoteEvent::Created { title, content, .. }
+            | NoteEvent::Updated { title, content, .. }
+            | NoteEvent::Synced { title, content, .. } => (title, content),
+        };
+
+        let source_path = format!("./{}.{}", title, self.settings.file_type);
+        for target in extract_outlinks(content, &self.settings.file_type) {
+            if &target != title {
+                buckets
+                    .entry(target)
+                    .or_insert_with(Vec::new)
+                    .push(serde_json::json!({ "title": title, "path": source_path }));
+            }
+        }
+
+        buckets
+    }
+
+    fn write_buckets(&self, buckets: HashMap<String, Vec<serde_json::Value>>) -> io::Result<()> {
+        let mut index: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+
+        for (target, entries) in buckets {
+            let mut pairs: Vec<(String, String)> = entries
+                .into_iter()
+                .filter_map(|entry| {
+                    let title = entry.get("title")?.as_str()?.to_string();
+                    let path = entry.get("path")?.as_str()?.to_string();
+                    Some((title, path))
+                })
+                .collect();
+            pairs.sort_by(|a, b| a.0.cmp(&b.0));
+            index.insert(target, pairs);
+        }
+
+        self.write_index_atomic(&index)
+    }
+}
@@ -0,0 +1,176 @@
+use rusqlite::vtab::{eponymous_only_module, Context, IndexInfo, VTab, VTabConnection, VTabCursor, Values};
+use rusqlite::{Connection, Error, Result as SqlResult};
+use std::io;
+use std::os::raw::c_int;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{debug, error};
+
+use crate::db;
+use crate::settings::Settings;
+
+/// Read-only virtual table pivoting `frontmatter` into one row per note with
+/// one column per distinct key, so `SELECT title FROM fm_pivot WHERE
+/// status='done' AND priority='high'` replaces the usual self-join-per-key
+/// dance. The column set is discovered once from `SELECT DISTINCT key FROM
+/// frontmatter` when the module connects (i.e. when the owning `SqliteObserver`
+/// starts up) and is declared to SQLite via the `CREATE TABLE` string returned
+/// from `connect` - it is **not** refreshed for new frontmatter keys added
+/// during the run; restart the process to pick those up.
+#[repr(C)]
+struct FmPivotTab {
+    base: rusqlite::vtab::sqlite3_vtab,
+    conn: Connection,
+    keys: Vec<String>,
+}
+
+unsafe impl<'vtab> VTab<'vtab> for FmPivotTab {
+    type Aux = PathBuf;
+    type Cursor = FmPivotCursor<'vtab>;
+
+    fn connect(
+        _db: &mut VTabConnection,
+        aux: Option<&PathBuf>,
+        _args: &[&[u8]],
+    ) -> SqlResult<(String, Self)> {
+        let db_path = aux.cloned().ok_or_else(|| {
+            Error::ModuleError("fm_pivot: missing database path aux argument".to_string())
+        })?;
+
+        let conn = Connection::open(&db_path)?;
+        let mut stmt = conn.prepare("SELECT DISTINCT key FROM frontmatter ORDER BY key")?;
+        let keys: Vec<String> = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<SqlResult<Vec<String>>>()?;
+        drop(stmt);
+
+        let mut sql = "CREATE TABLE x(id INTEGER, title TEXT, path TEXT".to_string();
+        for key in &keys {
+            sql.push_str(&format!(", \"{}\" TEXT", key.replace('"', "\"\"")));
+        }
+        sql.push(')');
+
+        let vtab = FmPivotTab {
+            base: Default::default(),
+            conn,
+            keys,
+        };
+        Ok((sql, vtab))
+    }
+
+    fn best_index(&self, info: &mut IndexInfo) -> SqlResult<()> {
+        // No constraint pushdown: every row is produced by `filter` and
+        // SQLite applies the note's WHERE clause itself against `column()`.
+        info.set_estimated_cost(1_000_000.0);
+        Ok(())
+    }
+
+    fn open(&mut self) -> SqlResult<FmPivotCursor<'_>> {
+        Ok(FmPivotCursor::default())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PivotRow {
+    id: i64,
+    title: String,
+    path: String,
+    values: Vec<Option<String>>,
+}
+
+#[derive(Default)]
+#[repr(C)]
+struct FmPivotCursor<'vtab> {
+    base: rusqlite::vtab::sqlite3_vtab_cursor,
+    rows: Vec<PivotRow>,
+    index: usize,
+    phantom: std::marker::PhantomData<&'vtab FmPivotTab>,
+}
+
+unsafe impl VTabCursor for FmPivotCursor<'_> {
+    fn filter(&mut self, _idx_num: c_int, _idx_str: Option<&str>, _args: &Values<'_>) -> SqlResult<()> {
+        // Eagerly materializes the whole pivot into memory rather than
+        // streaming, which keeps the cursor free of SQLite's row-at-a-time
+        // lifetime constraints - acceptable since a vault's frontmatter is
+        // small relative to note content.
+        let tab = self.vtab();
+        let mut stmt = tab.conn.prepare("SELECT id, title, path FROM notes")?;
+        let notes: Vec<(i64, String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<SqlResult<Vec<_>>>()?;
+        drop(stmt);
+
+        let mut fm_stmt = tab
+            .conn
+            .prepare("SELECT value FROM frontmatter WHERE file_id = ?1 AND key = ?2")?;
+
+        let mut rows = Vec::with_capacity(notes.len());
+        for (id, title, path) in notes {
+            let mut values = Vec::with_capacity(tab.keys.len());
+            for key in &tab.keys {
+                let value: Option<String> = fm_stmt
+                    .query_row(rusqlite::params![id, key], |row| row.get(0))
+                    .ok();
+                values.push(value);
+            }
+            rows.push(PivotRow {
+                id,
+                title,
+                path,
+                values,
+            });
+        }
+
+        self.rows = rows;
+        self.index = 0;
+        Ok(())
+    }
+
+    fn next(&mut self) -> SqlResult<()> {
+        self.index += 1;
+        Ok(())
+    }
+
+    fn eof(&self) -> bool {
+        self.index >= self.rows.len()
+    }
+
+    fn column(&self, ctx: &mut Context, i: c_int) -> SqlResult<()> {
+        let row = &self.rows[self.index];
+        match i {
+            0 => ctx.set_result(&row.id),
+            1 => ctx.set_result(&row.title),
+            2 => ctx.set_result(&row.path),
+            i => ctx.set_result(&row.values[(i - 3) as usize]),
+        }
+    }
+
+    fn rowid(&self) -> SqlResult<i64> {
+        Ok(self.rows[self.index].id)
+    }
+}
+
+impl FmPivotCursor<'_> {
+    fn vtab(&self) -> &FmPivotTab {
+        unsafe { &*(self.base.pVtab as *const FmPivotTab) }
+    }
+}
+
+/// Registers `fm_pivot` as an eponymous virtual table on `conn`, pointed at
+/// the same database file `conn` itself is backed by so the module's own
+/// auxiliary connection (opened in [`FmPivotTab::connect`]) sees the same data.
+pub(crate) fn register(conn: &Connection, settings: &Arc<Settings>) -> io::Result<()> {
+    let db_path = db::db_path(settings);
+    conn.create_module(
+        "fm_pivot",
+        eponymous_only_module::<FmPivotTab>(),
+        Some(db_path),
+    )
+    .map_err(|e| {
+        error!("Failed to register fm_pivot virtual table: {}", e);
+        io::Error::new(io::ErrorKind::Other, e)
+    })?;
+
+    debug!("Registered fm_pivot virtual table");
+    Ok(())
+}
@@ -0,0 +1,284 @@
+use crate::event::{NoteEvent, NoteObserver, ObserverResult};
+use crate::settings::Settings;
+use comrak::{markdown_to_html, ComrakOptions};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::future::Future;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use syntect::highlighting::ThemeSet;
+use syntect::html::highlighted_html_for_string;
+use syntect::parsing::SyntaxSet;
+use tracing::{debug, error, info};
+
+const INDEX_FILENAME: &str = "index.html";
+
+pub struct HtmlExportObserver {
+    site_dir: String,
+    settings: Arc<Settings>,
+    syntax_set: SyntaxSet,
+    theme_set: ThemeSet,
+}
+
+impl HtmlExportObserver {
+    pub fn new(settings: Arc<Settings>) -> io::Result<Self> {
+        let site_dir = settings.site_dir.clone();
+        debug!("Initializing HtmlExportObserver, site_dir: {}", site_dir);
+        fs::create_dir_all(&site_dir).map_err(|e| {
+            error!("Failed to create site directory {}: {}", site_dir, e);
+            e
+        })?;
+
+        Ok(Self {
+            site_dir,
+            settings,
+            syntax_set: SyntaxSet::load_defaults_newlines(),
+            theme_set: ThemeSet::load_defaults(),
+        })
+    }
+
+    fn highlight_code_blocks(&self, html: &str) -> String {
+        // comrak emits <pre><code class="language-xxx">...</code></pre> for fenced blocks.
+        // Re-highlight those blocks with syntect, leaving the rest of the document untouched.
+        let theme = &self.theme_set.themes["base16-ocean.dark"];
+        let mut output = String::new();
+        let mut rest = html;
+
+        while let Some(start) = rest.find("<pre><code class=\"language-") {
+            output.push_str(&rest[..start]);
+            let after_class = &rest[start + "<pre><code class=\"language-".len()..];
+            let lang_end = after_class.find('"').unwrap_or(0);
+            let lang = &after_class[..lang_end];
+
+            let code_start = after_class[lang_end..].find('>').map(|i| lang_end + i + 1);
+            let Some(code_start) = code_start else {
+                output.push_str(&rest[start..]);
+                return output;
+            };
+            let code_slice = &after_class[code_start..];
+            let Some(code_end) = code_slice.find("</code></pre>") else {
+                output.push_str(&rest[start..]);
+                return output;
+            };
+
+            let code = html_escape_decode(&code_slice[..code_end]);
+            let syntax = self
+                .syntax_set
+                .find_syntax_by_token(lang)
+                .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
+
+            match highlighted_html_for_string(&code, &self.syntax_set, syntax, theme) {
+                Ok(highlighted) => output.push_str(&highlighted),
+                Err(e) => {
+                    error!("Failed to highlight code block (lang={}): {}", lang, e);
+                    output.push_str("<pre><code>");
+                    output.push_str(&code);
+                    output.push_str("</code></pre>");
+                }
+            }
+
+            rest = &code_slice[code_end + "</code></pre>".len()..];
+        }
+
+        output.push_str(rest);
+        output
+    }
+
+    fn render_page(&self, title: &str, frontmatter: &HashMap<String, String>, content: &str) -> String {
+        let mut options = ComrakOptions::default();
+        options.extension.table = true;
+        options.extension.strikethrough = true;
+        options.extension.autolink = true;
+        options.extension.tasklist = true;
+
+        let body_html = markdown_to_html(content, &options);
+        let body_html = self.highlight_code_blocks(&body_html);
+
+        let tags = frontmatter.get("tags").cloned().unwrap_or_default();
+        let date = frontmatter
+            .get("created_at")
+            .or_else(|| frontmatter.get("updated_at"))
+            .cloned()
+            .unwrap_or_default();
+
+        let title = escape_html(title);
+        let date = escape_html(&date);
+        let tags = escape_html(&tags);
+
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<link rel=\"stylesheet\" href=\"./site.css\">\n</head>\n<body>\n<header><h1>{title}</h1><p class=\"meta\">{date} &middot; {tags}</p></header>\n<main>\n{body}\n</main>\n<footer><a href=\"./{index}\">&larr; Index</a></footer>\n</body>\n</html>\n",
+            title = title,
+            date = date,
+            tags = tags,
+            body = body_html,
+            index = INDEX_FILENAME,
+        )
+    }
+
+    fn output_path(&self, title: &str) -> PathBuf {
+        Path::new(&self.site_dir).join(format!("{}.html", slugify(title)))
+    }
+
+    fn write_page(&self, title: &str, frontmatter: &HashMap<String, String>, content: &str) -> io::Result<()> {
+        let html = self.render_page(title, frontmatter, content);
+        let path = self.output_path(title);
+
+        let mut file = File::create(&path)?;
+        file.write_all(html.as_bytes())?;
+
+        if self.settings.site_precompress {
+            spawn_gzip(path);
+        }
+
+        self.rebuild_index()
+    }
+
+    fn rebuild_index(&self) -> io::Result<()> {
+        debug!("Rebuilding HTML export index at {}", self.site_dir);
+        let mut entries: Vec<String> = Vec::new();
+        for entry in fs::read_dir(&self.site_dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("html")
+                && path.file_name().and_then(|n| n.to_str()) != Some(INDEX_FILENAME)
+            {
+                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                    entries.push(stem.to_string());
+                }
+            }
+        }
+        entries.sort();
+
+        let mut links = String::new();
+        for entry in &entries {
+            links.push_str(&format!("<li><a href=\"./{}.html\">{}</a></li>\n", entry, entry));
+        }
+
+        let index_html = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head><meta charset=\"utf-8\"><title>Notes</title><link rel=\"stylesheet\" href=\"./site.css\"></head>\n<body>\n<h1>Notes</h1>\n<ul>\n{}</ul>\n</body>\n</html>\n",
+            links
+        );
+
+        let index_path = Path::new(&self.site_dir).join(INDEX_FILENAME);
+        fs::write(&index_path, &index_html)?;
+
+        if self.settings.site_precompress {
+            spawn_gzip(index_path);
+        }
+
+        Ok(())
+    }
+}
+
+fn html_escape_decode(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Inverse of [`html_escape_decode`]. `body_html` already comes out of
+/// comrak with raw HTML in the markdown escaped - `title`, `date`, and
+/// `tags` are interpolated straight from frontmatter/the note's own title,
+/// so they need the same treatment before landing in the page template.
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn slugify(title: &str) -> String {
+    title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+fn spawn_gzip(path: PathBuf) {
+    tokio::spawn(async move {
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => {
+                let compressed = tokio::task::spawn_blocking(move || {
+                    use flate2::write::GzEncoder;
+                    use flate2::Compression;
+                    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                    encoder.write_all(&bytes).ok();
+                    encoder.finish()
+                })
+                .await;
+
+                match compressed {
+                    Ok(Ok(data)) => {
+                        let gz_path = path.with_extension(format!(
+                            "{}.gz",
+                            path.extension().and_then(|e| e.to_str()).unwrap_or("html")
+                        ));
+                        if let Err(e) = tokio::fs::write(&gz_path, data).await {
+                            error!("Failed to write precompressed asset {:?}: {}", gz_path, e);
+                        }
+                    }
+                    _ => error!("Failed to gzip asset {:?}", path),
+                }
+            }
+            Err(e) => error!("Failed to read asset for precompression {:?}: {}", path, e),
+        }
+    });
+}
+
+impl NoteObserver for HtmlExportObserver {
+    fn on_event_boxed(
+        &self,
+        event: NoteEvent,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Option<ObserverResult>>> + Send + '_>> {
+        Box::pin(async move {
+            match event {
+                NoteEvent::Created {
+                    title,
+                    content,
+                    frontmatter,
+                    ..
+                }
+                | NoteEvent::Updated {
+                    title,
+                    content,
+                    frontmatter,
+                    ..
+                }
+                | NoteEvent::Synced {
+                    title,
+                    content,
+                    frontmatter,
+                    ..
+                } => {
+                    debug!("Exporting HTML for note '{}'", title);
+                    self.write_page(&title, &frontmatter, &content)?;
+                    info!("✨ Exported HTML for '{}'", title);
+                    Ok(None)
+                }
+            }
+        })
+    }
+
+    fn name(&self) -> String {
+        "html_export".to_string()
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn priority(&self) -> i32 {
+        -50 // Run after content-producing observers have settled
+    }
+}
@@ -1,4 +1,6 @@
+use crate::db;
 use crate::event::{NoteEvent, NoteObserver, ObserverResult};
+use crate::settings::Settings;
 use kalosm::language::*;
 use rusqlite::{Connection, Error as SqliteError};
 use std::any::Any;
@@ -21,15 +23,10 @@ pub struct LlmMetadataObserver {
 }
 
 impl LlmMetadataObserver {
-    pub fn new() -> io::Result<Self> {
-        let conn = Connection::open("data/frontmatter.db").map_err(|e| {
-            error!("Failed to open frontmatter database: {}", e);
-            io::Error::new(io::ErrorKind::Other, e)
-        })?;
+    pub fn new(settings: Arc<Settings>) -> io::Result<Self> {
+        let conn = db::open_connection(&settings)?;
         debug!("LlmMetadataObserver initialized successfully");
-        Ok(Self {
-            conn: Arc::new(Mutex::new(conn)),
-        })
+        Ok(Self { conn })
     }
 
     async fn get_existing_tags(&self) -> io::Result<Vec<String>> {
@@ -136,6 +133,7 @@ impl NoteObserver for LlmMetadataObserver {
                     Ok(Some(ObserverResult {
                         metadata: Some(fields),
                         content: None,
+                        ..Default::default()
                     }))
                 }
             }
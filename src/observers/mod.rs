@@ -3,10 +3,29 @@ use crate::settings::Settings;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+pub mod backlinks;
+mod fm_pivot;
+pub mod html_export;
+pub mod nostr;
+pub mod revision;
+pub mod search;
+pub mod semantic_index;
+pub mod similar_notes;
 pub mod sqlite_store;
 pub mod tag_index;
+pub mod template_render;
+pub mod time_track;
 pub mod timestamp;
 mod toc;
+use backlinks::BacklinksObserver;
+use html_export::HtmlExportObserver;
+use nostr::NostrObserver;
+use revision::RevisionObserver;
+use search::SearchObserver;
+use semantic_index::SemanticIndexObserver;
+use similar_notes::SimilarNotesObserver;
+use template_render::TemplateRenderObserver;
+use time_track::TimeTrackObserver;
 use toc::TocObserver;
 
 // Update the type to include Settings
@@ -27,9 +46,54 @@ fn create_tag_index_observer(settings: Arc<Settings>) -> Box<dyn NoteObserver> {
     Box::new(tag_index::TagIndexObserver::new(settings).unwrap())
 }
 
+// Function to create BacklinksObserver
+fn create_backlinks_observer(settings: Arc<Settings>) -> Box<dyn NoteObserver> {
+    Box::new(BacklinksObserver::new(settings).unwrap())
+}
+
+// Function to create TimeTrackObserver
+fn create_time_track_observer(settings: Arc<Settings>) -> Box<dyn NoteObserver> {
+    Box::new(TimeTrackObserver::new(settings).unwrap())
+}
+
 // Function to create TocObserver
-fn create_toc_observer(_settings: Arc<Settings>) -> Box<dyn NoteObserver> {
-    Box::new(TocObserver::new())
+fn create_toc_observer(settings: Arc<Settings>) -> Box<dyn NoteObserver> {
+    Box::new(TocObserver::new(settings))
+}
+
+// Function to create HtmlExportObserver
+fn create_html_export_observer(settings: Arc<Settings>) -> Box<dyn NoteObserver> {
+    Box::new(HtmlExportObserver::new(settings).unwrap())
+}
+
+// Function to create SemanticIndexObserver
+fn create_semantic_index_observer(settings: Arc<Settings>) -> Box<dyn NoteObserver> {
+    Box::new(SemanticIndexObserver::new(settings).unwrap())
+}
+
+// Function to create TemplateRenderObserver
+fn create_template_render_observer(settings: Arc<Settings>) -> Box<dyn NoteObserver> {
+    Box::new(TemplateRenderObserver::new(settings).unwrap())
+}
+
+// Function to create RevisionObserver
+fn create_revision_observer(settings: Arc<Settings>) -> Box<dyn NoteObserver> {
+    Box::new(RevisionObserver::new(settings).unwrap())
+}
+
+// Function to create SimilarNotesObserver
+fn create_similar_notes_observer(settings: Arc<Settings>) -> Box<dyn NoteObserver> {
+    Box::new(SimilarNotesObserver::new(settings).unwrap())
+}
+
+// Function to create SearchObserver
+fn create_search_observer(settings: Arc<Settings>) -> Box<dyn NoteObserver> {
+    Box::new(SearchObserver::new(settings).unwrap())
+}
+
+// Function to create NostrObserver
+fn create_nostr_observer(settings: Arc<Settings>) -> Box<dyn NoteObserver> {
+    Box::new(NostrObserver::new(settings).unwrap())
 }
 
 // Static registry of available Rust observers
@@ -38,10 +102,18 @@ lazy_static::lazy_static! {
         let mut m = HashMap::new();
         m.insert("timestamp", create_timestamp_observer as ObserverConstructor);
         // m.insert("llm_metadata", create_llm_metadata_observer as ObserverConstructor);
-        // m.insert("similar_notes", create_similar_notes_observer as ObserverConstructor);
+        m.insert("similar_notes", create_similar_notes_observer as ObserverConstructor);
         m.insert("sqlite", create_sqlite_observer as ObserverConstructor);
         m.insert("tag_index", create_tag_index_observer as ObserverConstructor);
         m.insert("toc", create_toc_observer as ObserverConstructor);
+        m.insert("html_export", create_html_export_observer as ObserverConstructor);
+        m.insert("semantic_index", create_semantic_index_observer as ObserverConstructor);
+        m.insert("template_render", create_template_render_observer as ObserverConstructor);
+        m.insert("revision", create_revision_observer as ObserverConstructor);
+        m.insert("search", create_search_observer as ObserverConstructor);
+        m.insert("backlinks", create_backlinks_observer as ObserverConstructor);
+        m.insert("time_track", create_time_track_observer as ObserverConstructor);
+        m.insert("nostr", create_nostr_observer as ObserverConstructor);
         m
     };
 }
@@ -67,5 +139,41 @@ pub fn create_observers(settings: Settings) -> Vec<Box<dyn NoteObserver>> {
         observers.push(create_toc_observer(settings.clone()));
     }
 
+    if settings.enabled_observers.contains(&"html_export".to_string()) {
+        observers.push(create_html_export_observer(settings.clone()));
+    }
+
+    if settings.enabled_observers.contains(&"semantic_index".to_string()) {
+        observers.push(create_semantic_index_observer(settings.clone()));
+    }
+
+    if settings.enabled_observers.contains(&"template_render".to_string()) {
+        observers.push(create_template_render_observer(settings.clone()));
+    }
+
+    if settings.enabled_observers.contains(&"revision".to_string()) {
+        observers.push(create_revision_observer(settings.clone()));
+    }
+
+    if settings.enabled_observers.contains(&"similar_notes".to_string()) {
+        observers.push(create_similar_notes_observer(settings.clone()));
+    }
+
+    if settings.enabled_observers.contains(&"search".to_string()) {
+        observers.push(create_search_observer(settings.clone()));
+    }
+
+    if settings.enabled_observers.contains(&"backlinks".to_string()) {
+        observers.push(create_backlinks_observer(settings.clone()));
+    }
+
+    if settings.enabled_observers.contains(&"time_track".to_string()) {
+        observers.push(create_time_track_observer(settings.clone()));
+    }
+
+    if settings.enabled_observers.contains(&"nostr".to_string()) {
+        observers.push(create_nostr_observer(settings.clone()));
+    }
+
     observers
 }
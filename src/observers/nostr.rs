@@ -0,0 +1,190 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use nostr_sdk::prelude::*;
+use tracing::{error, info};
+
+use crate::event::{NoteEvent, NoteObserver, ObserverResult};
+use crate::reference_parser::slugify;
+use crate::settings::Settings;
+
+/// Publishes a note to Nostr relays as a NIP-23 long-form content event
+/// (kind 30023), opted into per-note via frontmatter rather than a global
+/// switch:
+/// - `nostr_publish: true` - required, or the note is left untouched.
+/// - `nostr_relays` - optional comma-separated relay URL override; falls
+///   back to `settings.nostr.relays`.
+/// - `nostr_key` - optional alias into `settings.nostr.keys`, resolved to an
+///   env var holding the nsec/hex signing key; falls back to the
+///   `"default"` alias. Config only ever stores the env var's *name*, never
+///   the key itself.
+///
+/// Kind 30023 is a *parameterized replaceable* event: relays keep only the
+/// latest event per `(pubkey, kind, d-tag)`, and the `d` tag here is the
+/// note's title slug. So re-publishing after an edit updates the relays'
+/// copy in place instead of leaving old revisions scattered around - the
+/// returned `nostr_event_id` metadata just lets a future sync see that a
+/// copy already exists.
+pub struct NostrObserver {
+    settings: Arc<Settings>,
+}
+
+impl NostrObserver {
+    pub fn new(settings: Arc<Settings>) -> io::Result<Self> {
+        Ok(Self { settings })
+    }
+
+    /// Strips the auto-generated `## References` section (backlinks,
+    /// similar notes) `Note::to_string` appends, so only the note's own
+    /// prose gets published.
+    fn publishable_content(content: &str) -> String {
+        content
+            .split("\n## References\n")
+            .next()
+            .unwrap_or(content)
+            .trim()
+            .to_string()
+    }
+
+    fn resolve_relays(&self, frontmatter: &HashMap<String, String>) -> Vec<String> {
+        frontmatter
+            .get("nostr_relays")
+            .map(|relays| {
+                relays
+                    .split(',')
+                    .map(|relay| relay.trim().to_string())
+                    .filter(|relay| !relay.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|relays| !relays.is_empty())
+            .unwrap_or_else(|| self.settings.nostr.relays.clone())
+    }
+
+    fn resolve_keys(&self, frontmatter: &HashMap<String, String>) -> io::Result<Keys> {
+        let alias = frontmatter
+            .get("nostr_key")
+            .map(|alias| alias.trim())
+            .filter(|alias| !alias.is_empty())
+            .unwrap_or("default");
+
+        let env_var = self.settings.nostr.keys.get(alias).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no nostr key configured for alias '{}'", alias),
+            )
+        })?;
+
+        let secret = std::env::var(env_var).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("env var '{}' (nostr key alias '{}') is not set", env_var, alias),
+            )
+        })?;
+
+        Keys::parse(&secret).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("invalid nostr key: {}", e))
+        })
+    }
+
+    async fn publish(
+        &self,
+        title: &str,
+        content: &str,
+        frontmatter: &HashMap<String, String>,
+    ) -> io::Result<String> {
+        let relays = self.resolve_relays(frontmatter);
+        if relays.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "nostr_publish is set but no relays are configured",
+            ));
+        }
+
+        let keys = self.resolve_keys(frontmatter)?;
+        let client = Client::new(&keys);
+        for relay in &relays {
+            client.add_relay(relay.as_str()).await.map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("failed to add relay '{}': {}", relay, e),
+                )
+            })?;
+        }
+        client.connect().await;
+
+        let body = Self::publishable_content(content);
+        let event = EventBuilder::long_form_text_note(body)
+            .tag(Tag::identifier(slugify(title)))
+            .tag(Tag::title(title))
+            .sign_with_keys(&keys)
+            .map_err(|e| {
+                io::Error::new(io::ErrorKind::Other, format!("failed to sign nostr event: {}", e))
+            })?;
+
+        let event_id = event.id;
+        client.send_event(event).await.map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                format!("failed to publish to relays: {}", e),
+            )
+        })?;
+
+        info!("📡 Published '{}' to Nostr as event {}", title, event_id.to_hex());
+        Ok(event_id.to_hex())
+    }
+}
+
+impl NoteObserver for NostrObserver {
+    fn on_event_boxed(
+        &self,
+        event: NoteEvent,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Option<ObserverResult>>> + Send + '_>> {
+        Box::pin(async move {
+            let frontmatter = event.frontmatter().clone();
+            let should_publish = frontmatter
+                .get("nostr_publish")
+                .map_or(false, |v| v.trim() == "true");
+            if !should_publish {
+                return Ok(None);
+            }
+
+            let (title, content) = match event {
+                NoteEvent::Created { title, content, .. } => (title, content),
+                NoteEvent::Updated { title, content, .. } => (title, content),
+                NoteEvent::Synced { title, content, .. } => (title, content),
+            };
+
+            match self.publish(&title, &content, &frontmatter).await {
+                Ok(event_id) => {
+                    let mut metadata = HashMap::new();
+                    metadata.insert("nostr_event_id".to_string(), event_id);
+                    Ok(Some(ObserverResult {
+                        metadata: Some(metadata),
+                        content: None,
+                        ..Default::default()
+                    }))
+                }
+                Err(e) => {
+                    error!("Failed to publish '{}' to Nostr: {}", title, e);
+                    Err(e)
+                }
+            }
+        })
+    }
+
+    fn name(&self) -> String {
+        "nostr".to_string()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn priority(&self) -> i32 {
+        -50 // after metadata-generating observers settle, well before storage
+    }
+}
@@ -0,0 +1,272 @@
+use crate::db;
+use crate::event::{NoteEvent, NoteObserver, ObserverResult};
+use crate::settings::Settings;
+use rusqlite::{params, Connection};
+use std::any::Any;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info};
+
+/// A single stored revision of a note.
+#[derive(Debug, Clone)]
+pub struct Revision {
+    pub revision_id: i64,
+    pub file_path: String,
+    pub timestamp: String,
+    pub content: String,
+    pub diff: String,
+}
+
+/// Default number of revisions kept per note before older ones are pruned.
+const DEFAULT_RETENTION_LIMIT: usize = 50;
+
+pub struct RevisionObserver {
+    conn: Arc<Mutex<Connection>>,
+    retention_limit: usize,
+}
+
+impl RevisionObserver {
+    pub fn new(settings: Arc<Settings>) -> io::Result<Self> {
+        let conn = db::open_connection(&settings)?;
+        let retention_limit = if settings.revision_retention_limit > 0 {
+            settings.revision_retention_limit
+        } else {
+            DEFAULT_RETENTION_LIMIT
+        };
+
+        {
+            let conn = conn.blocking_lock();
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS revisions (
+                    revision_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    file_path TEXT NOT NULL,
+                    timestamp TEXT NOT NULL DEFAULT (datetime('now')),
+                    content TEXT NOT NULL,
+                    diff TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_revisions_file_path ON revisions(file_path);",
+            )
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        }
+
+        debug!("RevisionObserver initialized with retention limit {}", retention_limit);
+        Ok(Self {
+            conn,
+            retention_limit,
+        })
+    }
+
+    async fn latest_content(&self, file_path: &str) -> io::Result<Option<String>> {
+        let conn = self.conn.lock().await;
+        conn.query_row(
+            "SELECT content FROM revisions WHERE file_path = ?1 ORDER BY revision_id DESC LIMIT 1",
+            params![file_path],
+            |row| row.get(0),
+        )
+        .map(Some)
+        .or_else(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => Ok(None),
+            e => Err(io::Error::new(io::ErrorKind::Other, e)),
+        })
+    }
+
+    async fn store_revision(&self, file_path: &str, content: &str, diff: &str) -> io::Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT INTO revisions (file_path, content, diff) VALUES (?1, ?2, ?3)",
+            params![file_path, content, diff],
+        )
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        conn.execute(
+            "DELETE FROM revisions WHERE file_path = ?1 AND revision_id NOT IN (
+                SELECT revision_id FROM revisions WHERE file_path = ?1
+                ORDER BY revision_id DESC LIMIT ?2
+            )",
+            params![file_path, self.retention_limit as i64],
+        )
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        Ok(())
+    }
+
+    /// Lists all stored revisions for a note, oldest first.
+    pub async fn list_revisions(&self, file_path: &str) -> io::Result<Vec<Revision>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn
+            .prepare(
+                "SELECT revision_id, file_path, timestamp, content, diff
+                 FROM revisions WHERE file_path = ?1 ORDER BY revision_id ASC",
+            )
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        let revisions = stmt
+            .query_map(params![file_path], |row| {
+                Ok(Revision {
+                    revision_id: row.get(0)?,
+                    file_path: row.get(1)?,
+                    timestamp: row.get(2)?,
+                    content: row.get(3)?,
+                    diff: row.get(4)?,
+                })
+            })
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        Ok(revisions)
+    }
+
+    /// Reconstructs the exact content stored for a given revision.
+    pub async fn restore_revision(&self, file_path: &str, revision_id: i64) -> io::Result<Option<String>> {
+        let conn = self.conn.lock().await;
+        conn.query_row(
+            "SELECT content FROM revisions WHERE file_path = ?1 AND revision_id = ?2",
+            params![file_path, revision_id],
+            |row| row.get(0),
+        )
+        .map(Some)
+        .or_else(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => Ok(None),
+            e => Err(io::Error::new(io::ErrorKind::Other, e)),
+        })
+    }
+}
+
+/// Computes a longest-common-subsequence line diff between `old` and `new`,
+/// returning every line prefixed with `+ `, `- ` or two spaces (unchanged)
+/// as a single human-readable string.
+pub fn line_diff(old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = myers_diff(&old_lines, &new_lines);
+
+    let mut out = String::new();
+    for op in ops {
+        match op {
+            DiffOp::Equal(line) => {
+                out.push_str("  ");
+                out.push_str(line);
+                out.push('\n');
+            }
+            DiffOp::Delete(line) => {
+                out.push_str("- ");
+                out.push_str(line);
+                out.push('\n');
+            }
+            DiffOp::Insert(line) => {
+                out.push_str("+ ");
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+enum DiffOp<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// Longest-common-subsequence based line diff, computed from a full
+/// O(N*M) dynamic-programming table, emitting a sequence of
+/// equal/delete/insert ops.
+fn myers_diff<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffOp<'a>> {
+    let lcs = lcs_table(old, new);
+    let mut ops = Vec::new();
+    backtrack(&lcs, old, new, old.len(), new.len(), &mut ops);
+    ops.reverse();
+    ops
+}
+
+fn lcs_table(old: &[&str], new: &[&str]) -> Vec<Vec<u32>> {
+    let (n, m) = (old.len(), new.len());
+    let mut table = vec![vec![0u32; m + 1]; n + 1];
+    for i in 1..=n {
+        for j in 1..=m {
+            table[i][j] = if old[i - 1] == new[j - 1] {
+                table[i - 1][j - 1] + 1
+            } else {
+                table[i - 1][j].max(table[i][j - 1])
+            };
+        }
+    }
+    table
+}
+
+fn backtrack<'a>(
+    lcs: &[Vec<u32>],
+    old: &[&'a str],
+    new: &[&'a str],
+    i: usize,
+    j: usize,
+    ops: &mut Vec<DiffOp<'a>>,
+) {
+    if i > 0 && j > 0 && old[i - 1] == new[j - 1] {
+        ops.push(DiffOp::Equal(old[i - 1]));
+        backtrack(lcs, old, new, i - 1, j - 1, ops);
+    } else if j > 0 && (i == 0 || lcs[i][j - 1] >= lcs[i - 1][j]) {
+        ops.push(DiffOp::Insert(new[j - 1]));
+        backtrack(lcs, old, new, i, j - 1, ops);
+    } else if i > 0 && (j == 0 || lcs[i][j - 1] < lcs[i - 1][j]) {
+        ops.push(DiffOp::Delete(old[i - 1]));
+        backtrack(lcs, old, new, i - 1, j, ops);
+    }
+}
+
+impl NoteObserver for RevisionObserver {
+    fn on_event_boxed(
+        &self,
+        event: NoteEvent,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Option<ObserverResult>>> + Send + '_>> {
+        Box::pin(async move {
+            match event {
+                NoteEvent::Updated {
+                    content, file_path, ..
+                }
+                | NoteEvent::Synced {
+                    content, file_path, ..
+                } => {
+                    let previous = self.latest_content(&file_path).await?;
+                    let diff = match &previous {
+                        Some(prev) if prev == &content => return Ok(None),
+                        Some(prev) => line_diff(prev, &content),
+                        None => line_diff("", &content),
+                    };
+
+                    self.store_revision(&file_path, &content, &diff).await?;
+                    info!("✨ Stored revision for '{}'", file_path);
+                    Ok(None)
+                }
+                NoteEvent::Created {
+                    content, file_path, ..
+                } => {
+                    let diff = line_diff("", &content);
+                    self.store_revision(&file_path, &content, &diff).await?;
+                    Ok(None)
+                }
+            }
+        })
+    }
+
+    fn name(&self) -> String {
+        "revision".to_string()
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn priority(&self) -> i32 {
+        0
+    }
+}
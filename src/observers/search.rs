@@ -0,0 +1,315 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{debug, info};
+
+use crate::event::{NoteEvent, NoteObserver, ObserverResult};
+use crate::settings::Settings;
+
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+/// One term's occurrence in a single document: how many times it appeared
+/// and at which token positions (kept for potential future phrase queries).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Posting {
+    doc_id: String,
+    term_frequency: u32,
+    positions: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct SearchIndex {
+    /// term -> postings, one per document containing that term.
+    postings: HashMap<String, Vec<Posting>>,
+    /// title -> token count, needed for BM25's document-length normalization.
+    doc_lengths: HashMap<String, usize>,
+    doc_paths: HashMap<String, String>,
+    doc_tags: HashMap<String, Vec<String>>,
+}
+
+impl SearchIndex {
+    fn doc_count(&self) -> usize {
+        self.doc_lengths.len()
+    }
+
+    fn average_doc_length(&self) -> f64 {
+        if self.doc_lengths.is_empty() {
+            return 0.0;
+        }
+        self.doc_lengths.values().sum::<usize>() as f64 / self.doc_lengths.len() as f64
+    }
+
+    /// Drops every posting/length/path/tag entry for `title`, e.g. before
+    /// re-indexing it with fresh content or when the note is deleted.
+    fn remove_document(&mut self, title: &str) {
+        for postings in self.postings.values_mut() {
+            postings.retain(|p| p.doc_id != title);
+        }
+        self.postings.retain(|_, postings| !postings.is_empty());
+        self.doc_lengths.remove(title);
+        self.doc_paths.remove(title);
+        self.doc_tags.remove(title);
+    }
+
+    fn index_document(
+        &mut self,
+        title: &str,
+        path: &str,
+        content: &str,
+        tags: Vec<String>,
+    ) {
+        self.remove_document(title);
+
+        let tokens = tokenize(&format!("{} {}", title, content));
+        self.doc_lengths.insert(title.to_string(), tokens.len());
+        self.doc_paths.insert(title.to_string(), path.to_string());
+        self.doc_tags.insert(title.to_string(), tags);
+
+        let mut positions_by_term: HashMap<String, Vec<u32>> = HashMap::new();
+        for (position, term) in tokens.into_iter().enumerate() {
+            positions_by_term
+                .entry(term)
+                .or_default()
+                .push(position as u32);
+        }
+
+        for (term, positions) in positions_by_term {
+            self.postings
+                .entry(term)
+                .or_default()
+                .push(Posting {
+                    doc_id: title.to_string(),
+                    term_frequency: positions.len() as u32,
+                    positions,
+                });
+        }
+    }
+}
+
+/// Lowercases and splits on anything that isn't alphanumeric, dropping empty
+/// tokens left over from runs of punctuation.
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| term.to_string())
+        .collect()
+}
+
+/// A single search match: the note's title, file path, and BM25 score
+/// (higher is more relevant).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub title: String,
+    pub path: String,
+    pub score: f64,
+}
+
+/// Optional constraints narrowing a [`SearchObserver::search`] call to notes
+/// whose `tags` frontmatter contains every listed tag.
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilters {
+    pub tags: Vec<String>,
+}
+
+/// Maintains an inverted index over note titles/content/frontmatter and
+/// ranks matches with BM25, mirroring how [`crate::observers::tag_index::TagIndexObserver`]
+/// maintains its own sidecar index: incrementally on `Created`/`Updated`/`Synced`
+/// (remove the document's old postings, then add the new ones), persisted
+/// as a JSON sidecar next to the notes rather than a database.
+pub struct SearchObserver {
+    index_path: PathBuf,
+    index: Mutex<SearchIndex>,
+}
+
+impl SearchObserver {
+    pub fn new(settings: Arc<Settings>) -> io::Result<Self> {
+        let index_path = Path::new(&settings.note_dir).join("_search_index.json");
+
+        let index = if index_path.exists() {
+            let content = fs::read_to_string(&index_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            SearchIndex::default()
+        };
+
+        Ok(Self {
+            index_path,
+            index: Mutex::new(index),
+        })
+    }
+
+    fn save(&self, index: &SearchIndex) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(index)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        fs::write(&self.index_path, json)
+    }
+
+    fn extract_tags(frontmatter: &HashMap<String, String>) -> Vec<String> {
+        frontmatter
+            .get("tags")
+            .map(|tags| {
+                tags.split(',')
+                    .map(|t| t.trim().to_string())
+                    .filter(|t| !t.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Removes `title` from the index, e.g. when its note is deleted.
+    pub async fn remove(&self, title: &str) -> io::Result<()> {
+        let mut index = self.index.lock().await;
+        index.remove_document(title);
+        self.save(&index)
+    }
+
+    /// BM25-ranked search over the indexed title/content/frontmatter,
+    /// supporting prefix terms (`rust*`) and an optional tag filter.
+    pub async fn search(
+        &self,
+        query: &str,
+        filters: Option<&SearchFilters>,
+    ) -> io::Result<Vec<SearchHit>> {
+        let index = self.index.lock().await;
+        // Same split as `tokenize`, but keeping `*` so prefix terms survive.
+        let terms: Vec<String> = query
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric() && c != '*')
+            .filter(|term| !term.trim_matches('*').is_empty())
+            .map(|term| term.to_string())
+            .collect();
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let n = index.doc_count() as f64;
+        let avgdl = index.average_doc_length();
+        let mut scores: HashMap<String, f64> = HashMap::new();
+
+        for term in &terms {
+            let (is_prefix, term) = match term.strip_suffix('*') {
+                Some(stripped) => (true, stripped),
+                None => (false, term.as_str()),
+            };
+
+            let matching_terms: Vec<&String> = if is_prefix {
+                index
+                    .postings
+                    .keys()
+                    .filter(|t| t.starts_with(term))
+                    .collect()
+            } else {
+                index.postings.keys().filter(|t| t.as_str() == term).collect()
+            };
+
+            for matched_term in matching_terms {
+                let postings = match index.postings.get(matched_term) {
+                    Some(postings) => postings,
+                    None => continue,
+                };
+
+                let df = postings.len() as f64;
+                let idf = ((n - df + 0.5) / (df + 0.5) + 1.0).ln();
+
+                for posting in postings {
+                    let doc_len = *index.doc_lengths.get(&posting.doc_id).unwrap_or(&0) as f64;
+                    let tf = posting.term_frequency as f64;
+                    let denom = tf + K1 * (1.0 - B + B * doc_len / avgdl.max(1.0));
+                    let score = idf * (tf * (K1 + 1.0)) / denom;
+                    *scores.entry(posting.doc_id.clone()).or_insert(0.0) += score;
+                }
+            }
+        }
+
+        let mut hits: Vec<SearchHit> = scores
+            .into_iter()
+            .filter(|(title, _)| match filters {
+                Some(filters) if !filters.tags.is_empty() => index
+                    .doc_tags
+                    .get(title)
+                    .map(|tags| filters.tags.iter().all(|tag| tags.contains(tag)))
+                    .unwrap_or(false),
+                _ => true,
+            })
+            .map(|(title, score)| {
+                let path = index.doc_paths.get(&title).cloned().unwrap_or_default();
+                SearchHit { title, path, score }
+            })
+            .collect();
+
+        hits.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        Ok(hits)
+    }
+}
+
+impl NoteObserver for SearchObserver {
+    fn on_event_boxed(
+        &self,
+        event: NoteEvent,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Option<ObserverResult>>> + Send + '_>> {
+        Box::pin(async move {
+            match event {
+                NoteEvent::Created {
+                    title,
+                    content,
+                    file_path,
+                    frontmatter,
+                    ..
+                }
+                | NoteEvent::Updated {
+                    title,
+                    content,
+                    file_path,
+                    frontmatter,
+                    ..
+                }
+                | NoteEvent::Synced {
+                    title,
+                    content,
+                    file_path,
+                    frontmatter,
+                    ..
+                } => {
+                    debug!("🔎 Indexing '{}' for full-text search", title);
+                    let tags = Self::extract_tags(&frontmatter);
+                    let frontmatter_values: Vec<&str> =
+                        frontmatter.values().map(String::as_str).collect();
+                    let indexed = format!("{} {}", content, frontmatter_values.join(" "));
+
+                    let mut index = self.index.lock().await;
+                    index.index_document(&title, &file_path, &indexed, tags);
+                    self.save(&index)?;
+
+                    info!("✅ Search index updated for '{}'", title);
+                    Ok(None)
+                }
+            }
+        })
+    }
+
+    fn name(&self) -> String {
+        "search".to_string()
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn priority(&self) -> i32 {
+        -99 // Run after metadata generation but before storage, like tag_index
+    }
+}
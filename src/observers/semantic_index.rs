@@ -0,0 +1,247 @@
+use crate::event::{NoteEvent, NoteObserver, ObserverResult};
+use crate::settings::Settings;
+use kalosm::language::*;
+use rusqlite::{params, Connection};
+use sha2::{Digest, Sha256};
+use std::any::Any;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, error, info};
+
+const CHUNK_TOKEN_SIZE: usize = 512;
+
+pub struct SemanticIndexObserver {
+    conn: Arc<Mutex<Connection>>,
+    bert: Arc<Mutex<Bert>>,
+    settings: Arc<Settings>,
+}
+
+impl SemanticIndexObserver {
+    pub fn new(settings: Arc<Settings>) -> io::Result<Self> {
+        let data_dir = Settings::get_data_dir();
+        let sqlite_dir = data_dir.join("sqlite");
+        std::fs::create_dir_all(&sqlite_dir)?;
+        let db_path = sqlite_dir.join("frontmatter.db");
+
+        let conn = Connection::open(&db_path).map_err(|e| {
+            error!("Failed to open semantic index database: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS embeddings (
+                file_path TEXT NOT NULL,
+                chunk_index INTEGER NOT NULL,
+                chunk_hash TEXT NOT NULL,
+                embedding BLOB NOT NULL,
+                text TEXT NOT NULL,
+                PRIMARY KEY (file_path, chunk_index)
+            )",
+            [],
+        )
+        .map_err(|e| {
+            error!("Failed to create embeddings table: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+
+        debug!("Initializing BERT model for semantic index");
+        let bert = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                Bert::new()
+                    .await
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+            })
+        })?;
+
+        info!("✨ SemanticIndexObserver initialized successfully");
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            bert: Arc::new(Mutex::new(bert)),
+            settings,
+        })
+    }
+
+    fn chunk_content(content: &str) -> Vec<String> {
+        let words: Vec<&str> = content.split_whitespace().collect();
+        if words.is_empty() {
+            return Vec::new();
+        }
+
+        words
+            .chunks(CHUNK_TOKEN_SIZE)
+            .map(|chunk| chunk.join(" "))
+            .collect()
+    }
+
+    fn hash_chunk(text: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(text.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    async fn update_embeddings(&self, file_path: &str, content: &str) -> io::Result<()> {
+        let chunks = Self::chunk_content(content);
+        debug!("Splitting '{}' into {} chunks", file_path, chunks.len());
+
+        let conn = self.conn.lock().await;
+        for (index, chunk) in chunks.iter().enumerate() {
+            let chunk_hash = Self::hash_chunk(chunk);
+
+            let existing_hash: Option<String> = conn
+                .query_row(
+                    "SELECT chunk_hash FROM embeddings WHERE file_path = ?1 AND chunk_index = ?2",
+                    params![file_path, index as i64],
+                    |row| row.get(0),
+                )
+                .ok();
+
+            if existing_hash.as_deref() == Some(chunk_hash.as_str()) {
+                debug!("Chunk {} of '{}' unchanged, skipping re-embed", index, file_path);
+                continue;
+            }
+
+            let embedding = self.bert.lock().await.embed(chunk).await.map_err(|e| {
+                error!("Failed to embed chunk {} of '{}': {}", index, file_path, e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+
+            let bytes = embedding_to_bytes(embedding.to_vec());
+
+            conn.execute(
+                "INSERT OR REPLACE INTO embeddings (file_path, chunk_index, chunk_hash, embedding, text)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![file_path, index as i64, chunk_hash, bytes, chunk],
+            )
+            .map_err(|e| {
+                error!("Failed to persist embedding for '{}': {}", file_path, e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+        }
+
+        // Drop any stale chunks beyond the new chunk count (note shrank).
+        conn.execute(
+            "DELETE FROM embeddings WHERE file_path = ?1 AND chunk_index >= ?2",
+            params![file_path, chunks.len() as i64],
+        )
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        Ok(())
+    }
+
+    pub async fn search(&self, query: &str, top_k: usize) -> io::Result<Vec<(String, f32)>> {
+        debug!("Semantic search for: {}", query);
+        let query_embedding = self.bert.lock().await.embed(query).await.map_err(|e| {
+            error!("Failed to embed query: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+        let query_vec = query_embedding.to_vec();
+
+        let conn = self.conn.lock().await;
+        let mut stmt = conn
+            .prepare("SELECT file_path, embedding FROM embeddings")
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                let file_path: String = row.get(0)?;
+                let embedding: Vec<u8> = row.get(1)?;
+                Ok((file_path, embedding))
+            })
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        let mut best_per_file: std::collections::HashMap<String, f32> = std::collections::HashMap::new();
+        for row in rows {
+            let (file_path, embedding_bytes) = row.map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            let chunk_vec = bytes_to_embedding(&embedding_bytes);
+            let score = cosine_similarity(&query_vec, &chunk_vec);
+
+            best_per_file
+                .entry(file_path)
+                .and_modify(|existing| {
+                    if score > *existing {
+                        *existing = score;
+                    }
+                })
+                .or_insert(score);
+        }
+
+        let mut results: Vec<(String, f32)> = best_per_file.into_iter().collect();
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(top_k);
+
+        debug!("Semantic search for vault at '{}' returned {} results", self.settings.note_dir, results.len());
+        Ok(results)
+    }
+}
+
+fn embedding_to_bytes(embedding: Vec<f32>) -> Vec<u8> {
+    embedding.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+fn bytes_to_embedding(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+
+    let dot: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+impl NoteObserver for SemanticIndexObserver {
+    fn on_event_boxed(
+        &self,
+        event: NoteEvent,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Option<ObserverResult>>> + Send + '_>> {
+        Box::pin(async move {
+            match event {
+                NoteEvent::Created {
+                    content, file_path, ..
+                }
+                | NoteEvent::Updated {
+                    content, file_path, ..
+                }
+                | NoteEvent::Synced {
+                    content, file_path, ..
+                } => {
+                    debug!("Updating semantic index for '{}'", file_path);
+                    self.update_embeddings(&file_path, &content).await?;
+                    info!("✨ Semantic index updated for '{}'", file_path);
+                    Ok(None)
+                }
+            }
+        })
+    }
+
+    fn name(&self) -> String {
+        "semantic_index".to_string()
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn priority(&self) -> i32 {
+        0
+    }
+}
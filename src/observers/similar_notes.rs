@@ -2,6 +2,8 @@ use crate::event::ObserverResult;
 use crate::event::{NoteEvent, NoteObserver};
 use crate::settings::Settings;
 use kalosm::language::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::any::Any;
 use std::future::Future;
 use std::io;
@@ -12,6 +14,15 @@ use surrealdb::{engine::local::RocksDb, Surreal};
 use tokio::sync::Mutex;
 use tracing::{debug, error, info};
 
+/// A note's stripped-content digest, stored alongside its `Document` keyed
+/// by the same `safe_id` so `update_embeddings` can skip re-embedding and
+/// re-upserting when a write didn't actually change the prose (e.g. a
+/// frontmatter-only edit).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ContentHash {
+    hash: String,
+}
+
 #[derive(Clone)]
 pub struct SimilarNotesObserver {
     db: Arc<Mutex<Surreal<Db>>>,
@@ -154,24 +165,150 @@ impl SimilarNotesObserver {
             return Ok(());
         }
 
+        let safe_id = title
+            .replace(|c: char| !c.is_alphanumeric(), "_")
+            .to_lowercase();
+        let content_hash = Self::hash_content(&clean_content);
+
+        let existing_hash: Option<ContentHash> = self
+            .db
+            .lock()
+            .await
+            .select(("content_hashes", safe_id.as_str()))
+            .await
+            .map_err(|e| {
+                error!("Failed to look up content hash for {}: {}", title, e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+
+        if existing_hash.as_ref().map(|h| h.hash.as_str()) == Some(content_hash.as_str()) {
+            debug!("Content unchanged for '{}', skipping re-embed", title);
+            return Ok(());
+        }
+
         let document = Document::from_parts(title.clone(), clean_content);
         debug!("Created document for {}, performing upsert", title);
 
         let table = self.document_table.lock().await;
-        let safe_id = title
-            .replace(|c: char| !c.is_alphanumeric(), "_")
-            .to_lowercase();
-        let id = surrealdb::sql::Id::from(safe_id);
+        let id = surrealdb::sql::Id::from(safe_id.clone());
 
         table.update(id, document).await.map_err(|e| {
             error!("Failed to upsert document for {}: {}", title, e);
             io::Error::new(io::ErrorKind::Other, e)
         })?;
 
+        self.db
+            .lock()
+            .await
+            .upsert::<Option<ContentHash>>(("content_hashes", safe_id.as_str()))
+            .content(ContentHash { hash: content_hash })
+            .await
+            .map_err(|e| {
+                error!("Failed to persist content hash for {}: {}", title, e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+
         info!("✨ Successfully updated embeddings for {}", title);
         Ok(())
     }
 
+    fn hash_content(content: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(content.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Removes a note's document and content-hash record by its derived
+    /// `safe_id`. A title change produces a new `safe_id`, so the old
+    /// record is otherwise left orphaned; callers that learn a title has
+    /// disappeared (e.g. a future delete/rename event) should invoke this
+    /// to garbage-collect it.
+    pub async fn remove_embeddings(&self, title: &str) -> io::Result<()> {
+        let safe_id = title
+            .replace(|c: char| !c.is_alphanumeric(), "_")
+            .to_lowercase();
+
+        let table = self.document_table.lock().await;
+        let id = surrealdb::sql::Id::from(safe_id.clone());
+        table.delete(id).await.map_err(|e| {
+            error!("Failed to remove document for {}: {}", title, e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+        drop(table);
+
+        self.db
+            .lock()
+            .await
+            .delete::<Option<ContentHash>>(("content_hashes", safe_id.as_str()))
+            .await
+            .map_err(|e| {
+                error!("Failed to remove content hash for {}: {}", title, e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+
+        Ok(())
+    }
+
+    /// Public entry point for `update_embeddings`, used by `Command::Reindex`
+    /// to (re)embed a note directly rather than going through a full
+    /// `NoteEvent`.
+    pub async fn embed_note(&self, title: String, content: String) -> io::Result<()> {
+        self.update_embeddings(title, content).await
+    }
+
+    /// Embeds `query` and returns the `limit` nearest notes by cosine
+    /// distance, excluding anything in `similar_notes.excluded_notes`. This
+    /// exposes the same retrieval path `find_similar_notes` uses for the
+    /// write-time "Similar Notes" section as a first-class read query, so
+    /// `noorg search` can find notes by meaning instead of by title/content
+    /// substring.
+    pub async fn search(&self, query: &str, limit: usize) -> io::Result<Vec<(String, String, f32)>> {
+        debug!("Running semantic search for query: {}", query);
+
+        let query_embedding = self.bert.lock().await.embed(query).await.map_err(|e| {
+            error!("Failed to embed search query: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+
+        let nearest = self
+            .document_table
+            .lock()
+            .await
+            .select_nearest(query_embedding, limit)
+            .await
+            .map_err(|e| {
+                error!("Failed to run semantic search: {}", e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+
+        let excluded = self
+            .settings
+            .similar_notes
+            .excluded_notes
+            .as_ref()
+            .map(|v| v.as_slice())
+            .unwrap_or(&[]);
+
+        let mut matches: Vec<_> = nearest
+            .into_iter()
+            .filter(|doc| !excluded.contains(&doc.record.title().to_string()))
+            .map(|doc| {
+                let title = doc.record.title().to_string();
+                let path = format!("./{}.{}", title, self.settings.file_type);
+                (title, path, doc.distance)
+            })
+            .collect();
+
+        matches.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+
+        info!(
+            "Semantic search for '{}' returned {} result(s)",
+            query,
+            matches.len()
+        );
+        Ok(matches)
+    }
+
     fn extract_content(text: &str) -> String {
         // Remove frontmatter and get clean content
         if let Some(start) = text.find("---\n") {
@@ -256,6 +393,7 @@ impl NoteObserver for SimilarNotesObserver {
                         Ok(Some(ObserverResult {
                             metadata: None,
                             content: Some(new_content),
+                            ..Default::default()
                         }))
                     } else {
                         debug!("No similar notes found for '{}'", title);
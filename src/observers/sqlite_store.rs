@@ -1,21 +1,49 @@
+use crate::db;
 use crate::event::*;
+use crate::observers::fm_pivot;
 use crate::settings::Settings;
+use chrono::{NaiveDate, Utc};
 use kalosm::language::*;
-use rusqlite::{Connection, Result as SqlResult};
+use rusqlite::backup::Backup;
+use rusqlite::functions::{Aggregate, Context, FunctionFlags};
+use rusqlite::hooks::Action;
+use rusqlite::session::{ConflictAction, ConflictType, Session};
+use rusqlite::{Connection, Error as SqlError, Result as SqlResult};
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
 use std::future::Future;
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
 use tokio::sync::Mutex;
 use tracing::{debug, error, info};
 
 pub struct SqliteObserver {
+    /// Change-tracking session attached to `conn`'s connection, capturing
+    /// `notes`/`frontmatter` mutations for `export_changeset`/
+    /// `apply_changeset`. Declared before `conn` so it's dropped first: it
+    /// borrows that connection via a raw pointer (see `new()`) and must
+    /// never outlive it.
+    session: Mutex<Session<'static>>,
     conn: Arc<Mutex<Connection>>,
     model: Llama,
     settings: Arc<Settings>,
+    /// Count of `Synced` events processed, used to trigger a rolling backup
+    /// every `settings.sqlite_backup_interval` events (see `on_event_boxed`).
+    sync_count: AtomicUsize,
+    /// Title -> tables referenced by that note's SQL blocks, refreshed each
+    /// time the note is synced. The commit hook registered in `new()`
+    /// consults this to figure out which *other* notes a just-committed
+    /// change invalidates.
+    table_refs: Arc<StdMutex<HashMap<String, HashSet<String>>>>,
+    /// Note titles invalidated by a committed change to a table they
+    /// reference, drained by `dirty_notes`.
+    dirty: Arc<StdMutex<HashSet<String>>>,
 }
 #[derive(Debug)]
 pub struct NoteResult {
@@ -27,58 +55,274 @@ pub struct NoteResult {
 #[derive(Debug)]
 pub struct QueryResult {
     pub columns: Vec<String>,
-    pub rows: Vec<HashMap<String, String>>,
+    pub rows: Vec<HashMap<String, CellValue>>,
+}
+
+/// A single result cell, preserving its SQLite storage class instead of
+/// collapsing everything to `String` the way `row.get::<_, String>()`
+/// (defaulting to `""` on error) used to - which silently mangled integers,
+/// reals, and NULLs into misleading text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl CellValue {
+    fn from_value_ref(value: rusqlite::types::ValueRef<'_>) -> Self {
+        match value {
+            rusqlite::types::ValueRef::Null => CellValue::Null,
+            rusqlite::types::ValueRef::Integer(i) => CellValue::Integer(i),
+            rusqlite::types::ValueRef::Real(r) => CellValue::Real(r),
+            rusqlite::types::ValueRef::Text(t) => {
+                CellValue::Text(String::from_utf8_lossy(t).into_owned())
+            }
+            rusqlite::types::ValueRef::Blob(b) => CellValue::Blob(b.to_vec()),
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, CellValue::Integer(_) | CellValue::Real(_))
+    }
+
+    /// Converts to a JSON value, used to hand `QueryResult` rows across the
+    /// Lua/Python boundary in the script host API (see `crate::host_api`).
+    /// A `Blob` becomes a hex string, same rendering as `Display`.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            CellValue::Null => serde_json::Value::Null,
+            CellValue::Integer(i) => serde_json::Value::from(*i),
+            CellValue::Real(r) => serde_json::Value::from(*r),
+            CellValue::Text(t) => serde_json::Value::String(t.clone()),
+            CellValue::Blob(b) => serde_json::Value::String(
+                b.iter().map(|byte| format!("{:02x}", byte)).collect(),
+            ),
+        }
+    }
+}
+
+/// Kept for backward compatibility with callers that formatted the old
+/// `String`-typed rows directly; blobs render as hex rather than losing
+/// their bytes to lossy UTF-8 conversion.
+impl fmt::Display for CellValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CellValue::Null => write!(f, ""),
+            CellValue::Integer(i) => write!(f, "{}", i),
+            CellValue::Real(r) => write!(f, "{}", r),
+            CellValue::Text(t) => write!(f, "{}", t),
+            CellValue::Blob(b) => {
+                for byte in b {
+                    write!(f, "{:02x}", byte)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn row_to_cells(row: &rusqlite::Row<'_>, columns: &[String]) -> SqlResult<HashMap<String, CellValue>> {
+    let mut map = HashMap::new();
+    for (i, column) in columns.iter().enumerate() {
+        map.insert(column.clone(), CellValue::from_value_ref(row.get_ref(i)?));
+    }
+    Ok(map)
+}
+
+/// Rewrites `filename='...'`/`filename="..."` arguments inside a block's SQL
+/// so a relative path resolves against the note's own directory instead of
+/// the process's working directory.
+fn resolve_csv_filenames(sql: &str, note_dir: &Path) -> String {
+    const NEEDLE: &str = "filename=";
+    let mut result = String::with_capacity(sql.len());
+    let mut rest = sql;
+
+    while let Some(pos) = rest.find(NEEDLE) {
+        result.push_str(&rest[..pos + NEEDLE.len()]);
+        rest = &rest[pos + NEEDLE.len()..];
+
+        let quote = rest.chars().next().filter(|c| *c == '\'' || *c == '"');
+        match quote.and_then(|q| rest[1..].find(q).map(|end| (q, end))) {
+            Some((quote, end)) => {
+                let path_str = &rest[1..1 + end];
+                let resolved = if Path::new(path_str).is_relative() {
+                    note_dir.join(path_str).to_string_lossy().into_owned()
+                } else {
+                    path_str.to_string()
+                };
+                result.push(quote);
+                result.push_str(&resolved);
+                result.push(quote);
+                rest = &rest[1 + end + 1..];
+            }
+            None => continue,
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Splits off a leading `CREATE VIRTUAL TABLE temp.<name> USING csv(...)`
+/// statement from the rest of a block's SQL, returning `(setup_statement,
+/// remaining_query, temp_table_name)`. A block with no such statement
+/// returns `(None, sql.to_string(), None)` unchanged.
+fn split_csv_setup(sql: &str) -> (Option<String>, String, Option<String>) {
+    const MARKER: &str = "create virtual table temp.";
+    let lower = sql.to_lowercase();
+
+    let Some(pos) = lower.find(MARKER) else {
+        return (None, sql.to_string(), None);
+    };
+    let Some(rel_end) = sql[pos..].find(';') else {
+        return (None, sql.to_string(), None);
+    };
+
+    let end = pos + rel_end;
+    let setup = sql[pos..=end].to_string();
+    let remaining = format!("{}{}", &sql[..pos], &sql[end + 1..]);
+    let table_name = extract_temp_vtable_name(&setup);
+
+    (Some(setup), remaining.trim().to_string(), table_name)
+}
+
+/// Pulls `<name>` out of a `CREATE VIRTUAL TABLE temp.<name> ...` statement.
+fn extract_temp_vtable_name(setup_sql: &str) -> Option<String> {
+    let lower = setup_sql.to_lowercase();
+    const MARKER: &str = "create virtual table temp.";
+    let pos = lower.find(MARKER)?;
+    let after = &setup_sql[pos + MARKER.len()..];
+    let name: String = after
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Which query backend a fenced block runs against: ` ```sql ` blocks hit
+/// `query` directly, ` ```search ` blocks run their body as an FTS5 `MATCH`
+/// expression through `search`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    Sql,
+    Search,
+}
+
+impl BlockKind {
+    fn fence(self) -> &'static str {
+        match self {
+            BlockKind::Sql => "sql",
+            BlockKind::Search => "search",
+        }
+    }
 }
 
 #[derive(Debug)]
 struct SqlBlock {
+    kind: BlockKind,
     sql: String,
     range: (usize, usize),
 }
 
+/// Backs the `tag_cloud(value)` aggregate SQL function: splits each row's
+/// comma-separated tag value and counts occurrences, finalizing to a
+/// `tag(count), ...` summary ordered by frequency.
+struct TagCloud;
+
+impl Aggregate<HashMap<String, i64>, String> for TagCloud {
+    fn init(&self, _ctx: &mut Context<'_>) -> SqlResult<HashMap<String, i64>> {
+        Ok(HashMap::new())
+    }
+
+    fn step(&self, ctx: &mut Context<'_>, counts: &mut HashMap<String, i64>) -> SqlResult<()> {
+        let value: String = ctx.get(0)?;
+        for tag in value.split(',').map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            *counts.entry(tag.to_string()).or_insert(0) += 1;
+        }
+        Ok(())
+    }
+
+    fn finalize(
+        &self,
+        _ctx: &mut Context<'_>,
+        counts: Option<HashMap<String, i64>>,
+    ) -> SqlResult<String> {
+        let mut counts: Vec<(String, i64)> = counts.unwrap_or_default().into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(counts
+            .into_iter()
+            .map(|(tag, count)| format!("{}({})", tag, count))
+            .collect::<Vec<_>>()
+            .join(", "))
+    }
+}
+
 impl SqliteObserver {
     pub fn new(settings: Arc<Settings>) -> io::Result<Self> {
-        let data_dir = Settings::get_data_dir();
-        let sqlite_dir = data_dir.join("sqlite");
-        let db_path = sqlite_dir.join("frontmatter.db");
+        debug!("Initializing shared database connection");
+        let conn = db::open_connection(&settings)?;
 
-        debug!("Creating SQLite directory at {:?}", sqlite_dir);
-        std::fs::create_dir_all(&sqlite_dir)?;
+        debug!("Attaching change-tracking session");
+        let session = {
+            let guard = conn.blocking_lock();
+            // SAFETY: `conn` is an `Arc<Mutex<Connection>>` allocated once by
+            // `db::open_connection`; its heap address is stable for the life
+            // of the Arc. `observer` below keeps a clone of that same `Arc`
+            // alive for as long as `session` exists, and `session` is
+            // declared before `conn` in the struct so it's dropped first -
+            // this borrow never outlives the connection it points to.
+            let conn_ref: &'static Connection = unsafe { &*(&*guard as *const Connection) };
+            let mut session = Session::new(conn_ref).map_err(|e| {
+                error!("Failed to create change-tracking session: {}", e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+            session.attach(Some("notes")).map_err(|e| {
+                error!("Failed to attach session to 'notes': {}", e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+            session.attach(Some("frontmatter")).map_err(|e| {
+                error!("Failed to attach session to 'frontmatter': {}", e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+            session
+        };
 
-        debug!("Initializing SQLite database at {:?}", db_path);
-        let conn = Connection::open(&db_path).map_err(|e| {
-            error!("Failed to open SQLite database: {}", e);
-            io::Error::new(io::ErrorKind::Other, e)
-        })?;
+        debug!("Registering dirty-note tracking hooks");
+        let table_refs: Arc<StdMutex<HashMap<String, HashSet<String>>>> =
+            Arc::new(StdMutex::new(HashMap::new()));
+        let dirty: Arc<StdMutex<HashSet<String>>> = Arc::new(StdMutex::new(HashSet::new()));
+        {
+            let guard = conn.blocking_lock();
+            let changed_tables: Arc<StdMutex<HashSet<String>>> =
+                Arc::new(StdMutex::new(HashSet::new()));
 
-        debug!("Creating database schema");
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS notes (
-                id INTEGER PRIMARY KEY,
-                title TEXT UNIQUE NOT NULL,
-                path TEXT NOT NULL
-            )",
-            [],
-        )
-        .map_err(|e| {
-            error!("Failed to create notes table: {}", e);
-            io::Error::new(io::ErrorKind::Other, e)
-        })?;
+            let hook_changed = Arc::clone(&changed_tables);
+            guard.update_hook(Some(
+                move |_action: Action, _db: &str, table: &str, _rowid: i64| {
+                    hook_changed.lock().unwrap().insert(table.to_string());
+                },
+            ));
 
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS frontmatter (
-                file_id INTEGER,
-                key TEXT,
-                value TEXT,
-                PRIMARY KEY (file_id, key),
-                FOREIGN KEY (file_id) REFERENCES notes(id)
-            )",
-            [],
-        )
-        .map_err(|e| {
-            error!("Failed to create frontmatter table: {}", e);
-            io::Error::new(io::ErrorKind::Other, e)
-        })?;
+            let hook_changed = Arc::clone(&changed_tables);
+            let hook_table_refs = Arc::clone(&table_refs);
+            let hook_dirty = Arc::clone(&dirty);
+            guard.commit_hook(Some(move || {
+                let changed = std::mem::take(&mut *hook_changed.lock().unwrap());
+                if !changed.is_empty() {
+                    let refs = hook_table_refs.lock().unwrap();
+                    let mut dirty = hook_dirty.lock().unwrap();
+                    for (title, tables) in refs.iter() {
+                        if tables.intersection(&changed).next().is_some() {
+                            dirty.insert(title.clone());
+                        }
+                    }
+                }
+                false // never abort the commit
+            }));
+        }
 
         debug!("Initializing LLM model");
         let model = tokio::task::block_in_place(|| {
@@ -89,12 +333,104 @@ impl SqliteObserver {
             })
         })?;
 
-        info!("âœ¨ SQLite observer initialized successfully");
-        Ok(Self {
-            conn: Arc::new(Mutex::new(conn)),
+        let observer = Self {
+            session: Mutex::new(session),
+            conn,
             model,
             settings,
+            sync_count: AtomicUsize::new(0),
+            table_refs,
+            dirty,
+        };
+        observer.register_functions()?;
+
+        info!("âœ¨ SQLite observer initialized successfully");
+        Ok(observer)
+    }
+
+    /// Installs the domain helpers every `query`/`natural_query`/
+    /// `process_sql_blocks` call can use inside a note's SQL blocks, via
+    /// rusqlite's `functions` feature. Registered once here against the
+    /// shared connection so callers never see the plumbing.
+    fn register_functions(&self) -> io::Result<()> {
+        let conn = self.conn.blocking_lock();
+        let flags = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+
+        let file_type = self.settings.file_type.clone();
+        conn.create_scalar_function("note_link", 1, flags, move |ctx| {
+            let path: String = ctx.get(0)?;
+            let title = Path::new(&path)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            Ok(format!("[{}](./{}.{})", title, title, file_type))
+        })
+        .map_err(|e| {
+            error!("Failed to register note_link function: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+
+        conn.create_scalar_function("days_since", 1, flags, |ctx| {
+            let date_value: String = ctx.get(0)?;
+            match NaiveDate::parse_from_str(date_value.trim(), "%Y-%m-%d") {
+                Ok(date) => {
+                    let days = (Utc::now().date_naive() - date).num_days();
+                    Ok(Some(days))
+                }
+                Err(_) => Ok(None),
+            }
+        })
+        .map_err(|e| {
+            error!("Failed to register days_since function: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+
+        // `fm` looks up frontmatter for an arbitrary note ID from inside a
+        // query running against the shared, already-locked connection, so it
+        // needs its own connection rather than re-entering `self.conn`.
+        let lookup_path = db::db_path(&self.settings);
+        let lookup_conn = Connection::open(&lookup_path).map_err(|e| {
+            error!("Failed to open frontmatter lookup connection: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+        conn.create_scalar_function("fm", 2, flags, move |ctx| {
+            let file_id: i64 = ctx.get(0)?;
+            let key: String = ctx.get(1)?;
+            let value: SqlResult<String> = lookup_conn.query_row(
+                "SELECT value FROM frontmatter WHERE file_id = ?1 AND key = ?2",
+                rusqlite::params![file_id, key],
+                |row| row.get(0),
+            );
+            match value {
+                Ok(value) => Ok(Some(value)),
+                Err(SqlError::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(e),
+            }
         })
+        .map_err(|e| {
+            error!("Failed to register fm function: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+
+        conn.create_aggregate_function("tag_cloud", 1, flags, TagCloud)
+            .map_err(|e| {
+                error!("Failed to register tag_cloud function: {}", e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+
+        debug!("Registered note_link, days_since, fm, and tag_cloud SQL functions");
+
+        fm_pivot::register(&conn, &self.settings)?;
+
+        // Lets a note's SQL blocks do `CREATE VIRTUAL TABLE temp.data USING
+        // csv(filename='./budget.csv', header=yes)` against CSV files kept
+        // alongside the vault, without importing them first.
+        rusqlite::vtab::csvtab::load_module(&conn).map_err(|e| {
+            error!("Failed to load csvtab module: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+
+        Ok(())
     }
 
     async fn store_frontmatter(
@@ -130,6 +466,198 @@ impl SqliteObserver {
         Ok(())
     }
 
+    /// Indexes a note's stripped markdown body into `notes_fts`, keyed by
+    /// title the same way `store_frontmatter` keys `frontmatter` - delete
+    /// then re-insert, since FTS5 has no upsert.
+    async fn index_fts(&self, title: &str, content: &str) -> SqlResult<()> {
+        debug!("Indexing note for full-text search: {}", title);
+        let conn = self.conn.lock().await;
+
+        conn.execute("DELETE FROM notes_fts WHERE title = ?1", [title])?;
+        conn.execute(
+            "INSERT INTO notes_fts (title, body) VALUES (?1, ?2)",
+            [title, content],
+        )?;
+
+        debug!("Successfully indexed '{}' for full-text search", title);
+        Ok(())
+    }
+
+    /// Copies the live database to a timestamped file under
+    /// `data_dir/sqlite/backups/` using rusqlite's online backup API, so the
+    /// copy stays consistent even while the shared connection is in use
+    /// elsewhere. `dest` overrides the default timestamped path; afterwards
+    /// only the `sqlite_backup_retention` most recent backups are kept.
+    pub async fn backup(&self, dest: Option<&Path>) -> io::Result<PathBuf> {
+        let backups_dir = db::db_path(&self.settings)
+            .parent()
+            .map(|parent| parent.join("backups"))
+            .unwrap_or_else(|| PathBuf::from("backups"));
+        fs::create_dir_all(&backups_dir)?;
+
+        let dest_path = match dest {
+            Some(path) => path.to_path_buf(),
+            None => backups_dir.join(format!(
+                "frontmatter-{}.db",
+                Utc::now().format("%Y%m%d%H%M%S")
+            )),
+        };
+
+        debug!("Backing up database to {:?}", dest_path);
+        let conn = self.conn.lock().await;
+        let mut dst_conn = Connection::open(&dest_path).map_err(|e| {
+            error!("Failed to open backup destination {:?}: {}", dest_path, e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+        Backup::new(&conn, &mut dst_conn)
+            .and_then(|backup| backup.run_to_completion(100, Duration::from_millis(0), None))
+            .map_err(|e| {
+                error!("Failed to back up database: {}", e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+        drop(conn);
+
+        self.prune_old_backups(&backups_dir)?;
+
+        info!("âœ¨ Backed up frontmatter database to {:?}", dest_path);
+        Ok(dest_path)
+    }
+
+    /// Restores the live database in place from a previously written backup
+    /// file, via the same online backup mechanism run with source and
+    /// destination swapped.
+    pub async fn restore(&self, from: &Path) -> io::Result<()> {
+        debug!("Restoring database from {:?}", from);
+        let src_conn = Connection::open(from).map_err(|e| {
+            error!("Failed to open backup source {:?}: {}", from, e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+        let mut conn = self.conn.lock().await;
+        Backup::new(&src_conn, &mut conn)
+            .and_then(|backup| backup.run_to_completion(100, Duration::from_millis(0), None))
+            .map_err(|e| {
+                error!("Failed to restore database: {}", e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+
+        info!("âœ¨ Restored frontmatter database from {:?}", from);
+        Ok(())
+    }
+
+    /// Deletes all but the `settings.sqlite_backup_retention` most recent
+    /// backup files in `backups_dir` - the fixed-width `%Y%m%d%H%M%S` names
+    /// sort lexicographically in chronological order, so a plain sort finds
+    /// the oldest ones. A retention of `0` disables pruning entirely.
+    fn prune_old_backups(&self, backups_dir: &Path) -> io::Result<()> {
+        let retention = self.settings.sqlite_backup_retention;
+        if retention == 0 {
+            return Ok(());
+        }
+
+        let mut backups: Vec<PathBuf> = fs::read_dir(backups_dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("db"))
+            .collect();
+        backups.sort();
+
+        if backups.len() > retention {
+            for path in &backups[..backups.len() - retention] {
+                match fs::remove_file(path) {
+                    Ok(_) => debug!("Pruned old backup {:?}", path),
+                    Err(e) => error!("Failed to prune old backup {:?}: {}", path, e),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Serializes every `notes`/`frontmatter` mutation accumulated since the
+    /// last call (or since startup) into a portable SQLite changeset, so
+    /// another noorg instance can replay it via `apply_changeset` instead of
+    /// re-observing every note.
+    ///
+    /// `session` holds a raw `'static` alias of the connection `conn` guards
+    /// (see the safety comment in `new()`), so it's only sound to touch the
+    /// session while also holding `conn`'s lock - otherwise this can run
+    /// concurrently with `query`/`store_frontmatter`/etc, all of which lock
+    /// `conn` directly against the same non-`Sync` `rusqlite::Connection`.
+    pub async fn export_changeset(&self) -> io::Result<Vec<u8>> {
+        let _conn = self.conn.lock().await;
+        let mut session = self.session.lock().await;
+        let mut buf = Vec::new();
+        session.changeset_strm(&mut buf).map_err(|e| {
+            error!("Failed to export changeset: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+
+        debug!("Exported changeset ({} bytes)", buf.len());
+        Ok(buf)
+    }
+
+    /// Applies a changeset produced by another instance's `export_changeset`
+    /// to the live database: an incoming row wins over local state on
+    /// `DATA`/`CONFLICT` conflicts, and rows the local database has already
+    /// lost track of are skipped on `NOTFOUND` rather than resurrected.
+    pub async fn apply_changeset(&self, data: &[u8]) -> io::Result<()> {
+        let conn = self.conn.lock().await;
+        let mut input = data;
+        conn.apply_strm(
+            &mut input,
+            None::<fn(&str) -> bool>,
+            |conflict_type, _item| match conflict_type {
+                ConflictType::Data | ConflictType::Conflict => ConflictAction::Replace,
+                ConflictType::NotFound => ConflictAction::Omit,
+                _ => ConflictAction::Abort,
+            },
+        )
+        .map_err(|e| {
+            error!("Failed to apply changeset: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+
+        debug!("Applied changeset ({} bytes)", data.len());
+        Ok(())
+    }
+
+    /// Runs `terms` as an FTS5 `MATCH` expression against `notes_fts`,
+    /// returning hits ranked by `bm25`, best match first.
+    pub async fn search(&self, terms: &str) -> io::Result<QueryResult> {
+        debug!("Running full-text search: {}", terms);
+        let conn = self.conn.lock().await;
+        let mut stmt = conn
+            .prepare(
+                "SELECT n.id, n.title, n.path, bm25(notes_fts) AS rank
+                 FROM notes_fts
+                 JOIN notes n ON n.rowid = notes_fts.rowid
+                 WHERE notes_fts MATCH ?1
+                 ORDER BY rank",
+            )
+            .map_err(|e| {
+                error!("Failed to prepare full-text search statement: {}", e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+
+        let columns: Vec<String> = stmt
+            .column_names()
+            .into_iter()
+            .map(|c| c.to_string())
+            .collect();
+
+        let rows = stmt
+            .query_map([terms], |row| row_to_cells(row, &columns))
+            .map_err(|e| {
+                error!("Failed to execute full-text search: {}", e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        debug!("Full-text search returned {} rows", rows.len());
+        Ok(QueryResult { columns, rows })
+    }
+
     pub async fn natural_query(&self, query: &str) -> io::Result<QueryResult> {
         debug!("Processing natural language query: {}", query);
 
@@ -141,7 +669,8 @@ impl SqliteObserver {
              Database tables:
              notes (id INTEGER PRIMARY KEY, title TEXT, path TEXT)
              frontmatter (file_id INTEGER, key TEXT, value TEXT)
-             
+             notes_fts (title, body) -- FTS5 virtual table indexing each note's full content
+
              Rules:
              1. Use proper table aliases (n for notes, f for frontmatter)
              2. Join using: ON n.id = f.file_id
@@ -149,13 +678,19 @@ impl SqliteObserver {
              4. Use single quotes for string values
              5. Always include n.id, n.title, n.path in SELECT clause
              6. Return only the raw SQL query, nothing else
-             
+             7. If the question is about a note's content or prose rather than its
+                frontmatter, query notes_fts with MATCH instead of LIKE, joining
+                back to notes via rowid, and order by bm25(notes_fts)
+
              Examples:
              Q: show notes tagged with rust
              A: SELECT n.id, n.title, n.path FROM notes n JOIN frontmatter f ON n.id = f.file_id WHERE f.key = 'tags' AND f.value LIKE '%rust%'
-             
+
              Q: find all notes
-             A: SELECT n.id, n.title, n.path FROM notes n"
+             A: SELECT n.id, n.title, n.path FROM notes n
+
+             Q: find notes mentioning kubernetes
+             A: SELECT n.id, n.title, n.path, bm25(notes_fts) AS rank FROM notes_fts JOIN notes n ON n.rowid = notes_fts.rowid WHERE notes_fts MATCH 'kubernetes' ORDER BY rank"
         );
 
         let mut response = String::new();
@@ -204,14 +739,7 @@ impl SqliteObserver {
             .collect();
 
         let rows = stmt
-            .query_map([], |row| {
-                let mut map = HashMap::new();
-                for (i, column) in columns.iter().enumerate() {
-                    let value: String = row.get(i).unwrap_or_else(|_| "".to_string());
-                    map.insert(column.clone(), value);
-                }
-                Ok(map)
-            })
+            .query_map([], |row| row_to_cells(row, &columns))
             .map_err(|e| {
                 error!("Failed to execute query: {}", e);
                 io::Error::new(io::ErrorKind::Other, e)
@@ -258,7 +786,10 @@ impl SqliteObserver {
         Ok(())
     }
 
-    pub async fn process_sql_blocks(&self, content: &str) -> io::Result<String> {
+    /// `note_dir` anchors any `csv(filename='...')` vtable a block creates:
+    /// relative filenames resolve against the note's own directory rather
+    /// than the process's working directory.
+    pub async fn process_sql_blocks(&self, content: &str, note_dir: &Path) -> io::Result<String> {
         let sql_blocks = self.extract_sql_blocks(content);
 
         if sql_blocks.is_empty() {
@@ -272,23 +803,67 @@ impl SqliteObserver {
 
         // Process blocks in reverse to maintain correct positions
         for block in sql_blocks.into_iter().rev() {
-            let results = self.query(&block.sql).await?;
+            let resolved_sql = resolve_csv_filenames(&block.sql, note_dir);
+            let (csv_setup, query_sql, temp_table) = split_csv_setup(&resolved_sql);
+
+            if let Some(setup) = &csv_setup {
+                if let Err(e) = self.run_csv_setup(setup).await {
+                    error!("Failed to register CSV virtual table: {}", e);
+                }
+            }
+
+            let results = match block.kind {
+                BlockKind::Sql => self.query(&query_sql).await?,
+                BlockKind::Search => self.search(&block.sql).await?,
+            };
+
+            if let Some(table) = &temp_table {
+                if let Err(e) = self.drop_temp_table(table).await {
+                    error!("Failed to drop temp CSV virtual table '{}': {}", table, e);
+                }
+            }
 
             // Build the replacement content
             let mut output = String::new();
-            output.push_str("```sql\n");
+            output.push_str("```");
+            output.push_str(block.kind.fence());
+            output.push('\n');
             output.push_str(&block.sql);
             output.push_str("\n```\n");
             output.push_str("<!-- BEGIN SQL -->\n");
 
+            // A column renders right-aligned when every non-null value in it
+            // is numeric, so integer/real results don't read as left-aligned
+            // text while everything else keeps its natural alignment.
+            let right_aligned: Vec<bool> = results
+                .columns
+                .iter()
+                .map(|col| {
+                    let mut saw_numeric = false;
+                    for row in &results.rows {
+                        match row.get(col.as_str()) {
+                            Some(cell) if cell.is_numeric() => saw_numeric = true,
+                            Some(CellValue::Null) | None => {}
+                            _ => return false,
+                        }
+                    }
+                    saw_numeric
+                })
+                .collect();
+
             // Add table header
             output.push_str("| ");
             output.push_str(&results.columns.join(" | "));
             output.push_str(" |\n|");
-            output.push_str(&vec!["---"; results.columns.len()].join("|"));
+            output.push_str(
+                &right_aligned
+                    .iter()
+                    .map(|right| if *right { "---:" } else { "---" })
+                    .collect::<Vec<_>>()
+                    .join("|"),
+            );
             output.push_str("|\n");
 
-            let default_string = String::new();
             // Add table rows
             for row in &results.rows {
                 output.push_str("| ");
@@ -296,9 +871,10 @@ impl SqliteObserver {
                     .columns
                     .iter()
                     .map(|col| {
-                        let val = row.get(col.as_str()).unwrap_or(&default_string);
+                        let cell = row.get(col.as_str());
                         if col == "path" {
                             // Extract title from the full path
+                            let val = cell.map(CellValue::to_string).unwrap_or_default();
                             let path = Path::new(&val);
                             let title = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
 
@@ -308,7 +884,10 @@ impl SqliteObserver {
                             // Format link using relative path
                             format!("[{}]({})", title, relative_path)
                         } else {
-                            val.trim().to_string()
+                            match cell {
+                                Some(CellValue::Null) | None => "*NULL*".to_string(),
+                                Some(val) => val.to_string().trim().to_string(),
+                            }
                         }
                     })
                     .collect();
@@ -330,13 +909,74 @@ impl SqliteObserver {
         Ok(new_content)
     }
 
+    /// Runs the `CREATE VIRTUAL TABLE temp....USING csv(...)` statement a
+    /// block split off via [`split_csv_setup`], registering the temp vtable
+    /// for the lifetime of the connection's `temp` schema (i.e. until
+    /// `drop_temp_table` removes it just after this block's query runs).
+    async fn run_csv_setup(&self, setup_sql: &str) -> io::Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute_batch(setup_sql)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    async fn drop_temp_table(&self, table: &str) -> io::Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute_batch(&format!("DROP TABLE IF EXISTS temp.{};", table))
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    /// Refreshes `title`'s entry in `table_refs`, naively scanning each
+    /// block's SQL text for the table/virtual-table names this observer
+    /// knows about rather than fully parsing SQL.
+    fn update_table_refs(&self, title: &str, blocks: &[SqlBlock]) {
+        const KNOWN_TABLES: &[&str] = &["notes", "frontmatter", "notes_fts", "fm_pivot"];
+
+        let mut tables = HashSet::new();
+        for block in blocks {
+            for &table in KNOWN_TABLES {
+                if block.sql.contains(table) {
+                    tables.insert(table.to_string());
+                }
+            }
+        }
+
+        let mut refs = self.table_refs.lock().unwrap();
+        if tables.is_empty() {
+            refs.remove(title);
+        } else {
+            refs.insert(title.to_string(), tables);
+        }
+    }
+
+    /// Drains the set of note titles whose SQL blocks reference a table
+    /// that changed in a transaction committed since the last call, so the
+    /// sync pipeline can re-run `process_sql_blocks` only on notes actually
+    /// affected by someone else's edit.
+    pub fn dirty_notes(&self) -> Vec<String> {
+        self.dirty.lock().unwrap().drain().collect()
+    }
+
     fn extract_sql_blocks(&self, content: &str) -> Vec<SqlBlock> {
         let mut blocks = Vec::new();
         let lines: Vec<&str> = content.lines().collect();
         let mut i = 0;
 
         while i < lines.len() {
-            if lines[i].trim().starts_with("```sql") && !content[..i].contains("## Contents") {
+            let trimmed = lines[i].trim();
+            let kind = if trimmed.starts_with("```sql") {
+                Some(BlockKind::Sql)
+            } else if trimmed.starts_with("```search") {
+                Some(BlockKind::Search)
+            } else {
+                None
+            };
+
+            if let Some(kind) = kind {
+                if content[..i].contains("## Contents") {
+                    i += 1;
+                    continue;
+                }
+
                 let start_line = i;
                 let mut sql = String::new();
 
@@ -371,8 +1011,8 @@ impl SqliteObserver {
                 let mut end_line = i;
                 while end_line < lines.len() {
                     let line = lines[end_line].trim();
-                    if line.starts_with("```sql") {
-                        // Next SQL block starts
+                    if line.starts_with("```sql") || line.starts_with("```search") {
+                        // Next block starts
                         break;
                     }
                     if line == "<!-- END SQL -->" {
@@ -404,6 +1044,7 @@ impl SqliteObserver {
                 );
 
                 blocks.push(SqlBlock {
+                    kind,
                     sql: sql.trim().to_string(),
                     range: (start_pos, end_pos),
                 });
@@ -434,6 +1075,11 @@ impl NoteObserver for SqliteObserver {
                 } => {
                     info!("ðŸ”„ Processing note '{}' with SQLite observer", title);
 
+                    let note_dir = Path::new(&file_path)
+                        .parent()
+                        .map(Path::to_path_buf)
+                        .unwrap_or_else(|| PathBuf::from("."));
+
                     match self
                         .store_frontmatter(&title, &frontmatter, file_path)
                         .await
@@ -442,20 +1088,41 @@ impl NoteObserver for SqliteObserver {
                         Err(e) => error!("Failed to store frontmatter for '{}': {}", title, e),
                     }
 
-                    if self.extract_sql_blocks(&content).is_empty() {
+                    match self.index_fts(&title, &content).await {
+                        Ok(_) => debug!("Successfully indexed '{}' for full-text search", title),
+                        Err(e) => error!("Failed to index '{}' for full-text search: {}", title, e),
+                    }
+
+                    if self.settings.sqlite_backup_enabled
+                        && self.settings.sqlite_backup_interval > 0
+                    {
+                        let processed = self.sync_count.fetch_add(1, Ordering::Relaxed) + 1;
+                        if processed % self.settings.sqlite_backup_interval == 0 {
+                            match self.backup(None).await {
+                                Ok(path) => info!("âœ¨ Rolling backup written to {:?}", path),
+                                Err(e) => error!("Rolling backup failed: {}", e),
+                            }
+                        }
+                    }
+
+                    let blocks = self.extract_sql_blocks(&content);
+                    self.update_table_refs(&title, &blocks);
+
+                    if blocks.is_empty() {
                         debug!(
                             "No SQL blocks found in note '{}', skipping processing",
                             title
                         );
                         Ok(None)
                     } else {
-                        match self.process_sql_blocks(&content).await {
+                        match self.process_sql_blocks(&content, &note_dir).await {
                             Ok(processed_content) => {
                                 info!("âœ¨ Successfully processed SQL blocks for '{}'", title);
                                 debug!("SQL OBSERVER: Processed content:\n{}", processed_content);
                                 Ok(Some(ObserverResult {
                                     metadata: None,
                                     content: Some(processed_content),
+                                    ..Default::default()
                                 }))
                             }
                             Err(e) => {
@@ -479,6 +1146,14 @@ impl NoteObserver for SqliteObserver {
     }
 
     fn priority(&self) -> i32 {
-        100 // Make sure SQLite runs last
+        -100 // Tie-breaker only now; depends_on is what actually makes SQLite run last
+    }
+
+    fn depends_on(&self) -> Vec<String> {
+        vec![
+            "timestamp".to_string(),
+            "tag_index".to_string(),
+            "toc".to_string(),
+        ]
     }
 }
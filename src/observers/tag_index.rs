@@ -1,10 +1,10 @@
 use tracing::{debug, info};
 
-use crate::event::{NoteEvent, NoteObserver, ObserverResult};
+use crate::event::{Bucketable, NoteEvent, NoteObserver, ObserverResult};
 use crate::settings::Settings;
 use std::any::Any;
 use std::collections::{BTreeMap, HashMap};
-use std::fs::File;
+use std::fs::{self, File};
 use std::future::Future;
 use std::io::{self, Read, Write};
 use std::path::Path;
@@ -64,8 +64,9 @@ impl TagIndexObserver {
         Ok(index)
     }
 
-    fn write_index(&self, index: &BTreeMap<String, Vec<(String, String)>>) -> io::Result<()> {
-        // First read existing content to preserve frontmatter
+    /// Renders the index file's full contents, preserving any frontmatter
+    /// block already present at the top of the existing file.
+    fn render_index(&self, index: &BTreeMap<String, Vec<(String, String)>>) -> String {
         let existing_content = if let Ok(mut content) =
             File::open(&self.index_path).and_then(|mut f| {
                 let mut content = String::new();
@@ -92,24 +93,40 @@ impl TagIndexObserver {
             None
         };
 
-        let mut file = File::create(&self.index_path)?;
-
-        // Write frontmatter if it exists
+        let mut rendered = String::new();
         if let Some(fm) = frontmatter {
-            writeln!(file, "{}\n", fm)?;
+            rendered.push_str(&fm);
+            rendered.push_str("\n\n");
         }
 
-        writeln!(file, "# _tag_index\n")?;
+        rendered.push_str("# _tag_index\n\n");
 
         for (tag, entries) in index {
-            writeln!(file, "## {}\n", tag)?;
-
+            rendered.push_str(&format!("## {}\n\n", tag));
             for (title, path) in entries {
-                writeln!(file, "- [{}]({})", title, path)?;
+                rendered.push_str(&format!("- [{}]({})\n", title, path));
             }
-            writeln!(file)?;
+            rendered.push('\n');
         }
 
+        rendered
+    }
+
+    fn write_index(&self, index: &BTreeMap<String, Vec<(String, String)>>) -> io::Result<()> {
+        let rendered = self.render_index(index);
+        let mut file = File::create(&self.index_path)?;
+        write!(file, "{}", rendered)?;
+        Ok(())
+    }
+
+    /// Same as [`Self::write_index`] but writes to a temp file next to the
+    /// index and renames it into place, so a full [`Bucketable::write_buckets`]
+    /// rebuild never leaves readers seeing a half-written index.
+    fn write_index_atomic(&self, index: &BTreeMap<String, Vec<(String, String)>>) -> io::Result<()> {
+        let rendered = self.render_index(index);
+        let tmp_path = format!("{}.tmp", self.index_path);
+        fs::write(&tmp_path, rendered)?;
+        fs::rename(&tmp_path, &self.index_path)?;
         Ok(())
     }
 
@@ -208,6 +225,7 @@ impl NoteObserver for TagIndexObserver {
                         Ok(Some(ObserverResult {
                             metadata: Some(metadata),
                             content: None,
+                            ..Default::default()
                         }))
                     } else {
                         Ok(None)
@@ -228,4 +246,57 @@ impl NoteObserver for TagIndexObserver {
     fn priority(&self) -> i32 {
         -99 // Run after metadata generation but before storage
     }
+
+    fn as_bucketable(&self) -> Option<&dyn Bucketable> {
+        Some(self)
+    }
+}
+
+impl Bucketable for TagIndexObserver {
+    fn bucket_note(&self, event: &NoteEvent) -> HashMap<String, Vec<serde_json::Value>> {
+        let mut buckets = HashMap::new();
+
+        let (title, frontmatter) = match event {
+            NoteEvent::Created {
+                title, frontmatter, ..
+            }
+            | NoteEvent::Updated {
+                title, frontmatter, ..
+            }
+            | NoteEvent::Synced {
+                title, frontmatter, ..
+            } => (title, frontmatter),
+        };
+
+        if let Some(tags) = frontmatter.get("tags") {
+            for tag in tags.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+                let file_path = format!("./{}.{}", title, self.settings.file_type);
+                buckets
+                    .entry(tag.to_string())
+                    .or_insert_with(Vec::new)
+                    .push(serde_json::json!({ "title": title, "path": file_path }));
+            }
+        }
+
+        buckets
+    }
+
+    fn write_buckets(&self, buckets: HashMap<String, Vec<serde_json::Value>>) -> io::Result<()> {
+        let mut index: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+
+        for (tag, entries) in buckets {
+            let mut pairs: Vec<(String, String)> = entries
+                .into_iter()
+                .filter_map(|entry| {
+                    let title = entry.get("title")?.as_str()?.to_string();
+                    let path = entry.get("path")?.as_str()?.to_string();
+                    Some((title, path))
+                })
+                .collect();
+            pairs.sort_by(|a, b| a.0.cmp(&b.0));
+            index.insert(tag, pairs);
+        }
+
+        self.write_index_atomic(&index)
+    }
 }
@@ -0,0 +1,193 @@
+use crate::event::{NoteEvent, NoteObserver, ObserverResult};
+use crate::settings::Settings;
+use handlebars::Handlebars;
+use serde::Serialize;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+
+#[derive(Serialize)]
+struct TemplateData {
+    title: String,
+    content: String,
+    file_path: String,
+    frontmatter: HashMap<String, String>,
+    tags: Vec<String>,
+}
+
+pub struct TemplateRenderObserver {
+    templates_dir: String,
+    settings: Arc<Settings>,
+}
+
+impl TemplateRenderObserver {
+    pub fn new(settings: Arc<Settings>) -> io::Result<Self> {
+        let templates_dir = Path::new(&settings.scripts_dir)
+            .join("templates")
+            .to_string_lossy()
+            .into_owned();
+
+        fs::create_dir_all(&templates_dir)?;
+
+        Ok(Self {
+            templates_dir,
+            settings,
+        })
+    }
+
+    fn build_data(
+        title: &str,
+        content: &str,
+        file_path: &str,
+        frontmatter: &HashMap<String, String>,
+    ) -> TemplateData {
+        let tags = frontmatter
+            .get("tags")
+            .map(|t| t.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+
+        TemplateData {
+            title: title.to_string(),
+            content: content.to_string(),
+            file_path: file_path.to_string(),
+            frontmatter: frontmatter.clone(),
+            tags,
+        }
+    }
+
+    fn render_template(&self, template_path: &Path, data: &TemplateData) -> io::Result<String> {
+        let template_source = fs::read_to_string(template_path)?;
+        let mut handlebars = Handlebars::new();
+        handlebars
+            .register_template_string("note", &template_source)
+            .map_err(|e| {
+                error!("Failed to register template {:?}: {}", template_path, e);
+                io::Error::new(io::ErrorKind::Other, e)
+            })?;
+
+        handlebars.render("note", data).map_err(|e| {
+            error!("Failed to render template {:?}: {}", template_path, e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })
+    }
+
+    fn render_json(&self, data: &TemplateData) -> io::Result<String> {
+        serde_json::to_string_pretty(data).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn active_templates(&self) -> Vec<std::path::PathBuf> {
+        let mut templates = Vec::new();
+        let dir = Path::new(&self.templates_dir);
+        if !dir.exists() {
+            return templates;
+        }
+
+        match fs::read_dir(dir) {
+            Ok(entries) => {
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    if path.extension().and_then(|e| e.to_str()) == Some("hbs") {
+                        templates.push(path);
+                    }
+                }
+            }
+            Err(e) => warn!("Failed to read templates dir {}: {}", self.templates_dir, e),
+        }
+
+        templates
+    }
+}
+
+impl NoteObserver for TemplateRenderObserver {
+    fn on_event_boxed(
+        &self,
+        event: NoteEvent,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Option<ObserverResult>>> + Send + '_>> {
+        Box::pin(async move {
+            match event {
+                NoteEvent::Created {
+                    title,
+                    content,
+                    file_path,
+                    frontmatter,
+                }
+                | NoteEvent::Updated {
+                    title,
+                    content,
+                    file_path,
+                    frontmatter,
+                    ..
+                }
+                | NoteEvent::Synced {
+                    title,
+                    content,
+                    file_path,
+                    frontmatter,
+                    ..
+                } => {
+                    let templates = self.active_templates();
+                    if templates.is_empty() {
+                        debug!("No active templates found in {}", self.templates_dir);
+                        return Ok(None);
+                    }
+
+                    let data = Self::build_data(&title, &content, &file_path, &frontmatter);
+                    let mut last_content = None;
+
+                    for template_path in templates {
+                        let stem = template_path
+                            .file_stem()
+                            .and_then(|s| s.to_str())
+                            .unwrap_or("template");
+
+                        let rendered = if stem.ends_with(".json") {
+                            self.render_json(&data)?
+                        } else {
+                            self.render_template(&template_path, &data)?
+                        };
+
+                        if stem == "content" {
+                            // Special-cased template name: feed straight back as the note body.
+                            last_content = Some(rendered);
+                        } else {
+                            let output_dir = Path::new(&self.settings.site_dir).join("rendered");
+                            fs::create_dir_all(&output_dir)?;
+                            let output_path = output_dir.join(format!("{}-{}.out", title, stem));
+                            fs::write(&output_path, &rendered)?;
+                            debug!("Rendered template '{}' for '{}' to {:?}", stem, title, output_path);
+                        }
+                    }
+
+                    if let Some(content) = last_content {
+                        info!("✨ Rendered template content for '{}'", title);
+                        Ok(Some(ObserverResult {
+                            metadata: None,
+                            content: Some(content),
+                            ..Default::default()
+                        }))
+                    } else {
+                        Ok(None)
+                    }
+                }
+            }
+        })
+    }
+
+    fn name(&self) -> String {
+        "template_render".to_string()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn priority(&self) -> i32 {
+        0
+    }
+}
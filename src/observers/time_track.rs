@@ -0,0 +1,283 @@
+use tracing::{debug, info};
+
+use crate::event::{NoteEvent, NoteObserver, ObserverResult};
+use crate::note::Note;
+use crate::settings::Settings;
+use crate::utils::get_fs_path;
+use chrono::{DateTime, Duration, Local, NaiveTime, TimeZone};
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Tracks time spent working on a note. Intervals accumulate in a note's
+/// `tracked:` frontmatter field as comma-separated `start..end` RFC3339
+/// pairs (opened via [`Self::start`], closed via [`Self::stop`]); the
+/// observer itself just aggregates that list into a `time_spent` metadata
+/// field whenever the note is processed.
+pub struct TimeTrackObserver {
+    settings: Arc<Settings>,
+}
+
+impl TimeTrackObserver {
+    pub fn new(settings: Arc<Settings>) -> io::Result<Self> {
+        Ok(Self { settings })
+    }
+
+    fn parse_tracked(tracked: &str) -> Vec<(DateTime<Local>, DateTime<Local>)> {
+        tracked
+            .split(',')
+            .filter_map(|entry| {
+                let (start, end) = entry.trim().split_once("..")?;
+                let start = DateTime::parse_from_rfc3339(start.trim())
+                    .ok()?
+                    .with_timezone(&Local);
+                let end = DateTime::parse_from_rfc3339(end.trim())
+                    .ok()?
+                    .with_timezone(&Local);
+                Some((start, end))
+            })
+            .collect()
+    }
+
+    /// Opens a new tracking interval on `title`, anchored at `when` (parsed
+    /// via [`parse_offset`]) or now. Errors if the note already has one open.
+    pub async fn start(&self, title: &str, when: Option<&str>) -> io::Result<()> {
+        let anchor = Self::resolve_anchor(when)?;
+
+        let path = get_fs_path(title, &self.settings);
+        let (content, mut frontmatter) = Note::from_file(&path)?.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("note '{}' not found", title))
+        })?;
+
+        if frontmatter.contains_key("tracking_since") {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{}' already has an open tracking interval", title),
+            ));
+        }
+
+        frontmatter.insert("tracking_since".to_string(), anchor.to_rfc3339());
+
+        Note::new(title.to_string(), content, frontmatter)
+            .await
+            .save(&self.settings)
+            .await?;
+
+        info!("⏱️ Started tracking '{}' at {}", title, anchor.to_rfc3339());
+        Ok(())
+    }
+
+    /// Closes `title`'s open tracking interval, appending `tracking_since..when`
+    /// to `tracked:`. Errors if nothing is currently open.
+    pub async fn stop(&self, title: &str, when: Option<&str>) -> io::Result<()> {
+        let anchor = Self::resolve_anchor(when)?;
+
+        let path = get_fs_path(title, &self.settings);
+        let (content, mut frontmatter) = Note::from_file(&path)?.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("note '{}' not found", title))
+        })?;
+
+        let started_at = frontmatter.remove("tracking_since").ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{}' has no open tracking interval", title),
+            )
+        })?;
+        let started_at = DateTime::parse_from_rfc3339(&started_at)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?
+            .with_timezone(&Local);
+
+        let mut intervals = frontmatter
+            .get("tracked")
+            .map(|t| Self::parse_tracked(t))
+            .unwrap_or_default();
+        intervals.push((started_at, anchor));
+
+        let tracked = intervals
+            .iter()
+            .map(|(start, end)| format!("{}..{}", start.to_rfc3339(), end.to_rfc3339()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        frontmatter.insert("tracked".to_string(), tracked);
+
+        Note::new(title.to_string(), content, frontmatter)
+            .await
+            .save(&self.settings)
+            .await?;
+
+        info!("⏹️ Stopped tracking '{}' at {}", title, anchor.to_rfc3339());
+        Ok(())
+    }
+
+    fn resolve_anchor(when: Option<&str>) -> io::Result<DateTime<Local>> {
+        match when {
+            None => Ok(Local::now()),
+            Some(input) => parse_offset(input).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("could not parse time offset '{}'", input),
+                )
+            }),
+        }
+    }
+}
+
+/// Parses a human-friendly time offset into a concrete timestamp resolved
+/// against `chrono::Local::now()`. Accepts a leading sign or `in` (`-1d`,
+/// `in 2 fortnights`), an absolute anchor (`yesterday`/`today`/`tomorrow`,
+/// optionally followed by `HH:MM`), or a relative quantity+unit - `minute`,
+/// `hour`, `day`, `week`, `fortnight` (14 days) - with multiple
+/// space-separated quantities summed (`1 day 2 hours`).
+pub fn parse_offset(input: &str) -> Option<DateTime<Local>> {
+    let trimmed = input.trim();
+
+    let (sign, rest) = if let Some(stripped) = trimmed.strip_prefix('-') {
+        (-1, stripped.trim_start())
+    } else if let Some(stripped) = trimmed.strip_prefix('+') {
+        (1, stripped.trim_start())
+    } else if let Some(stripped) = trimmed
+        .strip_prefix("in ")
+        .or_else(|| trimmed.strip_prefix("In "))
+    {
+        (1, stripped.trim_start())
+    } else {
+        (1, trimmed)
+    };
+
+    if let Some(anchor) = parse_anchor(rest) {
+        return Some(anchor);
+    }
+
+    let duration = parse_relative(rest)?;
+    Some(if sign < 0 {
+        Local::now() - duration
+    } else {
+        Local::now() + duration
+    })
+}
+
+fn parse_anchor(rest: &str) -> Option<DateTime<Local>> {
+    let mut parts = rest.splitn(2, char::is_whitespace);
+    let day_word = parts.next()?.to_lowercase();
+    let time_part = parts.next().map(str::trim).filter(|t| !t.is_empty());
+
+    let base_date = match day_word.as_str() {
+        "yesterday" => Local::now().date_naive() - Duration::days(1),
+        "today" => Local::now().date_naive(),
+        "tomorrow" => Local::now().date_naive() + Duration::days(1),
+        _ => return None,
+    };
+
+    let time = match time_part {
+        Some(t) => NaiveTime::parse_from_str(t, "%H:%M").ok()?,
+        None => Local::now().time(),
+    };
+
+    Local.from_local_datetime(&base_date.and_time(time)).single()
+}
+
+fn parse_relative(rest: &str) -> Option<Duration> {
+    let normalized = normalize_quantity_spacing(rest);
+    let tokens: Vec<&str> = normalized
+        .split_whitespace()
+        .filter(|t| !t.eq_ignore_ascii_case("and"))
+        .collect();
+
+    if tokens.is_empty() {
+        return None;
+    }
+
+    let mut total = Duration::zero();
+    let mut index = 0;
+    while index < tokens.len() {
+        let quantity: i32 = tokens[index].parse().ok()?;
+        let unit = tokens.get(index + 1)?;
+        total = total + unit_to_duration(unit)? * quantity;
+        index += 2;
+    }
+
+    Some(total)
+}
+
+/// Inserts a space between a digit and an immediately following letter, so
+/// glued shorthand like `1d` tokenizes the same as `1 day`.
+fn normalize_quantity_spacing(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = String::with_capacity(s.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        out.push(c);
+        if let Some(&next) = chars.get(i + 1) {
+            if c.is_ascii_digit() && next.is_alphabetic() {
+                out.push(' ');
+            }
+        }
+    }
+    out
+}
+
+fn unit_to_duration(unit: &str) -> Option<Duration> {
+    match unit.trim_end_matches('s').to_lowercase().as_str() {
+        "minute" | "min" | "m" => Some(Duration::minutes(1)),
+        "hour" | "hr" | "h" => Some(Duration::hours(1)),
+        "day" | "d" => Some(Duration::days(1)),
+        "week" | "wk" | "w" => Some(Duration::weeks(1)),
+        "fortnight" => Some(Duration::days(14)),
+        _ => None,
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let total_minutes = duration.num_minutes();
+    let hours = total_minutes / 60;
+    let minutes = total_minutes % 60;
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+impl NoteObserver for TimeTrackObserver {
+    fn on_event_boxed(
+        &self,
+        event: NoteEvent,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Option<ObserverResult>>> + Send + '_>> {
+        Box::pin(async move {
+            let tracked = match event.frontmatter().get("tracked") {
+                Some(tracked) => tracked.clone(),
+                None => return Ok(None),
+            };
+
+            let total = Self::parse_tracked(&tracked)
+                .into_iter()
+                .fold(Duration::zero(), |acc, (start, end)| acc + (end - start));
+            let time_spent = format_duration(total);
+
+            debug!("⏱️ Aggregated time_spent: {}", time_spent);
+
+            let mut metadata = HashMap::new();
+            metadata.insert("time_spent".to_string(), time_spent);
+
+            Ok(Some(ObserverResult {
+                metadata: Some(metadata),
+                content: None,
+                ..Default::default()
+            }))
+        })
+    }
+
+    fn name(&self) -> String {
+        "time_track".to_string()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn priority(&self) -> i32 {
+        -99 // Run after metadata generation but before storage
+    }
+}
@@ -57,6 +57,7 @@ impl NoteObserver for TimestampObserver {
             Ok(Some(ObserverResult {
                 metadata: Some(metadata),
                 content: None,
+                ..Default::default()
             }))
         })
     }
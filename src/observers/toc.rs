@@ -1,18 +1,22 @@
 use crate::event::{NoteEvent, NoteObserver, ObserverResult};
+use crate::settings::Settings;
 use pulldown_cmark::{Event as MarkdownEvent, HeadingLevel, Parser, Tag};
 use std::any::Any;
 use std::collections::HashMap;
 use std::future::Future;
 use std::io;
 use std::pin::Pin;
+use std::sync::Arc;
 use tracing::{debug, info};
 
-pub struct TocObserver;
+pub struct TocObserver {
+    settings: Arc<Settings>,
+}
 
 impl TocObserver {
-    pub fn new() -> Self {
+    pub fn new(settings: Arc<Settings>) -> Self {
         debug!("Initializing TOC observer");
-        TocObserver
+        TocObserver { settings }
     }
 
     fn generate_toc(&self, content: &str) -> Option<String> {
@@ -22,6 +26,9 @@ impl TocObserver {
         let mut current_level = 0;
         let mut current_heading = String::new();
         let mut first_h1_seen = false;
+        let mut slug_counts: HashMap<String, usize> = HashMap::new();
+        let min_level = self.settings.toc.min_level.max(1);
+        let max_level = self.settings.toc.max_level.min(6).max(min_level);
 
         debug!("Collecting headings from content");
         for event in parser {
@@ -38,21 +45,30 @@ impl TocObserver {
                     };
                 }
                 MarkdownEvent::Text(text) | MarkdownEvent::Code(text) if in_heading => {
+                    // Fires for the text inside nested emphasis/strong/link
+                    // spans too (pulldown-cmark flattens those into plain
+                    // `Text` events between their `Start`/`End` markers), so
+                    // "**Bold** [_x_](#)" accumulates as "Bold x" with no
+                    // markdown syntax characters to strip out later.
                     current_heading.push_str(&text);
                 }
+                MarkdownEvent::SoftBreak | MarkdownEvent::HardBreak if in_heading => {
+                    current_heading.push(' ');
+                }
                 MarkdownEvent::End(Tag::Heading(..)) => {
                     if !current_heading.is_empty() {
+                        let is_first_h1 = current_level == 1 && !first_h1_seen;
                         if current_level == 1 {
-                            if !first_h1_seen {
-                                first_h1_seen = true;
-                                debug!("Skipping first H1 heading: {}", current_heading);
-                            } else {
-                                let anchor = self.create_anchor(&current_heading);
-                                debug!("Adding H1 heading: {} ({})", current_heading, anchor);
-                                headings.push((current_level, current_heading.clone(), anchor));
-                            }
-                        } else {
-                            let anchor = self.create_anchor(&current_heading);
+                            first_h1_seen = true;
+                        }
+
+                        let skip_as_title = is_first_h1 && self.settings.toc.skip_first_h1;
+                        let in_range = current_level >= min_level && current_level <= max_level;
+
+                        if skip_as_title {
+                            debug!("Skipping first H1 heading: {}", current_heading);
+                        } else if in_range {
+                            let anchor = self.unique_anchor(&current_heading, &mut slug_counts);
                             debug!(
                                 "Adding H{} heading: {} ({})",
                                 current_level, current_heading, anchor
@@ -75,94 +91,237 @@ impl TocObserver {
         debug!("Generating TOC with {} headings", headings.len());
         let mut toc = String::from("## Contents\n\n");
 
+        // Indent relative to the smallest level actually included, so a TOC
+        // starting at H2 (min_level = 2) isn't needlessly indented one level
+        // deep.
+        let base_level = headings.iter().map(|(level, ..)| *level).min().unwrap_or(1);
+
         for (level, heading, anchor) in headings {
-            let indent = "  ".repeat(level - 1);
+            let indent = "  ".repeat(level - base_level);
             toc.push_str(&format!("{}* [{}](#{})\n", indent, heading, anchor));
         }
 
         Some(toc.to_string())
     }
 
+    /// GitHub-compatible slugification: keep Unicode alphanumerics and
+    /// literal `-`, collapse any whitespace run into a single `-`, drop
+    /// every other punctuation character, then collapse consecutive `-` and
+    /// trim them from both ends.
     fn create_anchor(&self, heading: &str) -> String {
-        heading
-            .to_lowercase()
-            .replace(' ', "-")
-            .replace(|c: char| !c.is_alphanumeric() && c != '-', "")
+        let mut raw = String::with_capacity(heading.len());
+        let mut chars = heading.to_lowercase().chars().peekable();
+        while let Some(c) = chars.next() {
+            if c.is_whitespace() {
+                raw.push('-');
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+            } else if c.is_alphanumeric() || c == '-' {
+                raw.push(c);
+            }
+        }
+
+        let mut anchor = String::with_capacity(raw.len());
+        let mut prev_dash = false;
+        for c in raw.chars() {
+            if c == '-' {
+                if !prev_dash {
+                    anchor.push('-');
+                }
+                prev_dash = true;
+            } else {
+                anchor.push(c);
+                prev_dash = false;
+            }
+        }
+
+        anchor.trim_matches('-').to_string()
     }
 
-    fn insert_toc(&self, content: &str) -> Option<String> {
-        let toc = self.generate_toc(content)?;
-        debug!("Generated TOC content:\n{}", toc);
-        debug!("Processing content for TOC insertion");
+    /// Slugs `heading` via [`Self::create_anchor`] and, on a repeat slug,
+    /// appends `-N` (GitHub-style) using the running occurrence count in
+    /// `slug_counts`, so duplicate heading text produces distinct anchors.
+    fn unique_anchor(&self, heading: &str, slug_counts: &mut HashMap<String, usize>) -> String {
+        let base = self.create_anchor(heading);
+        let count = slug_counts.entry(base.clone()).or_insert(0);
+        let anchor = if *count == 0 {
+            base
+        } else {
+            format!("{}-{}", base, count)
+        };
+        *count += 1;
+        anchor
+    }
 
-        let lines: Vec<&str> = content.lines().collect();
-        let mut output = Vec::new();
+    /// Locates the byte spans where the TOC goes, scanning the document once
+    /// line-by-line while tracking YAML frontmatter and fenced/indented code
+    /// blocks so a `#`/marker inside either is never mistaken for a real
+    /// heading or placeholder.
+    fn scan_insertion_points(&self, content: &str) -> TocScan {
+        let marker = self.settings.toc.marker.trim();
         let mut in_frontmatter = false;
-        let mut frontmatter_end = 0;
-        let mut first_heading_found = false;
-        let mut first_heading_pos = 0;
-
-        // Find frontmatter end and first heading
-        for (i, line) in lines.iter().enumerate() {
-            if line.trim() == "---" {
-                if !in_frontmatter {
-                    in_frontmatter = true;
-                    debug!("Found start of frontmatter at line {}", i);
-                } else {
-                    frontmatter_end = i;
-                    debug!("Found end of frontmatter at line {}", i);
+        let mut fence: Option<String> = None;
+        let mut marker_span = None;
+        let mut first_heading_span = None;
+
+        for (idx, (start, end)) in line_spans(content).enumerate() {
+            let line = &content[start..end];
+            let trimmed = line.trim_end_matches(['\n', '\r']);
+            let stripped = trimmed.trim();
+
+            if idx == 0 && stripped == "---" {
+                in_frontmatter = true;
+                continue;
+            }
+            if in_frontmatter {
+                if stripped == "---" {
+                    in_frontmatter = false;
                 }
+                continue;
+            }
+
+            if let Some(token) = &fence {
+                if stripped.starts_with(token.as_str()) {
+                    fence = None;
+                }
+                continue;
+            }
+            if stripped.starts_with("```") || stripped.starts_with("~~~") {
+                fence = Some(fence_token(stripped));
+                continue;
+            }
+            if line.starts_with("    ") || line.starts_with('\t') {
+                continue; // indented code block
             }
 
-            if line.starts_with("# ") && !first_heading_found {
-                first_heading_found = true;
-                first_heading_pos = i;
-                debug!("Found first heading at line {}", i);
+            if marker_span.is_none() && !marker.is_empty() && stripped == marker {
+                marker_span = Some((start, end));
             }
+            if first_heading_span.is_none() && stripped.starts_with("# ") {
+                first_heading_span = Some((start, end));
+            }
+        }
+
+        TocScan {
+            marker_span,
+            first_heading_span,
         }
+    }
+
+    fn insert_toc(&self, content: &str) -> Option<String> {
+        let toc = self.generate_toc(content)?;
+        debug!("Generated TOC content:\n{}", toc);
 
-        // Copy frontmatter
-        for i in 0..=frontmatter_end {
-            output.push(lines[i]);
+        let scan = self.scan_insertion_points(content);
+
+        if let Some((start, end)) = scan.marker_span {
+            debug!("Splicing TOC in at marker");
+            let mut result = String::with_capacity(content.len() + toc.len());
+            result.push_str(&content[..start]);
+            result.push_str(&toc);
+            result.push_str(&content[end..]);
+            return Some(result);
         }
-        output.push(""); // Add blank line after frontmatter
 
-        // Copy content up to first heading
-        for i in (frontmatter_end + 1)..first_heading_pos {
-            output.push(lines[i]);
+        if !self.settings.toc.fallback_to_first_heading {
+            debug!("No TOC marker found and first-heading fallback is disabled, skipping");
+            return None;
         }
 
-        // Add first heading
-        output.push(lines[first_heading_pos]);
-        output.push(""); // Add blank line after heading
+        let (_, heading_end) = scan.first_heading_span?;
+        debug!("Splicing TOC in after first heading");
+
+        let mut result = String::with_capacity(content.len() + toc.len() + 4);
+        result.push_str(&content[..heading_end]);
+        if !content[..heading_end].ends_with('\n') {
+            result.push('\n');
+        }
+        result.push('\n');
+        result.push_str(&toc);
+        result.push('\n');
+        result.push_str(&strip_existing_toc(&content[heading_end..]));
 
-        // Add TOC after first heading
-        output.extend(toc.lines());
-        output.push(""); // Add blank line after TOC
+        Some(result)
+    }
+}
 
-        // Add remaining content, skipping old TOC if present
-        let mut skip_old_toc = false;
-        for i in (first_heading_pos + 1)..lines.len() {
-            let line = lines[i];
+/// Byte spans of insertion candidates found by [`TocObserver::scan_insertion_points`].
+struct TocScan {
+    marker_span: Option<(usize, usize)>,
+    first_heading_span: Option<(usize, usize)>,
+}
 
-            if line.starts_with("## Contents") || line.starts_with("## Table of Contents") {
-                skip_old_toc = true;
-                continue;
+/// Splits `content` into `(start, end)` byte spans, one per line, each
+/// including its trailing newline (if any) so re-joining the spans
+/// reproduces `content` exactly.
+fn line_spans(content: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    for line in content.split_inclusive('\n') {
+        spans.push((start, start + line.len()));
+        start += line.len();
+    }
+    spans
+}
+
+/// The run of backticks/tildes a fence-opening line starts with, used to
+/// recognize the matching fence-closing line later.
+fn fence_token(stripped_line: &str) -> String {
+    let first = stripped_line.chars().next().unwrap_or('`');
+    stripped_line.chars().take_while(|&c| c == first).collect()
+}
+
+/// Drops a leading `## Contents`/`## Table of Contents` block (up to, but
+/// not including, the next top-level `## ` heading) from `rest`, leaving
+/// everything else - including any fenced/indented code - byte-for-byte
+/// untouched.
+fn strip_existing_toc(rest: &str) -> String {
+    let mut output = String::with_capacity(rest.len());
+    let mut fence: Option<String> = None;
+    let mut skipping = false;
+
+    for (start, end) in line_spans(rest) {
+        let line = &rest[start..end];
+        let trimmed = line.trim_end_matches(['\n', '\r']);
+        let stripped = trimmed.trim();
+
+        if let Some(token) = &fence {
+            if stripped.starts_with(token.as_str()) {
+                fence = None;
             }
+            output.push_str(line);
+            continue;
+        }
 
-            if skip_old_toc {
-                if line.starts_with("## ") {
-                    skip_old_toc = false;
-                } else {
-                    continue;
-                }
+        let is_indented = line.starts_with("    ") || line.starts_with('\t');
+
+        if !skipping && !is_indented && (stripped.starts_with("```") || stripped.starts_with("~~~")) {
+            fence = Some(fence_token(stripped));
+            output.push_str(line);
+            continue;
+        }
+
+        if skipping {
+            if !is_indented && stripped.starts_with("## ") {
+                skipping = false;
+            } else {
+                continue;
             }
+        }
 
-            output.push(line);
+        if !skipping
+            && !is_indented
+            && (stripped.starts_with("## Contents") || stripped.starts_with("## Table of Contents"))
+        {
+            skipping = true;
+            continue;
         }
 
-        Some(output.join("\n") + "\n")
+        output.push_str(line);
     }
+
+    output
 }
 
 impl NoteObserver for TocObserver {
@@ -195,6 +354,7 @@ impl NoteObserver for TocObserver {
                                     "toc_generated".to_string(),
                                     "true".to_string(),
                                 )])),
+                                ..Default::default()
                             }))
                         } else {
                             debug!("No changes needed for TOC in '{}'", title);
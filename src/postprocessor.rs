@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
+
+/// Mutable note context threaded through the postprocessor pipeline.
+/// Postprocessors may rewrite any of these fields in place before the note
+/// is written and handed off to the observer registry.
+#[derive(Debug, Clone)]
+pub struct NoteContext {
+    pub title: String,
+    pub destination: PathBuf,
+    pub frontmatter: HashMap<String, String>,
+    pub content: String,
+}
+
+/// What should happen to the remaining pipeline after a postprocessor runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostprocessOutcome {
+    /// Run the next postprocessor in the chain.
+    Continue,
+    /// Keep the note, but stop running any further postprocessors.
+    Stop,
+    /// Drop the note from this sync entirely (e.g. a private note filter).
+    Skip,
+}
+
+/// A single step in the markdown postprocessor pipeline, run just before a
+/// note is written and synced, analogous to obsidian-export's postprocessors.
+pub trait Postprocessor: Send + Sync {
+    fn name(&self) -> String;
+    fn process(&self, ctx: &mut NoteContext) -> io::Result<PostprocessOutcome>;
+}
@@ -0,0 +1,49 @@
+use crate::postprocessor::{NoteContext, PostprocessOutcome, Postprocessor};
+use std::sync::Arc;
+use std::io;
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+
+pub struct PostprocessorRegistry {
+    postprocessors: RwLock<Vec<Arc<Box<dyn Postprocessor>>>>,
+}
+
+impl PostprocessorRegistry {
+    pub fn new() -> Self {
+        debug!("Creating new PostprocessorRegistry");
+        Self {
+            postprocessors: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub async fn register(&self, postprocessor: Box<dyn Postprocessor>) {
+        let name = postprocessor.name();
+        debug!("Registering new postprocessor: {}", name);
+        let mut postprocessors = self.postprocessors.write().await;
+        postprocessors.push(Arc::new(postprocessor));
+        info!("✅ Postprocessor '{}' registered successfully", name);
+    }
+
+    /// Runs the pipeline over `ctx`, mutating it in place. Returns `false` if
+    /// a postprocessor requested the note be skipped entirely.
+    pub async fn run(&self, ctx: &mut NoteContext) -> io::Result<bool> {
+        let postprocessors = self.postprocessors.read().await;
+
+        for postprocessor in postprocessors.iter() {
+            debug!("Running postprocessor: {}", postprocessor.name());
+            match postprocessor.process(ctx)? {
+                PostprocessOutcome::Continue => continue,
+                PostprocessOutcome::Stop => {
+                    debug!("Postprocessor '{}' stopped the chain", postprocessor.name());
+                    break;
+                }
+                PostprocessOutcome::Skip => {
+                    debug!("Postprocessor '{}' skipped note '{}'", postprocessor.name(), ctx.title);
+                    return Ok(false);
+                }
+            }
+        }
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,228 @@
+use std::collections::HashSet;
+
+/// Extracts outbound cross-note references from a note's body: `[[Wiki
+/// Links]]`, and `#CamelCase`/`#kebab-case`/`#colon:case` hashtag-style
+/// mentions. Each reference is normalized via [`slugify`] to the same kind
+/// of slug a note title resolves to, so it can be matched against existing
+/// notes regardless of how it was written in the source note.
+pub struct ReferenceParser;
+
+impl ReferenceParser {
+    /// Returns the deduplicated set of slugs referenced by `content`,
+    /// skipping matches inside ` ```sql ` fenced blocks - the one section
+    /// `Note::to_string` already special-cases - and self-references to
+    /// `own_title`'s own slug.
+    pub fn extract_references(content: &str, own_title: &str) -> HashSet<String> {
+        let own_slug = slugify(own_title);
+        let mut references = HashSet::new();
+        let mut in_sql_block = false;
+
+        for line in content.lines() {
+            if line.trim_start().starts_with("```sql") {
+                in_sql_block = true;
+                continue;
+            }
+            if in_sql_block {
+                if line.trim_start().starts_with("```") {
+                    in_sql_block = false;
+                }
+                continue;
+            }
+
+            for reference in Self::extract_wikilinks(line)
+                .into_iter()
+                .chain(Self::extract_hashtags(line))
+            {
+                let slug = slugify(&reference);
+                if !slug.is_empty() && slug != own_slug {
+                    references.insert(slug);
+                }
+            }
+        }
+
+        references
+    }
+
+    fn extract_wikilinks(line: &str) -> Vec<String> {
+        let mut links = Vec::new();
+        let mut rest = line;
+
+        while let Some(start) = rest.find("[[") {
+            let after = &rest[start + 2..];
+            match after.find("]]") {
+                Some(end) => {
+                    links.push(after[..end].trim().to_string());
+                    rest = &after[end + 2..];
+                }
+                None => break,
+            }
+        }
+
+        links
+    }
+
+    /// `#CamelCase` / `#kebab-case` / `#colon:case` - a `#` followed by a
+    /// run of alphanumerics, hyphens, underscores, and colons.
+    fn extract_hashtags(line: &str) -> Vec<String> {
+        let mut hashtags = Vec::new();
+        let mut i = 0;
+
+        while let Some(offset) = line[i..].find('#') {
+            let start = i + offset + 1;
+            let mut end = start;
+            for ch in line[start..].chars() {
+                if ch.is_alphanumeric() || ch == '-' || ch == '_' || ch == ':' {
+                    end += ch.len_utf8();
+                } else {
+                    break;
+                }
+            }
+
+            if end > start {
+                hashtags.push(line[start..end].to_string());
+            }
+            i = end.max(start + 1);
+            if i > line.len() {
+                break;
+            }
+        }
+
+        hashtags
+    }
+}
+
+/// Rewrites every `[[wikilink]]` and `#hashtag` reference to `old_title` so
+/// it points at `new_title` instead, leaving everything else in `content`
+/// untouched - used by `NoteManager::rename_note` to keep other notes'
+/// links live across a rename. Wikilink aliases (`[[Old Title|display
+/// text]]`) are preserved, only the target changes; hashtags are rewritten
+/// to `new_title`'s slug, since a hashtag reference has no separate target.
+pub fn rewrite_references(content: &str, old_title: &str, new_title: &str) -> String {
+    let old_slug = slugify(old_title);
+    let new_slug = slugify(new_title);
+
+    let mut in_sql_block = false;
+    let mut rewritten_lines = Vec::new();
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("```sql") {
+            in_sql_block = true;
+            rewritten_lines.push(line.to_string());
+            continue;
+        }
+        if in_sql_block {
+            if line.trim_start().starts_with("```") {
+                in_sql_block = false;
+            }
+            rewritten_lines.push(line.to_string());
+            continue;
+        }
+
+        let line = rewrite_wikilinks(line, &old_slug, new_title);
+        let line = rewrite_hashtags(&line, &old_slug, &new_slug);
+        rewritten_lines.push(line);
+    }
+
+    let mut rewritten = rewritten_lines.join("\n");
+    if content.ends_with('\n') {
+        rewritten.push('\n');
+    }
+    rewritten
+}
+
+fn rewrite_wikilinks(line: &str, old_slug: &str, new_title: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+
+    loop {
+        match rest.find("[[") {
+            Some(start) => {
+                result.push_str(&rest[..start]);
+                let after = &rest[start + 2..];
+                match after.find("]]") {
+                    Some(end) => {
+                        let inner = &after[..end];
+                        let (target, alias) = match inner.split_once('|') {
+                            Some((target, alias)) => (target.trim(), Some(alias)),
+                            None => (inner.trim(), None),
+                        };
+
+                        result.push_str("[[");
+                        if slugify(target) == old_slug {
+                            result.push_str(new_title);
+                        } else {
+                            result.push_str(target);
+                        }
+                        if let Some(alias) = alias {
+                            result.push('|');
+                            result.push_str(alias);
+                        }
+                        result.push_str("]]");
+
+                        rest = &after[end + 2..];
+                    }
+                    None => {
+                        result.push_str(&rest[start..]);
+                        break;
+                    }
+                }
+            }
+            None => {
+                result.push_str(rest);
+                break;
+            }
+        }
+    }
+
+    result
+}
+
+fn rewrite_hashtags(line: &str, old_slug: &str, new_slug: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+
+    loop {
+        match rest.find('#') {
+            Some(start) => {
+                result.push_str(&rest[..start]);
+                let after = &rest[start + 1..];
+                let mut end = 0;
+                for ch in after.chars() {
+                    if ch.is_alphanumeric() || ch == '-' || ch == '_' || ch == ':' {
+                        end += ch.len_utf8();
+                    } else {
+                        break;
+                    }
+                }
+
+                result.push('#');
+                if end > 0 && slugify(&after[..end]) == old_slug {
+                    result.push_str(new_slug);
+                } else {
+                    result.push_str(&after[..end]);
+                }
+
+                rest = &after[end..];
+            }
+            None => {
+                result.push_str(rest);
+                break;
+            }
+        }
+    }
+
+    result
+}
+
+/// Normalizes text into a slug comparable against note titles: lowercase,
+/// spaces collapsed to underscores, punctuation stripped (hyphens and
+/// underscores kept, since they're meaningful in `#kebab-case` references
+/// and in filenames).
+pub fn slugify(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .replace(' ', "_")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
+        .collect()
+}
@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::engine::local::{Db, RocksDb};
+use surrealdb::Surreal;
+use tracing::{debug, error, info};
+
+use crate::note::Note;
+use crate::observer_registry::ObserverRegistry;
+use crate::observers::similar_notes::SimilarNotesObserver;
+use crate::settings::Settings;
+
+const JOB_ID: &str = "current";
+
+/// Persisted progress for a full-vault reindex: the ordered set of note
+/// paths the job was started with, and the subset already embedded.
+/// Serialized into the same embeddings SurrealDB the `similar_notes`
+/// observer uses, so a crash mid-run can resume rather than starting over.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ReindexJob {
+    paths: Vec<String>,
+    completed: Vec<String>,
+}
+
+async fn open_job_db() -> io::Result<Surreal<Db>> {
+    let db = Surreal::new::<RocksDb>("./db/embeddings.db")
+        .await
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    db.use_ns("test").use_db("test").await.map_err(|e| {
+        error!("Failed to select database namespace: {}", e);
+        io::Error::new(io::ErrorKind::Other, e)
+    })?;
+    Ok(db)
+}
+
+fn enumerate_notes(settings: &Settings) -> io::Result<Vec<String>> {
+    let mut paths: Vec<String> = fs::read_dir(&settings.note_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().and_then(|ext| ext.to_str()) == Some(settings.file_type.as_str())
+        })
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Handles `Command::Reindex`: (re)embeds every note under `settings.note_dir`
+/// through the `similar_notes` observer, checkpointing after each note so an
+/// interrupted run resumes from where it left off instead of re-embedding
+/// everything.
+pub async fn run(settings: &Settings, observer_registry: Arc<ObserverRegistry>) -> io::Result<()> {
+    let observers = observer_registry.get_observers().await;
+    let similar_notes = observers
+        .iter()
+        .find(|o| o.name() == "similar_notes")
+        .and_then(|o| o.as_any().downcast_ref::<SimilarNotesObserver>())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "similar_notes observer not found in registry (enable it in enabled_observers)",
+            )
+        })?;
+
+    let db = open_job_db().await?;
+    let paths = enumerate_notes(settings)?;
+
+    let existing_job: Option<ReindexJob> =
+        db.select(("reindex_jobs", JOB_ID)).await.map_err(|e| {
+            error!("Failed to load reindex job state: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+
+    let mut job = match existing_job {
+        Some(job) if job.paths == paths => {
+            info!(
+                "Resuming reindex job: {}/{} notes already embedded",
+                job.completed.len(),
+                job.paths.len()
+            );
+            job
+        }
+        _ => {
+            info!("Starting new reindex job over {} notes", paths.len());
+            ReindexJob {
+                paths: paths.clone(),
+                completed: Vec::new(),
+            }
+        }
+    };
+
+    let done: HashSet<String> = job.completed.iter().cloned().collect();
+
+    for path in job.paths.clone() {
+        if done.contains(&path) {
+            continue;
+        }
+
+        let title = Path::new(&path)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .to_string();
+
+        match Note::from_file(Path::new(&path)) {
+            Ok(Some((content, _frontmatter))) => {
+                if let Err(e) = similar_notes.embed_note(title.clone(), content).await {
+                    error!("Failed to index '{}', will retry next run: {}", title, e);
+                    continue;
+                }
+            }
+            Ok(None) => {
+                debug!("Could not parse note, skipping: {}", path);
+            }
+            Err(e) => {
+                error!("Failed to read note '{}': {}", path, e);
+                continue;
+            }
+        }
+
+        job.completed.push(path);
+        persist_progress(&db, &job).await?;
+        debug!("Reindexed {}/{} notes", job.completed.len(), job.paths.len());
+    }
+
+    info!(
+        "✨ Reindex complete: {}/{} notes embedded",
+        job.completed.len(),
+        job.paths.len()
+    );
+    Ok(())
+}
+
+async fn persist_progress(db: &Surreal<Db>, job: &ReindexJob) -> io::Result<()> {
+    db.upsert::<Option<ReindexJob>>(("reindex_jobs", JOB_ID))
+        .content(job.clone())
+        .await
+        .map_err(|e| {
+            error!("Failed to checkpoint reindex progress: {}", e);
+            io::Error::new(io::ErrorKind::Other, e)
+        })?;
+    Ok(())
+}
+
+/// Reports `(completed, total)` for the most recent reindex job, if one has
+/// ever been started. Used by the tray's "Show Info" dialog and by
+/// `Command::Watch`'s startup check for an incomplete run to resume.
+pub async fn progress() -> io::Result<Option<(usize, usize)>> {
+    let db = open_job_db().await?;
+    let job: Option<ReindexJob> = db.select(("reindex_jobs", JOB_ID)).await.map_err(|e| {
+        error!("Failed to load reindex job state: {}", e);
+        io::Error::new(io::ErrorKind::Other, e)
+    })?;
+    Ok(job.map(|j| (j.completed.len(), j.paths.len())))
+}
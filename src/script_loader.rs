@@ -1,9 +1,12 @@
-use crate::event::{NoteEvent, NoteObserver, ObserverResult};
+use crate::event::{NoteEvent, NoteObserver, ObserverAction, ObserverResult};
+use crate::host_api::HostApi;
 use crate::observers;
+use crate::postprocessor::{NoteContext, PostprocessOutcome, Postprocessor};
 use crate::settings::Settings;
 use mlua::Lua;
+use pyo3::exceptions::PyIOError;
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyModule};
+use pyo3::types::{PyAny, PyDict, PyList, PyModule};
 use std::any::Any;
 use std::collections::HashMap;
 use std::fs;
@@ -15,6 +18,197 @@ use std::sync::Arc;
 use tokio::task;
 use tracing::{debug, error, info, trace, warn};
 
+/// Serializes `event` to the JSON a Python observer's `process_event`
+/// receives, stamping the vault's data directory onto the event's inner
+/// variant so scripts can locate sibling state (e.g. the SQLite index)
+/// without re-deriving it from `Settings`.
+fn build_python_event_json(event: &NoteEvent) -> io::Result<String> {
+    let mut event_json = serde_json::to_value(event)?;
+    if let serde_json::Value::Object(ref mut map) = event_json {
+        let event_type = match map {
+            m if m.contains_key("Created") => m.get_mut("Created"),
+            m if m.contains_key("Updated") => m.get_mut("Updated"),
+            m if m.contains_key("Synced") => m.get_mut("Synced"),
+            _ => None,
+        };
+
+        if let Some(serde_json::Value::Object(ref mut event_map)) = event_type {
+            event_map.insert(
+                "data_dir".to_string(),
+                serde_json::Value::String(Settings::get_data_dir().to_string_lossy().to_string()),
+            );
+        }
+    }
+    Ok(serde_json::to_string(&event_json)?)
+}
+
+/// Turns a Python `process_event`/command handler's return value into an
+/// `ObserverResult`, shared between the blocking-pool and async-coroutine
+/// execution paths.
+fn python_value_to_result(value: &Bound<'_, PyAny>) -> io::Result<Option<ObserverResult>> {
+    if let Ok(result_str) = value.extract::<String>() {
+        if let Ok(result) = parse_observer_result(&result_str) {
+            return Ok(Some(result));
+        }
+    }
+    Ok(None)
+}
+
+/// Parses a script's JSON return value into an [`ObserverResult`]. A bare
+/// `{metadata, content}` object is treated as a single `UpdateSelf` action,
+/// for backward compatibility with the original observer protocol; anything
+/// else is parsed as a JSON array of [`ObserverAction`]s tagged by `type`.
+fn parse_observer_result(raw: &str) -> io::Result<ObserverResult> {
+    let value: serde_json::Value = serde_json::from_str(raw)?;
+
+    let actions: Vec<ObserverAction> = if value.is_array() {
+        serde_json::from_value(value)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?
+    } else {
+        let metadata = value
+            .get("metadata")
+            .and_then(|m| serde_json::from_value::<HashMap<String, String>>(m.clone()).ok());
+        let content = value
+            .get("content")
+            .and_then(|c| c.as_str())
+            .map(|s| s.to_string());
+        vec![ObserverAction::UpdateSelf { metadata, content }]
+    };
+
+    Ok(ObserverResult::from_actions(actions))
+}
+
+/// Turns an `on_event`/command handler's Lua return value into an
+/// `ObserverResult`, shared between the blocking-pool and async-coroutine
+/// execution paths.
+fn lua_value_to_result(value: mlua::Value) -> io::Result<Option<ObserverResult>> {
+    match value {
+        mlua::Value::Nil => {
+            debug!("Lua observer returned no changes");
+            Ok(None)
+        }
+        mlua::Value::String(s) => {
+            debug!("Processing Lua observer result");
+            let result = parse_observer_result(&s.to_string_lossy())?;
+            trace!(
+                "Lua observer returned - metadata: {:?}, content modified: {}, {} extra action(s)",
+                result.metadata,
+                result.content.is_some(),
+                result.actions.len()
+            );
+            Ok(Some(result))
+        }
+        _ => {
+            error!("Invalid return type from Lua script");
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Invalid return type from Lua script",
+            ))
+        }
+    }
+}
+
+/// Converts a JSON value into the Lua value scripts see from `noorg.query`/
+/// `noorg.get_note`/`noorg.list`.
+fn json_to_lua_value(lua: &Lua, value: &serde_json::Value) -> mlua::Result<mlua::Value> {
+    match value {
+        serde_json::Value::Null => Ok(mlua::Value::Nil),
+        serde_json::Value::Bool(b) => Ok(mlua::Value::Boolean(*b)),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Ok(mlua::Value::Integer(i)),
+            None => Ok(mlua::Value::Number(n.as_f64().unwrap_or_default())),
+        },
+        serde_json::Value::String(s) => Ok(mlua::Value::String(lua.create_string(s)?)),
+        serde_json::Value::Array(items) => {
+            let table = lua.create_table()?;
+            for (index, item) in items.iter().enumerate() {
+                table.set(index + 1, json_to_lua_value(lua, item)?)?;
+            }
+            Ok(mlua::Value::Table(table))
+        }
+        serde_json::Value::Object(fields) => {
+            let table = lua.create_table()?;
+            for (key, value) in fields {
+                table.set(key.as_str(), json_to_lua_value(lua, value)?)?;
+            }
+            Ok(mlua::Value::Table(table))
+        }
+    }
+}
+
+/// Turns a `QueryResult`'s rows into a JSON array of `{column: value}`
+/// objects, the shared wire format `noorg.query` hands back to both Lua and
+/// Python observers.
+fn query_result_to_json(result: &crate::observers::sqlite_store::QueryResult) -> serde_json::Value {
+    let rows = result
+        .rows
+        .iter()
+        .map(|row| {
+            serde_json::Value::Object(
+                row.iter()
+                    .map(|(column, value)| (column.clone(), value.to_json()))
+                    .collect(),
+            )
+        })
+        .collect();
+    serde_json::Value::Array(rows)
+}
+
+/// Registers the `noorg` global table scripts use to look at the rest of the
+/// vault: `noorg.query(sql)`, `noorg.get_note(title)`, `noorg.list(filter)`.
+/// Bound once per interpreter since `LuaObserver`'s `Lua` instance is reused
+/// across every `on_event`/command call.
+fn register_host_api(lua: &Lua, host_api: &HostApi) -> io::Result<()> {
+    let noorg = lua.create_table().map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+
+    let host = host_api.clone();
+    let get_note = lua
+        .create_function(move |lua, title: String| match host.get_note(&title) {
+            Ok(Some((content, metadata))) => {
+                let table = lua.create_table()?;
+                table.set("content", content)?;
+                let metadata_table = lua.create_table()?;
+                for (key, value) in metadata {
+                    metadata_table.set(key, value)?;
+                }
+                table.set("metadata", metadata_table)?;
+                Ok(mlua::Value::Table(table))
+            }
+            Ok(None) => Ok(mlua::Value::Nil),
+            Err(e) => Err(mlua::Error::RuntimeError(e.to_string())),
+        })
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+    noorg.set("get_note", get_note).map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+
+    let host = host_api.clone();
+    let list = lua
+        .create_function(move |_, filter: Option<mlua::Table>| {
+            let mut filters = HashMap::new();
+            if let Some(filter) = filter {
+                for pair in filter.pairs::<String, String>() {
+                    let (key, value) = pair?;
+                    filters.insert(key, value);
+                }
+            }
+            host.list(filters).map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+        })
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+    noorg.set("list", list).map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+
+    let host = host_api.clone();
+    let query = lua
+        .create_function(move |lua, sql: String| match host.query(&sql) {
+            Ok(result) => json_to_lua_value(lua, &query_result_to_json(&result)),
+            Err(e) => Err(mlua::Error::RuntimeError(e.to_string())),
+        })
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+    noorg.set("query", query).map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+
+    lua.globals()
+        .set("noorg", noorg)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))
+}
+
 #[derive(Clone)]
 pub struct LuaObserver {
     lua: Lua,
@@ -22,9 +216,11 @@ pub struct LuaObserver {
 }
 
 impl LuaObserver {
-    pub fn new(script_path: &Path) -> io::Result<Self> {
+    pub fn new(script_path: &Path, lib_dir: &Path, host_api: HostApi) -> io::Result<Self> {
         debug!("Creating new Lua observer from: {}", script_path.display());
         let lua = Lua::new();
+        register_lib_searcher(&lua, lib_dir)?;
+        register_host_api(&lua, &host_api)?;
 
         // First, register the json module
         lua.load(
@@ -131,6 +327,84 @@ return M
         info!("✨ Loaded Lua observer: {}", name);
         Ok(Self { lua, name })
     }
+
+    /// Names of the commands this script exposes via an optional global
+    /// `register_commands() -> { [name] = { description = ..., handler = ... } }`.
+    /// Returns an empty list if the script doesn't define that function.
+    pub fn list_commands(&self) -> io::Result<Vec<String>> {
+        let globals = self.lua.globals();
+        let Ok(register_commands) = globals.get::<_, mlua::Function>("register_commands") else {
+            return Ok(Vec::new());
+        };
+
+        let table: mlua::Table = register_commands.call(()).map_err(|e| {
+            error!("Failed to call register_commands() in '{}': {}", self.name, e);
+            io::Error::new(io::ErrorKind::Other, e.to_string())
+        })?;
+
+        let mut names = Vec::new();
+        for pair in table.pairs::<String, mlua::Value>() {
+            let (name, _) = pair.map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+            names.push(name);
+        }
+        Ok(names)
+    }
+}
+
+/// Registers a `package.searchers` (`package.loaders` on Lua 5.1) entry
+/// resolving `require("foo.bar")` against `<lib_dir>/foo/bar.lua`, so every
+/// observer built against the same `lib_dir` can share helper modules
+/// instead of inlining them per-script.
+fn register_lib_searcher(lua: &Lua, lib_dir: &Path) -> io::Result<()> {
+    let lib_dir = lib_dir.to_path_buf();
+    let package: mlua::Table = lua.globals().get("package").map_err(|e| {
+        error!("Failed to get package table: {}", e);
+        io::Error::new(io::ErrorKind::Other, e.to_string())
+    })?;
+
+    let searchers: mlua::Table = package
+        .get("searchers")
+        .or_else(|_| package.get("loaders"))
+        .map_err(|e| {
+            error!("Failed to get package.searchers/loaders table: {}", e);
+            io::Error::new(io::ErrorKind::Other, e.to_string())
+        })?;
+
+    let searcher = lua
+        .create_function(move |lua, name: String| {
+            let rel_path = name.replace('.', std::path::MAIN_SEPARATOR_STR);
+            let module_path = lib_dir.join(format!("{}.lua", rel_path));
+
+            match fs::read_to_string(&module_path) {
+                Ok(source) => {
+                    debug!("Resolved require(\"{}\") to {}", name, module_path.display());
+                    let chunk = lua
+                        .load(&source)
+                        .set_name(module_path.to_str().unwrap_or(&name))
+                        .into_function()?;
+                    Ok(mlua::Value::Function(chunk))
+                }
+                Err(e) => {
+                    trace!("require(\"{}\") not found at {}: {}", name, module_path.display(), e);
+                    Ok(mlua::Value::String(lua.create_string(&format!(
+                        "\n\tno file '{}'",
+                        module_path.display()
+                    ))?))
+                }
+            }
+        })
+        .map_err(|e| {
+            error!("Failed to create shared-lib searcher: {}", e);
+            io::Error::new(io::ErrorKind::Other, e.to_string())
+        })?;
+
+    let next_index = searchers.raw_len() + 1;
+    searchers.set(next_index, searcher).map_err(|e| {
+        error!("Failed to register shared-lib searcher: {}", e);
+        io::Error::new(io::ErrorKind::Other, e.to_string())
+    })?;
+
+    Ok(())
 }
 
 impl NoteObserver for LuaObserver {
@@ -142,6 +416,25 @@ impl NoteObserver for LuaObserver {
         let observer_name = self.name.clone();
 
         Box::pin(async move {
+            let event_str = serde_json::to_string(&event)?;
+
+            // An `on_event_async` entrypoint runs as a Lua coroutine driven
+            // directly on this task via mlua's `call_async`, instead of
+            // occupying a blocking-pool thread for the whole call - the
+            // point being that an observer doing network I/O (e.g.
+            // `http.get` backed by a `create_async_function`) yields at its
+            // await points instead of blocking a thread.
+            if let Ok(on_event_async) = lua.globals().get::<_, mlua::Function>("on_event_async") {
+                debug!("Running on_event_async for Lua observer: {}", observer_name);
+                trace!("Sending event to Lua (async): {}", event_str);
+                let result: mlua::Value =
+                    on_event_async.call_async(event_str).await.map_err(|e| {
+                        error!("Failed to execute Lua on_event_async: {}", e);
+                        io::Error::new(io::ErrorKind::Other, e.to_string())
+                    })?;
+                return lua_value_to_result(result);
+            }
+
             debug!("Processing event in Lua observer: {}", observer_name);
             task::spawn_blocking(move || {
                 let globals = lua.globals();
@@ -150,7 +443,6 @@ impl NoteObserver for LuaObserver {
                     io::Error::new(io::ErrorKind::Other, e.to_string())
                 })?;
 
-                let event_str = serde_json::to_string(&event)?;
                 trace!("Sending event to Lua: {}", event_str);
 
                 let result: mlua::Value = on_event.call(event_str).map_err(|e| {
@@ -158,38 +450,7 @@ impl NoteObserver for LuaObserver {
                     io::Error::new(io::ErrorKind::Other, e.to_string())
                 })?;
 
-                match result {
-                    mlua::Value::Nil => {
-                        debug!("Lua observer returned no changes");
-                        Ok(None)
-                    }
-                    mlua::Value::String(s) => {
-                        debug!("Processing Lua observer result");
-                        let result: serde_json::Value = serde_json::from_str(&s.to_string_lossy())?;
-
-                        let metadata = result.get("metadata").and_then(|m| {
-                            serde_json::from_value::<HashMap<String, String>>(m.clone()).ok()
-                        });
-                        let content = result
-                            .get("content")
-                            .and_then(|c| c.as_str())
-                            .map(|s| s.to_string());
-
-                        trace!(
-                            "Lua observer returned - metadata: {:?}, content modified: {}",
-                            metadata,
-                            content.is_some()
-                        );
-                        Ok(Some(ObserverResult { metadata, content }))
-                    }
-                    _ => {
-                        error!("Invalid return type from Lua script");
-                        Err(io::Error::new(
-                            io::ErrorKind::Other,
-                            "Invalid return type from Lua script",
-                        ))
-                    }
-                }
+                lua_value_to_result(result)
             })
             .await
             .map_err(|e| {
@@ -206,15 +467,149 @@ impl NoteObserver for LuaObserver {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn on_command(
+        &self,
+        name: &str,
+        args: Vec<String>,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Option<ObserverResult>>> + Send + '_>> {
+        let lua = self.lua.clone();
+        let observer_name = self.name.clone();
+        let name = name.to_string();
+
+        Box::pin(async move {
+            debug!("Running command '{}' in Lua observer: {}", name, observer_name);
+            task::spawn_blocking(move || {
+                let globals = lua.globals();
+                let register_commands: mlua::Function =
+                    globals.get("register_commands").map_err(|e| {
+                        io::Error::new(
+                            io::ErrorKind::Unsupported,
+                            format!("'{}' does not expose register_commands(): {}", observer_name, e),
+                        )
+                    })?;
+
+                let commands: mlua::Table = register_commands.call(()).map_err(|e| {
+                    error!("Failed to call register_commands(): {}", e);
+                    io::Error::new(io::ErrorKind::Other, e.to_string())
+                })?;
+
+                let entry: mlua::Table = commands.get(name.as_str()).map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("'{}' has no command named '{}'", observer_name, name),
+                    )
+                })?;
+
+                let handler: mlua::Function = entry.get("handler").map_err(|e| {
+                    io::Error::new(
+                        io::ErrorKind::Other,
+                        format!("command '{}' has no handler: {}", name, e),
+                    )
+                })?;
+
+                let args_json = serde_json::to_string(&args)?;
+                let result: mlua::Value = handler.call(args_json).map_err(|e| {
+                    error!("Failed to execute handler for command '{}': {}", name, e);
+                    io::Error::new(io::ErrorKind::Other, e.to_string())
+                })?;
+
+                match result {
+                    mlua::Value::Nil => Ok(None),
+                    mlua::Value::String(s) => {
+                        let result = parse_observer_result(&s.to_string_lossy())?;
+                        Ok(Some(result))
+                    }
+                    _ => Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "Invalid return type from Lua command handler",
+                    )),
+                }
+            })
+            .await
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?
+        })
+    }
+}
+
+/// Converts a JSON value into the Python object scripts see from
+/// `noorg.query`/`noorg.get_note`/`noorg.list`.
+fn json_to_py(py: Python<'_>, value: &serde_json::Value) -> PyResult<PyObject> {
+    Ok(match value {
+        serde_json::Value::Null => py.None(),
+        serde_json::Value::Bool(b) => b.into_py(py),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into_py(py),
+            None => n.as_f64().unwrap_or_default().into_py(py),
+        },
+        serde_json::Value::String(s) => s.into_py(py),
+        serde_json::Value::Array(items) => {
+            let list = PyList::empty_bound(py);
+            for item in items {
+                list.append(json_to_py(py, item)?)?;
+            }
+            list.into_py(py)
+        }
+        serde_json::Value::Object(fields) => {
+            let dict = PyDict::new_bound(py);
+            for (key, value) in fields {
+                dict.set_item(key, json_to_py(py, value)?)?;
+            }
+            dict.into_py(py)
+        }
+    })
+}
+
+/// The `noorg` host object injected into a Python observer's execution
+/// namespace, bridging its `query`/`get_note`/`list` methods back to the
+/// [`HostApi`] (whose `query`, in turn, bridges to the async SQLite path via
+/// `Handle::block_on` since this runs on a blocking-pool thread).
+#[pyclass(name = "NoorgHostApi")]
+#[derive(Clone)]
+struct PyHostApi(HostApi);
+
+#[pymethods]
+impl PyHostApi {
+    fn get_note(&self, py: Python<'_>, title: &str) -> PyResult<PyObject> {
+        match self.0.get_note(title).map_err(|e| PyIOError::new_err(e.to_string()))? {
+            Some((content, metadata)) => {
+                let dict = PyDict::new_bound(py);
+                dict.set_item("content", content)?;
+                dict.set_item("metadata", metadata)?;
+                Ok(dict.into_py(py))
+            }
+            None => Ok(py.None()),
+        }
+    }
+
+    #[pyo3(signature = (filter=None))]
+    fn list(&self, filter: Option<HashMap<String, String>>) -> PyResult<Vec<String>> {
+        self.0
+            .list(filter.unwrap_or_default())
+            .map_err(|e| PyIOError::new_err(e.to_string()))
+    }
+
+    fn query(&self, py: Python<'_>, sql: &str) -> PyResult<PyObject> {
+        let result = self.0.query(sql).map_err(|e| PyIOError::new_err(e.to_string()))?;
+        json_to_py(py, &query_result_to_json(&result))
+    }
+}
+
+/// Injects the `noorg` host API object into a freshly built module's
+/// namespace, so a script can reference it at the top level or from inside
+/// `on_event`/`process_event`/a command handler.
+fn install_host_api(module: &Bound<'_, PyModule>, host_api: &HostApi) -> PyResult<()> {
+    module.add("noorg", PyHostApi(host_api.clone()))
 }
 
 pub struct PythonObserver {
     code: String,
     name: String,
+    host_api: HostApi,
 }
 
 impl PythonObserver {
-    pub fn new(script_path: &Path) -> io::Result<Self> {
+    pub fn new(script_path: &Path, host_api: HostApi) -> io::Result<Self> {
         debug!(
             "Creating new Python observer from: {}",
             script_path.display()
@@ -273,13 +668,36 @@ def log_trace(message: str, *args: Any) -> None:
                 .to_string();
 
             info!("✨ Loaded Python observer: {}", name);
-            Ok(Self { code, name })
+            Ok(Self { code, name, host_api })
         })
         .map_err(|e: PyErr| {
             error!("Failed to initialize Python observer: {}", e);
             io::Error::new(io::ErrorKind::Other, e.to_string())
         })
     }
+
+    /// Names of the commands this script exposes via an optional
+    /// `register_commands() -> {name: {"description": ..., "handler": ...}}`.
+    /// Returns an empty list if the script doesn't define that function.
+    pub fn list_commands(&self) -> io::Result<Vec<String>> {
+        Python::with_gil(|py| {
+            let module = PyModule::from_code_bound(py, &self.code, "", "")?;
+            install_host_api(&module, &self.host_api)?;
+            let Ok(register_commands) = module.getattr("register_commands") else {
+                return Ok(Vec::new());
+            };
+            let commands = register_commands.call0()?;
+            let names = commands
+                .extract::<HashMap<String, PyObject>>()?
+                .into_keys()
+                .collect();
+            Ok(names)
+        })
+        .map_err(|e: PyErr| {
+            error!("Failed to call register_commands() in '{}': {}", self.name, e);
+            io::Error::new(io::ErrorKind::Other, e.to_string())
+        })
+    }
 }
 
 impl NoteObserver for PythonObserver {
@@ -289,44 +707,62 @@ impl NoteObserver for PythonObserver {
     ) -> Pin<Box<dyn Future<Output = io::Result<Option<ObserverResult>>> + Send + '_>> {
         let code = self.code.clone();
         let observer_name = self.name.clone();
+        let host_api = self.host_api.clone();
 
         Box::pin(async move {
+            let event_json = build_python_event_json(&event)?;
+
+            // An `async def process_event` is driven straight off its
+            // coroutine via pyo3-asyncio instead of running on the blocking
+            // pool, so a script awaiting e.g. an HTTP call doesn't tie up a
+            // blocking-pool thread for the request's duration.
+            let async_coroutine = Python::with_gil(|py| -> PyResult<Option<_>> {
+                let module = PyModule::from_code_bound(py, &code, "", "")?;
+                install_host_api(&module, &host_api)?;
+                let Ok(func) = module.getattr("process_event") else {
+                    return Ok(None);
+                };
+                let is_coroutine: bool = py
+                    .import_bound("asyncio")?
+                    .getattr("iscoroutinefunction")?
+                    .call1((&func,))?
+                    .extract()?;
+                if !is_coroutine {
+                    return Ok(None);
+                }
+                let coro = func.call1((event_json.clone(),))?;
+                Ok(Some(pyo3_asyncio::tokio::into_future(coro)?))
+            })
+            .map_err(|e: PyErr| {
+                error!("Failed to prepare async Python process_event: {}", e);
+                io::Error::new(io::ErrorKind::Other, e.to_string())
+            })?;
+
+            if let Some(future) = async_coroutine {
+                debug!(
+                    "Running async process_event for Python observer: {}",
+                    observer_name
+                );
+                let result = future.await.map_err(|e| {
+                    error!("Failed to execute async Python process_event: {}", e);
+                    io::Error::new(io::ErrorKind::Other, e.to_string())
+                })?;
+                return Python::with_gil(|py| python_value_to_result(result.bind(py)));
+            }
+
             debug!("Processing event in Python observer: {}", observer_name);
             task::spawn_blocking(move || {
                 Python::with_gil(|py| {
-                    let mut event_json = serde_json::to_value(&event)?;
-                    if let serde_json::Value::Object(ref mut map) = event_json {
-                        let event_type = match map {
-                            m if m.contains_key("Created") => m.get_mut("Created"),
-                            m if m.contains_key("Updated") => m.get_mut("Updated"),
-                            m if m.contains_key("Synced") => m.get_mut("Synced"),
-                            _ => None,
-                        };
-
-                        if let Some(serde_json::Value::Object(ref mut event_map)) = event_type {
-                            event_map.insert(
-                                "data_dir".to_string(),
-                                serde_json::Value::String(
-                                    Settings::get_data_dir().to_string_lossy().to_string(),
-                                ),
-                            );
-                        }
-                    }
-                    let event_json = serde_json::to_string(&event_json)?;
                     trace!("Sending event to Python: {}", event_json);
 
-                    let locals = PyDict::new_bound(py);
-                    locals
-                        .set_item("event_json", event_json.clone())
-                        .map_err(|e| {
-                            error!("Failed to set event_json in Python context: {}", e);
-                            io::Error::new(io::ErrorKind::Other, e.to_string())
-                        })?;
-
                     let code = PyModule::from_code_bound(py, &code, "", "").map_err(|e| {
                         error!("Failed to create Python module: {}", e);
                         io::Error::new(io::ErrorKind::Other, e.to_string())
                     })?;
+                    install_host_api(&code, &host_api).map_err(|e| {
+                        error!("Failed to install noorg host API: {}", e);
+                        io::Error::new(io::ErrorKind::Other, e.to_string())
+                    })?;
 
                     if let Ok(func) = code.getattr("process_event") {
                         let result = func.call1((event_json,)).map_err(|e| {
@@ -334,23 +770,8 @@ impl NoteObserver for PythonObserver {
                             io::Error::new(io::ErrorKind::Other, e.to_string())
                         })?;
 
-                        if let Ok(result_str) = result.extract::<String>() {
-                            if let Ok(result) = serde_json::from_str(&result_str) {
-                                let result: serde_json::Value = result;
-
-                                let metadata = result.get("metadata").and_then(|m| {
-                                    serde_json::from_value::<HashMap<String, String>>(m.clone())
-                                        .ok()
-                                });
-
-                                // Only get content if it exists, don't fall back to original
-                                let content = result
-                                    .get("content")
-                                    .and_then(|c| c.as_str())
-                                    .map(|s| s.to_string());
-
-                                return Ok(Some(ObserverResult { metadata, content }));
-                            }
+                        if let Some(result) = python_value_to_result(&result)? {
+                            return Ok(Some(result));
                         }
                     }
 
@@ -373,6 +794,194 @@ impl NoteObserver for PythonObserver {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn on_command(
+        &self,
+        name: &str,
+        args: Vec<String>,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Option<ObserverResult>>> + Send + '_>> {
+        let code = self.code.clone();
+        let observer_name = self.name.clone();
+        let host_api = self.host_api.clone();
+        let name = name.to_string();
+
+        Box::pin(async move {
+            debug!("Running command '{}' in Python observer: {}", name, observer_name);
+            task::spawn_blocking(move || {
+                Python::with_gil(|py| {
+                    let module = PyModule::from_code_bound(py, &code, "", "").map_err(|e| {
+                        error!("Failed to create Python module: {}", e);
+                        io::Error::new(io::ErrorKind::Other, e.to_string())
+                    })?;
+                    install_host_api(&module, &host_api).map_err(|e| {
+                        error!("Failed to install noorg host API: {}", e);
+                        io::Error::new(io::ErrorKind::Other, e.to_string())
+                    })?;
+
+                    let register_commands = module.getattr("register_commands").map_err(|e| {
+                        io::Error::new(
+                            io::ErrorKind::Unsupported,
+                            format!("'{}' does not expose register_commands(): {}", observer_name, e),
+                        )
+                    })?;
+                    let commands = register_commands.call0().map_err(|e| {
+                        error!("Failed to call register_commands(): {}", e);
+                        io::Error::new(io::ErrorKind::Other, e.to_string())
+                    })?;
+
+                    let entry = commands.get_item(name.as_str()).map_err(|_| {
+                        io::Error::new(
+                            io::ErrorKind::NotFound,
+                            format!("'{}' has no command named '{}'", observer_name, name),
+                        )
+                    })?;
+                    let handler = entry.get_item("handler").map_err(|e| {
+                        io::Error::new(
+                            io::ErrorKind::Other,
+                            format!("command '{}' has no handler: {}", name, e),
+                        )
+                    })?;
+
+                    let result = handler.call1((args,)).map_err(|e| {
+                        error!("Failed to execute handler for command '{}': {}", name, e);
+                        io::Error::new(io::ErrorKind::Other, e.to_string())
+                    })?;
+
+                    if let Ok(result_str) = result.extract::<String>() {
+                        if let Ok(result) = parse_observer_result(&result_str) {
+                            return Ok(Some(result));
+                        }
+                    }
+
+                    Ok(None)
+                })
+            })
+            .await
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?
+        })
+    }
+}
+
+/// A postprocessor implemented as a Lua script exposing a `postprocess`
+/// function: `function postprocess(context_json) -> json_or_nil_or_string`.
+/// Returning `nil`/`"continue"` continues the chain, `"stop"` halts it
+/// keeping the (possibly mutated) note, and `"skip"` drops the note.
+/// Any JSON object returned is merged back into the context (title,
+/// frontmatter, content).
+pub struct LuaPostprocessor {
+    lua: Lua,
+    name: String,
+}
+
+impl LuaPostprocessor {
+    pub fn new(script_path: &Path) -> io::Result<Self> {
+        debug!(
+            "Creating new Lua postprocessor from: {}",
+            script_path.display()
+        );
+        let lua = Lua::new();
+
+        let script_content = fs::read_to_string(script_path).map_err(|e| {
+            error!("Failed to read Lua postprocessor script: {}", e);
+            e
+        })?;
+
+        lua.load(&script_content)
+            .set_name(script_path.to_str().unwrap_or("postprocessor"))
+            .exec()
+            .map_err(|e| {
+                error!("Failed to execute Lua postprocessor script: {}", e);
+                io::Error::new(io::ErrorKind::Other, e.to_string())
+            })?;
+
+        let name = script_path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        info!("✨ Loaded Lua postprocessor: {}", name);
+        Ok(Self { lua, name })
+    }
+}
+
+impl Postprocessor for LuaPostprocessor {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn process(&self, ctx: &mut NoteContext) -> io::Result<PostprocessOutcome> {
+        let globals = self.lua.globals();
+        let postprocess: mlua::Function = globals.get("postprocess").map_err(|e| {
+            error!("Failed to get postprocess function: {}", e);
+            io::Error::new(io::ErrorKind::Other, e.to_string())
+        })?;
+
+        let context_json = serde_json::to_string(&serde_json::json!({
+            "title": ctx.title,
+            "destination": ctx.destination,
+            "frontmatter": ctx.frontmatter,
+            "content": ctx.content,
+        }))?;
+
+        let result: mlua::Value = postprocess.call(context_json).map_err(|e| {
+            error!("Failed to execute Lua postprocess: {}", e);
+            io::Error::new(io::ErrorKind::Other, e.to_string())
+        })?;
+
+        match result {
+            mlua::Value::Nil => Ok(PostprocessOutcome::Continue),
+            mlua::Value::String(s) => {
+                let s = s.to_string_lossy();
+                match s.as_ref() {
+                    "stop" => Ok(PostprocessOutcome::Stop),
+                    "skip" => Ok(PostprocessOutcome::Skip),
+                    "continue" => Ok(PostprocessOutcome::Continue),
+                    json_str => {
+                        let value: serde_json::Value =
+                            serde_json::from_str(json_str).map_err(|e| {
+                                io::Error::new(io::ErrorKind::Other, e.to_string())
+                            })?;
+
+                        if let Some(title) = value.get("title").and_then(|v| v.as_str()) {
+                            ctx.title = title.to_string();
+                        }
+                        if let Some(content) = value.get("content").and_then(|v| v.as_str()) {
+                            ctx.content = content.to_string();
+                        }
+                        if let Some(frontmatter) = value
+                            .get("frontmatter")
+                            .and_then(|v| v.as_object())
+                        {
+                            for (k, v) in frontmatter {
+                                if let Some(v) = v.as_str() {
+                                    ctx.frontmatter.insert(k.clone(), v.to_string());
+                                }
+                            }
+                        }
+
+                        Ok(PostprocessOutcome::Continue)
+                    }
+                }
+            }
+            _ => {
+                error!("Invalid return type from Lua postprocessor");
+                Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Invalid return type from Lua postprocessor",
+                ))
+            }
+        }
+    }
+}
+
+/// Result of [`ScriptLoader::load_observers`]: the loaded observers plus the
+/// commands they registered, ready to hand to
+/// [`crate::observer_registry::ObserverRegistry::set_cmd_owners`].
+pub struct LoadedObservers {
+    pub observers: Vec<Box<dyn NoteObserver>>,
+    /// Command name -> index into `observers` of the owning observer.
+    pub cmd_owners: HashMap<String, usize>,
 }
 
 pub struct ScriptLoader {
@@ -392,9 +1001,11 @@ impl ScriptLoader {
     pub fn load_observers(
         &self,
         enabled_observers: &[String],
-    ) -> io::Result<Vec<Box<dyn NoteObserver>>> {
+        host_api: HostApi,
+    ) -> io::Result<LoadedObservers> {
         debug!("Loading observers. Enabled: {:?}", enabled_observers);
         let mut observers: Vec<Box<dyn NoteObserver>> = Vec::new();
+        let mut cmd_owners: HashMap<String, usize> = HashMap::new();
 
         // Add enabled Rust observers
         for observer_name in enabled_observers {
@@ -413,11 +1024,17 @@ impl ScriptLoader {
         let lua_dir = Path::new(&self.scripts_dir).join("lua");
         if lua_dir.exists() {
             debug!("Loading Lua scripts from: {}", lua_dir.display());
-            for entry in fs::read_dir(lua_dir)? {
+            let lib_dir = lua_dir.join("lib");
+            for entry in fs::read_dir(&lua_dir)? {
                 let path = entry?.path();
                 if path.extension().map_or(false, |ext| ext == "lua") {
                     debug!("Loading Lua script: {}", path.display());
-                    observers.push(Box::new(LuaObserver::new(&path)?));
+                    let observer = LuaObserver::new(&path, &lib_dir, host_api.clone())?;
+                    for command in observer.list_commands()? {
+                        debug!("'{}' registers command '{}'", observer.name(), command);
+                        cmd_owners.insert(command, observers.len());
+                    }
+                    observers.push(Box::new(observer));
                 }
             }
         } else {
@@ -432,14 +1049,48 @@ impl ScriptLoader {
                 let path = entry?.path();
                 if path.extension().map_or(false, |ext| ext == "py") {
                     debug!("Loading Python script: {}", path.display());
-                    observers.push(Box::new(PythonObserver::new(&path)?));
+                    let observer = PythonObserver::new(&path, host_api.clone())?;
+                    for command in observer.list_commands()? {
+                        debug!("'{}' registers command '{}'", observer.name(), command);
+                        cmd_owners.insert(command, observers.len());
+                    }
+                    observers.push(Box::new(observer));
                 }
             }
         } else {
             debug!("No Python scripts directory found");
         }
 
-        info!("🔌 Loaded {} observers total", observers.len());
-        Ok(observers)
+        info!(
+            "🔌 Loaded {} observers total ({} command(s) registered)",
+            observers.len(),
+            cmd_owners.len()
+        );
+        Ok(LoadedObservers {
+            observers,
+            cmd_owners,
+        })
+    }
+
+    pub fn load_postprocessors(&self) -> io::Result<Vec<Box<dyn Postprocessor>>> {
+        debug!("Loading postprocessors from scripts directory");
+        let mut postprocessors: Vec<Box<dyn Postprocessor>> = Vec::new();
+
+        let lua_dir = Path::new(&self.scripts_dir).join("postprocessors").join("lua");
+        if lua_dir.exists() {
+            debug!("Loading Lua postprocessors from: {}", lua_dir.display());
+            for entry in fs::read_dir(lua_dir)? {
+                let path = entry?.path();
+                if path.extension().map_or(false, |ext| ext == "lua") {
+                    debug!("Loading Lua postprocessor: {}", path.display());
+                    postprocessors.push(Box::new(LuaPostprocessor::new(&path)?));
+                }
+            }
+        } else {
+            debug!("No Lua postprocessors directory found");
+        }
+
+        info!("🔌 Loaded {} postprocessors total", postprocessors.len());
+        Ok(postprocessors)
     }
 }
@@ -1,10 +1,79 @@
 use crate::embedded::DefaultScripts;
-use config::{Config, ConfigError};
+use config::Config;
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
-use tracing::{debug, error, info};
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+/// Errors from locating, loading, or initializing `Settings`.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// Loading or deserializing a config layer via the `config` crate failed.
+    Source(config::ConfigError),
+    /// A filesystem operation needed to set up config/script paths failed.
+    Io(io::Error),
+    /// A `config.toml` was found in both the current config directory and a
+    /// legacy/alternate location; ask the user to consolidate rather than
+    /// silently picking one.
+    AmbiguousSource(PathBuf, PathBuf),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Source(e) => write!(f, "{}", e),
+            ConfigError::Io(e) => write!(f, "{}", e),
+            ConfigError::AmbiguousSource(current, legacy) => write!(
+                f,
+                "found a config.toml in two places: {} and {}. Please consolidate into one and remove the other.",
+                current.display(),
+                legacy.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Source(e) => Some(e),
+            ConfigError::Io(e) => Some(e),
+            ConfigError::AmbiguousSource(..) => None,
+        }
+    }
+}
+
+impl From<config::ConfigError> for ConfigError {
+    fn from(e: config::ConfigError) -> Self {
+        ConfigError::Source(e)
+    }
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(e: io::Error) -> Self {
+        ConfigError::Io(e)
+    }
+}
+
+impl From<ConfigError> for io::Error {
+    fn from(e: ConfigError) -> Self {
+        io::Error::new(io::ErrorKind::Other, e.to_string())
+    }
+}
+
+/// Overlays a later config layer onto an earlier one, field-by-field. A
+/// field left at its type's zero/default value (empty string, empty `Vec`,
+/// `false`, `0`) is treated as "not set in this layer" and the earlier
+/// layer's value is kept; anything else replaces it outright. `Vec` fields
+/// replace rather than append, so a vault override fully re-specifies a
+/// list instead of extending the global one.
+pub trait Merge {
+    fn merge(&mut self, other: Self);
+}
 
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
 pub struct SimilarNotesConfig {
@@ -12,7 +81,201 @@ pub struct SimilarNotesConfig {
     pub excluded_from_references: Option<Vec<String>>,
 }
 
+impl Merge for SimilarNotesConfig {
+    fn merge(&mut self, other: Self) {
+        if other.excluded_notes.as_ref().is_some_and(|v| !v.is_empty()) {
+            self.excluded_notes = other.excluded_notes;
+        }
+        if other
+            .excluded_from_references
+            .as_ref()
+            .is_some_and(|v| !v.is_empty())
+        {
+            self.excluded_from_references = other.excluded_from_references;
+        }
+    }
+}
+
+/// Peer-to-peer UDP sync configuration - lets two or more `noorg` instances
+/// watching copies of the same vault converge without a central server.
+/// See `gossip::run`.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GossipConfig {
+    pub enabled: bool,
+    /// Local address to bind the gossip UDP socket to, e.g. `"0.0.0.0:7846"`.
+    pub bind_addr: String,
+    /// Seed peer addresses (`"host:port"`) - more are learned automatically
+    /// from incoming gossip.
+    pub peers: Vec<String>,
+    /// How often to advertise this vault's note digests to a peer sample.
+    pub interval_secs: u64,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        GossipConfig {
+            enabled: false,
+            bind_addr: "0.0.0.0:7846".to_string(),
+            peers: Vec::new(),
+            interval_secs: 30,
+        }
+    }
+}
+
+impl Merge for GossipConfig {
+    fn merge(&mut self, other: Self) {
+        // An omitted `[gossip]` table comes back as `GossipConfig::default()`,
+        // so a value equal to that default counts as "not set" too.
+        let default = GossipConfig::default();
+        if other.enabled {
+            self.enabled = other.enabled;
+        }
+        if !other.bind_addr.is_empty() && other.bind_addr != default.bind_addr {
+            self.bind_addr = other.bind_addr;
+        }
+        if !other.peers.is_empty() {
+            self.peers = other.peers;
+        }
+        if other.interval_secs != 0 && other.interval_secs != default.interval_secs {
+            self.interval_secs = other.interval_secs;
+        }
+    }
+}
+
+/// Relay/key configuration for `NostrObserver`. Keys are stored as
+/// *references* (e.g. an env var name), never as raw secrets, so a
+/// `config.toml`/`.norg.toml` committed to a vault's own git repo doesn't
+/// leak a signing key.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct NostrConfig {
+    /// Relays a note publishes to when its `nostr_relays` frontmatter field
+    /// doesn't override them.
+    pub relays: Vec<String>,
+    /// Named key references (alias -> env var name holding the nsec/hex
+    /// private key), selected per-note via the `nostr_key` frontmatter
+    /// field. `"default"` is used when a publishing note doesn't set one.
+    pub keys: HashMap<String, String>,
+}
+
+impl Merge for NostrConfig {
+    fn merge(&mut self, other: Self) {
+        if !other.relays.is_empty() {
+            self.relays = other.relays;
+        }
+        if !other.keys.is_empty() {
+            self.keys = other.keys;
+        }
+    }
+}
+
+/// Where `TocObserver` drops the generated table of contents.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TocConfig {
+    /// A line that, when found verbatim (after trimming) anywhere in a note,
+    /// is replaced by the generated TOC - e.g. `<!-- toc -->` or GitLab's
+    /// `[[_TOC_]]`. Empty disables marker-based placement entirely.
+    pub marker: String,
+    /// When no marker line is found, fall back to inserting the TOC right
+    /// after the note's first `# ` heading (the original behavior). Disable
+    /// this to require an explicit marker instead.
+    pub fallback_to_first_heading: bool,
+    /// Shallowest heading level included in the TOC (1 = `#`).
+    pub min_level: usize,
+    /// Deepest heading level included in the TOC (6 = `######`).
+    pub max_level: usize,
+    /// Whether the note's very first `# ` heading is omitted from the TOC
+    /// (it's usually the note's own title, not a section to link to).
+    /// Independent of `min_level`/`max_level`.
+    pub skip_first_h1: bool,
+}
+
+impl Default for TocConfig {
+    fn default() -> Self {
+        TocConfig {
+            marker: "<!-- toc -->".to_string(),
+            fallback_to_first_heading: true,
+            min_level: 1,
+            max_level: 6,
+            skip_first_h1: true,
+        }
+    }
+}
+
+impl Merge for TocConfig {
+    fn merge(&mut self, other: Self) {
+        // An omitted `[toc]` table comes back as `TocConfig::default()`, so
+        // a value equal to that default counts as "not set" too.
+        let default = TocConfig::default();
+        if other.marker != default.marker {
+            self.marker = other.marker;
+        }
+        if other.fallback_to_first_heading != default.fallback_to_first_heading {
+            self.fallback_to_first_heading = other.fallback_to_first_heading;
+        }
+        if other.min_level != 0 && other.min_level != default.min_level {
+            self.min_level = other.min_level;
+        }
+        if other.max_level != 0 && other.max_level != default.max_level {
+            self.max_level = other.max_level;
+        }
+        if other.skip_first_h1 != default.skip_first_h1 {
+            self.skip_first_h1 = other.skip_first_h1;
+        }
+    }
+}
+
+/// What `watch_directory` should do when a new fs event for a note arrives
+/// while that note's previous sync is still running, borrowed from
+/// watchexec's "on busy update" modes.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OnBusyUpdate {
+    /// Finish the in-flight sync, then run once more with the latest content.
+    Queue,
+    /// Cancel the in-flight sync and start over with the latest content.
+    Restart,
+    /// Drop the new event; the in-flight sync keeps running undisturbed.
+    DoNothing,
+}
+
+impl Default for OnBusyUpdate {
+    fn default() -> Self {
+        OnBusyUpdate::Queue
+    }
+}
+
+/// The external editor command used to compose a note when `Add`'s `body`
+/// is omitted, accepted either as a single shell-style string (split on
+/// whitespace, e.g. `"nvim +star"`) or an explicit argv list (e.g.
+/// `["code", "--wait"]`) so flags containing spaces don't need escaping.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum EditorCommand {
+    Shell(String),
+    Argv(Vec<String>),
+}
+
+impl EditorCommand {
+    /// Splits this command into the program to run and the args to pass it,
+    /// before the caller appends the file path being edited.
+    pub fn program_and_args(&self) -> (String, Vec<String>) {
+        match self {
+            EditorCommand::Shell(command) => {
+                let mut parts = command.split_whitespace();
+                let program = parts.next().unwrap_or_default().to_string();
+                (program, parts.map(str::to_string).collect())
+            }
+            EditorCommand::Argv(argv) => {
+                let mut parts = argv.iter();
+                let program = parts.next().cloned().unwrap_or_default();
+                (program, parts.cloned().collect())
+            }
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
+#[serde(default)]
 #[allow(dead_code)]
 pub struct Settings {
     pub file_type: String,
@@ -22,58 +285,223 @@ pub struct Settings {
     pub obsidian_vault_path: Option<String>,
     pub enabled_observers: Vec<String>,
     pub similar_notes: SimilarNotesConfig,
+    pub site_dir: String,
+    pub site_precompress: bool,
+    pub log_level: String,
+    pub log_filter: String,
+    pub log_terminal: bool,
+    pub revision_retention_limit: usize,
+    pub ignore_patterns: Vec<String>,
+    pub notifications_enabled: bool,
+    pub clear_on_sync: bool,
+    pub debounce_ms: u64,
+    pub on_busy_update: OnBusyUpdate,
+    pub editor: Option<EditorCommand>,
+    pub sqlite_backup_enabled: bool,
+    pub sqlite_backup_interval: usize,
+    pub sqlite_backup_retention: usize,
+    pub toc: TocConfig,
+    pub nostr: NostrConfig,
+    /// Backend for the content-hash cache `should_process_note` uses to skip
+    /// unchanged notes during a sync: `"json"` (the original single-file
+    /// cache), `"sqlite"` (an indexed table, faster for large vaults), or
+    /// `"binary"` (a versioned `bitcode`-encoded file, optionally
+    /// `zstd`-compressed via `hash_cache_compress`).
+    pub hash_cache_backend: String,
+    /// When `hash_cache_backend = "binary"`, wrap the encoded cache in
+    /// `zstd` compression.
+    pub hash_cache_compress: bool,
+    pub gossip: GossipConfig,
+}
+
+impl Merge for Settings {
+    fn merge(&mut self, other: Self) {
+        let Settings {
+            file_type,
+            timestamps,
+            note_dir,
+            scripts_dir,
+            obsidian_vault_path,
+            enabled_observers,
+            similar_notes,
+            site_dir,
+            site_precompress,
+            log_level,
+            log_filter,
+            log_terminal,
+            revision_retention_limit,
+            ignore_patterns,
+            notifications_enabled,
+            clear_on_sync,
+            debounce_ms,
+            on_busy_update,
+            editor,
+            sqlite_backup_enabled,
+            sqlite_backup_interval,
+            sqlite_backup_retention,
+            toc,
+            nostr,
+            hash_cache_backend,
+            hash_cache_compress,
+            gossip,
+        } = other;
+
+        if !file_type.is_empty() {
+            self.file_type = file_type;
+        }
+        if timestamps {
+            self.timestamps = timestamps;
+        }
+        if !note_dir.is_empty() {
+            self.note_dir = note_dir;
+        }
+        if !scripts_dir.is_empty() {
+            self.scripts_dir = scripts_dir;
+        }
+        if obsidian_vault_path.is_some() {
+            self.obsidian_vault_path = obsidian_vault_path;
+        }
+        if !enabled_observers.is_empty() {
+            self.enabled_observers = enabled_observers;
+        }
+        self.similar_notes.merge(similar_notes);
+        if !site_dir.is_empty() {
+            self.site_dir = site_dir;
+        }
+        if site_precompress {
+            self.site_precompress = site_precompress;
+        }
+        if !log_level.is_empty() {
+            self.log_level = log_level;
+        }
+        if !log_filter.is_empty() {
+            self.log_filter = log_filter;
+        }
+        if log_terminal {
+            self.log_terminal = log_terminal;
+        }
+        if revision_retention_limit != 0 {
+            self.revision_retention_limit = revision_retention_limit;
+        }
+        if !ignore_patterns.is_empty() {
+            self.ignore_patterns = ignore_patterns;
+        }
+        if notifications_enabled {
+            self.notifications_enabled = notifications_enabled;
+        }
+        if clear_on_sync {
+            self.clear_on_sync = clear_on_sync;
+        }
+        if debounce_ms != 0 {
+            self.debounce_ms = debounce_ms;
+        }
+        if on_busy_update != OnBusyUpdate::default() {
+            self.on_busy_update = on_busy_update;
+        }
+        if editor.is_some() {
+            self.editor = editor;
+        }
+        if sqlite_backup_enabled {
+            self.sqlite_backup_enabled = sqlite_backup_enabled;
+        }
+        if sqlite_backup_interval != 0 {
+            self.sqlite_backup_interval = sqlite_backup_interval;
+        }
+        if sqlite_backup_retention != 0 {
+            self.sqlite_backup_retention = sqlite_backup_retention;
+        }
+        self.toc.merge(toc);
+        self.nostr.merge(nostr);
+        if !hash_cache_backend.is_empty() {
+            self.hash_cache_backend = hash_cache_backend;
+        }
+        if hash_cache_compress {
+            self.hash_cache_compress = hash_cache_compress;
+        }
+        self.gossip.merge(gossip);
+    }
 }
 
 impl Settings {
-    pub fn new() -> Self {
+    pub fn new() -> Result<Self, ConfigError> {
         debug!("Loading application settings");
 
-        let config_path = match Self::ensure_config_exists() {
-            Ok(path) => {
-                debug!("Using config file at: {:?}", path);
-                path
-            }
-            Err(e) => {
-                error!("Failed to initialize config: {}", e);
-                panic!("Failed to initialize config: {}", e);
-            }
-        };
+        let config_path = Self::ensure_config_exists()?;
+        debug!("Using config file at: {:?}", config_path);
+
+        let mut settings = Self::load_layer(&config_path, "global config")?;
+
+        if let Some(vault_config_path) = Self::find_vault_config(&settings.note_dir) {
+            debug!("Found vault-level config override at {:?}", vault_config_path);
+            let vault_settings = Self::load_layer(&vault_config_path, "vault config")?;
+            settings.merge(vault_settings);
+        }
+
+        let env_settings = Self::load_env_layer()?;
+        settings.merge(env_settings);
+
+        trace_settings(&settings);
+        Self::ensure_directories_exist(&settings)?;
+
+        info!("âœ¨ Settings loaded successfully");
+        Ok(settings)
+    }
 
-        let config_result = Config::builder()
-            .add_source(config::File::from(config_path).required(true))
+    /// Loads a single config layer (the global `config.toml` or a vault's
+    /// `.norg.toml`) into a full `Settings`. Thanks to `#[serde(default)]`,
+    /// a layer only needs to specify the fields it wants to set - everything
+    /// else comes back as each field's default value, which `Merge` treats as
+    /// "not set in this layer".
+    fn load_layer(path: &Path, label: &str) -> Result<Settings, ConfigError> {
+        let config = Config::builder()
+            .add_source(config::File::from(path.to_path_buf()).required(true))
+            .build()?;
+
+        let settings = config.try_deserialize::<Settings>()?;
+        debug!("Loaded {} from {:?}", label, path);
+        Ok(settings)
+    }
+
+    /// Last layer: `NOTE_CLI`-prefixed environment variables, applied on top
+    /// of the global config and any vault override.
+    fn load_env_layer() -> Result<Settings, ConfigError> {
+        let config = Config::builder()
             .add_source(config::Environment::with_prefix("NOTE_CLI"))
-            .build();
-
-        let settings = match config_result {
-            Ok(config) => {
-                debug!("Configuration sources loaded successfully");
-                match config.try_deserialize::<Settings>() {
-                    Ok(settings) => {
-                        debug!("Settings deserialized successfully");
-                        trace_settings(&settings);
-                        settings
-                    }
-                    Err(e) => {
-                        error!("Failed to deserialize configuration: {}", e);
-                        panic!("Failed to deserialize configuration: {}", e);
-                    }
-                }
-            }
-            Err(e) => {
-                error!("Failed to load configuration: {}", e);
-                panic!("Failed to load configuration: {}", e);
+            .build()?;
+
+        Ok(config.try_deserialize::<Settings>()?)
+    }
+
+    /// Walks up from `note_dir` (or the current working directory, if
+    /// `note_dir` doesn't exist yet) looking for a `.norg.toml` vault
+    /// override, the same way git walks up looking for `.git`.
+    fn find_vault_config(note_dir: &str) -> Option<PathBuf> {
+        let start = {
+            let note_dir_path = PathBuf::from(note_dir);
+            if note_dir_path.is_dir() {
+                note_dir_path
+            } else {
+                std::env::current_dir().ok()?
             }
         };
 
-        Self::ensure_directories_exist(&settings);
-
-        info!("âœ¨ Settings loaded successfully");
-        settings
+        let mut dir = start.as_path();
+        loop {
+            let candidate = dir.join(".norg.toml");
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+            dir = dir.parent()?;
+        }
     }
 
     fn ensure_config_exists() -> Result<PathBuf, ConfigError> {
-        let proj_dirs = ProjectDirs::from("", "norg", "norg")
-            .ok_or_else(|| ConfigError::NotFound("Could not determine config directory".into()))?;
+        let proj_dirs = ProjectDirs::from("", "norg", "norg").ok_or_else(|| {
+            ConfigError::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                "Could not determine config directory",
+            ))
+        })?;
 
         let config_dir = proj_dirs.config_dir();
         debug!("Config directory: {:?}", config_dir);
@@ -91,6 +519,20 @@ impl Settings {
         let config_path = config_dir.join("config.toml");
         debug!("Config file path: {:?}", config_path);
 
+        // Older builds wrote config.toml next to the notes/scripts directory
+        // instead of the XDG config dir. If both exist and disagree, refuse
+        // to silently prefer one - ask the user to consolidate them.
+        let legacy_config_path = norg_base_dir.join("config.toml");
+        if config_path.exists()
+            && legacy_config_path.exists()
+            && legacy_config_path != config_path
+        {
+            return Err(ConfigError::AmbiguousSource(
+                config_path,
+                legacy_config_path,
+            ));
+        }
+
         if !config_path.exists() {
             debug!("Creating default config file");
             let norg_base_dir = dirs::document_dir()
@@ -129,15 +571,36 @@ impl Settings {
                         "_kanban".to_string(),
                     ]),
                 },
+                site_dir: norg_base_dir.join("site").to_string_lossy().into_owned(),
+                site_precompress: false,
+                log_level: "info".to_string(),
+                log_filter: String::new(),
+                log_terminal: true,
+                revision_retention_limit: 50,
+                ignore_patterns: Vec::new(),
+                notifications_enabled: false,
+                clear_on_sync: false,
+                debounce_ms: 100,
+                on_busy_update: OnBusyUpdate::Queue,
+                editor: None,
+                sqlite_backup_enabled: false,
+                sqlite_backup_interval: 20,
+                sqlite_backup_retention: 5,
+                toc: TocConfig::default(),
+                nostr: NostrConfig::default(),
+                hash_cache_backend: "json".to_string(),
+                hash_cache_compress: false,
+                gossip: GossipConfig::default(),
             };
 
             let config_str = toml::to_string_pretty(&default_settings).map_err(|e| {
-                ConfigError::NotFound(format!("Failed to serialize default config: {}", e))
+                ConfigError::Io(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Failed to serialize default config: {}", e),
+                ))
             })?;
 
-            fs::write(&config_path, config_str).map_err(|e| {
-                ConfigError::NotFound(format!("Failed to write default config: {}", e))
-            })?;
+            fs::write(&config_path, config_str)?;
 
             debug!("Created default config at {:?}", config_path);
         }
@@ -145,22 +608,14 @@ impl Settings {
         Ok(config_path)
     }
 
-    fn ensure_directories_exist(settings: &Settings) {
-        if let Err(e) = fs::create_dir_all(&settings.note_dir) {
-            error!("Failed to create note directory: {}", e);
-            panic!("Failed to create note directory: {}", e);
-        }
-
-        if let Err(e) = fs::create_dir_all(&settings.scripts_dir) {
-            error!("Failed to create scripts directory: {}", e);
-            panic!("Failed to create scripts directory: {}", e);
-        }
+    fn ensure_directories_exist(settings: &Settings) -> Result<(), ConfigError> {
+        fs::create_dir_all(&settings.note_dir)?;
+        fs::create_dir_all(&settings.scripts_dir)?;
+        Ok(())
     }
 
-    fn copy_default_scripts(target_dir: &PathBuf) -> Result<(), ConfigError> {
-        fs::create_dir_all(target_dir).map_err(|e| {
-            ConfigError::NotFound(format!("Failed to create scripts directory: {}", e))
-        })?;
+    pub(crate) fn copy_default_scripts(target_dir: &PathBuf) -> Result<(), ConfigError> {
+        fs::create_dir_all(target_dir)?;
 
         for file in DefaultScripts::iter() {
             let file_path = PathBuf::from(file.as_ref());
@@ -168,31 +623,17 @@ impl Settings {
 
             // Create parent directories if they don't exist
             if let Some(parent) = script_path.parent() {
-                fs::create_dir_all(parent).map_err(|e| {
-                    ConfigError::NotFound(format!(
-                        "Failed to create directory {}: {}",
-                        parent.display(),
-                        e
-                    ))
-                })?;
+                fs::create_dir_all(parent)?;
             }
 
             if !script_path.exists() {
                 if let Some(content) = DefaultScripts::get(&file) {
-                    fs::write(&script_path, content.data).map_err(|e| {
-                        ConfigError::NotFound(format!("Failed to write script {}: {}", file, e))
-                    })?;
+                    fs::write(&script_path, content.data)?;
 
                     #[cfg(unix)]
                     {
                         use std::os::unix::fs::PermissionsExt;
-                        fs::set_permissions(&script_path, fs::Permissions::from_mode(0o755))
-                            .map_err(|e| {
-                                ConfigError::NotFound(format!(
-                                    "Failed to make script {} executable: {}",
-                                    file, e
-                                ))
-                            })?;
+                        fs::set_permissions(&script_path, fs::Permissions::from_mode(0o755))?;
                     }
 
                     debug!("Created script {} at {:?}", file, script_path);
@@ -203,6 +644,13 @@ impl Settings {
         Ok(())
     }
 
+    /// Resolves the `config.toml` path, creating a default one (and the
+    /// scripts directory) if nothing exists yet. Used by `noorg config` to
+    /// locate the file to print, read, or edit.
+    pub fn config_path() -> Result<PathBuf, ConfigError> {
+        Self::ensure_config_exists()
+    }
+
     pub fn get_data_dir() -> PathBuf {
         ProjectDirs::from("", "norg", "norg")
             .map(|proj_dirs| proj_dirs.data_dir().to_path_buf())
@@ -224,6 +672,16 @@ fn trace_settings(settings: &Settings) {
     }
 
     debug!("  Enabled observers: {:?}", settings.enabled_observers);
+    debug!("  Site export directory: {}", settings.site_dir);
+    debug!(
+        "  Log level: {} (filter: '{}', terminal: {})",
+        settings.log_level, settings.log_filter, settings.log_terminal
+    );
+    debug!(
+        "  Revision retention limit: {}",
+        settings.revision_retention_limit
+    );
+    debug!("  Ignore patterns: {:?}", settings.ignore_patterns);
 
     if let Some(ref excluded) = settings.similar_notes.excluded_notes {
         debug!("  Excluded notes: {:?}", excluded);
@@ -23,6 +23,9 @@ pub enum Message {
     SelectScriptsDir,
     SelectObsidianVault,
     ObserverToggled(String, bool),
+    LogLevelChanged(String),
+    LogTerminalToggled(bool),
+    RevisionRetentionLimitChanged(String),
     SaveSettings,
     DismissMessage,
 }
@@ -72,6 +75,17 @@ impl Application for SettingsDialog {
                         Some(path.to_string_lossy().to_string());
                 }
             }
+            Message::LogLevelChanged(value) => {
+                self.temp_settings.log_level = value;
+            }
+            Message::LogTerminalToggled(value) => {
+                self.temp_settings.log_terminal = value;
+            }
+            Message::RevisionRetentionLimitChanged(value) => {
+                if let Ok(limit) = value.parse::<usize>() {
+                    self.temp_settings.revision_retention_limit = limit;
+                }
+            }
             Message::ObserverToggled(observer, enabled) => {
                 if enabled {
                     self.temp_settings.enabled_observers.push(observer);
@@ -185,11 +199,68 @@ impl Application for SettingsDialog {
             )
             .padding(10),
             Space::with_height(20),
+            // Logging
+            section_title_style("Logging"),
+            container(
+                column![
+                    row![
+                        label_style("Log Level"),
+                        text_input("info", &self.temp_settings.log_level)
+                            .padding(6)
+                            .on_input(Message::LogLevelChanged)
+                    ]
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+                    Space::with_height(10),
+                    checkbox(
+                        "Log to Terminal",
+                        self.temp_settings.log_terminal,
+                        Message::LogTerminalToggled
+                    )
+                    .text_size(14),
+                    Space::with_height(10),
+                    text(format!(
+                        "Log directory: {}",
+                        directories::ProjectDirs::from("", "norg", "norg")
+                            .map(|p| p.data_dir().join("logs").to_string_lossy().into_owned())
+                            .unwrap_or_default()
+                    ))
+                    .size(12),
+                ]
+                .spacing(5)
+            )
+            .padding(10),
+            Space::with_height(20),
+            // Revisions
+            section_title_style("Revision History"),
+            container(
+                row![
+                    label_style("Retention Limit"),
+                    text_input(
+                        "50",
+                        &self.temp_settings.revision_retention_limit.to_string()
+                    )
+                    .padding(6)
+                    .on_input(Message::RevisionRetentionLimitChanged)
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center)
+            )
+            .padding(10),
+            Space::with_height(20),
             // Observers
             section_title_style("Enabled Observers"),
             container(
                 column(
-                    vec!["timestamp", "sqlite", "tag_index", "toc"]
+                    vec![
+                        "timestamp",
+                        "sqlite",
+                        "tag_index",
+                        "toc",
+                        "html_export",
+                        "template_render",
+                        "revision"
+                    ]
                         .into_iter()
                         .map(|observer| {
                             checkbox(
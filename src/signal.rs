@@ -0,0 +1,42 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::info;
+
+/// Installs SIGINT/SIGTERM handlers (Ctrl-C on Windows) and returns the
+/// `Arc<AtomicBool>` they flip on receipt. Hand the returned flag to
+/// [`crate::watcher::watch_directory`] (via `handle_command`'s `stop_signal`
+/// parameter) so Ctrl-C triggers a graceful drain instead of killing the
+/// process mid-sync.
+pub fn install() -> Arc<AtomicBool> {
+    let stop_signal = Arc::new(AtomicBool::new(false));
+    let flag = stop_signal.clone();
+
+    tokio::spawn(async move {
+        wait_for_shutdown_signal().await;
+        info!("Shutdown signal received, stopping watcher");
+        flag.store(true, Ordering::SeqCst);
+    });
+
+    stop_signal
+}
+
+#[cfg(unix)]
+async fn wait_for_shutdown_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut sigint =
+        signal(SignalKind::interrupt()).expect("failed to install SIGINT handler");
+    let mut sigterm =
+        signal(SignalKind::terminate()).expect("failed to install SIGTERM handler");
+
+    tokio::select! {
+        _ = sigint.recv() => info!("Received SIGINT"),
+        _ = sigterm.recv() => info!("Received SIGTERM"),
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_shutdown_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+    info!("Received Ctrl-C");
+}
@@ -0,0 +1,228 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use chrono::Local;
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// One note's state within a [`Generation`]. Notes whose content hash
+/// matches the prior generation store `content: None` - [`SnapshotStore`]
+/// walks backwards through older generations to recover it instead of
+/// duplicating every note's body generation after generation, the same
+/// "only store what changed" shape an incremental backup uses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteEntry {
+    pub hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+/// A single point-in-time manifest of every note in the vault, produced by
+/// [`SnapshotStore::snapshot`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Generation {
+    pub id: u64,
+    pub created_at: String,
+    pub notes: HashMap<String, NoteEntry>,
+}
+
+/// The titles that differ between two generations (or a generation and the
+/// live vault), grouped by how they differ. Titles unchanged between the two
+/// aren't listed - callers only care what moved. Each list is sorted for
+/// stable, diff-friendly output.
+#[derive(Debug, Clone, Default)]
+pub struct GenerationDiff {
+    pub added: Vec<String>,
+    pub changed: Vec<String>,
+    pub deleted: Vec<String>,
+}
+
+impl GenerationDiff {
+    fn sorted(mut self) -> Self {
+        self.added.sort();
+        self.changed.sort();
+        self.deleted.sort();
+        self
+    }
+}
+
+/// Persists [`Generation`] manifests under the project data dir (same
+/// `ProjectDirs::from("", "norg", "norg")` root the content-hash and
+/// frontmatter caches use), giving the vault point-in-time history without
+/// an external VCS.
+pub struct SnapshotStore {
+    dir: PathBuf,
+}
+
+impl SnapshotStore {
+    pub fn new() -> Self {
+        let dir = ProjectDirs::from("", "norg", "norg")
+            .map(|proj_dirs| proj_dirs.data_dir().join("snapshots"))
+            .unwrap_or_else(|| PathBuf::from("./data/snapshots"));
+        Self { dir }
+    }
+
+    fn generation_path(&self, id: u64) -> PathBuf {
+        self.dir.join(format!("generation_{:06}.json", id))
+    }
+
+    /// Every stored generation, oldest first.
+    pub fn list_generations(&self) -> io::Result<Vec<Generation>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut generations = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let content = fs::read_to_string(&path)?;
+            match serde_json::from_str::<Generation>(&content) {
+                Ok(generation) => generations.push(generation),
+                Err(e) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("corrupt generation file {}: {}", path.display(), e),
+                    ))
+                }
+            }
+        }
+
+        generations.sort_by_key(|g| g.id);
+        Ok(generations)
+    }
+
+    fn latest_generation(&self) -> io::Result<Option<Generation>> {
+        Ok(self.list_generations()?.into_iter().last())
+    }
+
+    /// Builds and persists a new generation from `notes` (every note's
+    /// current title and body), diffing against the most recent generation:
+    /// unchanged notes store `content: None`, added/changed notes store
+    /// their full body, and titles present in the prior generation but
+    /// missing from `notes` are reported as deleted.
+    pub fn snapshot(&self, notes: &[(String, String)]) -> io::Result<(Generation, GenerationDiff)> {
+        fs::create_dir_all(&self.dir)?;
+
+        let previous = self.latest_generation()?;
+        let previous_notes = previous.as_ref().map(|g| &g.notes);
+
+        let mut entries = HashMap::new();
+        let mut diff = GenerationDiff::default();
+
+        for (title, content) in notes {
+            let hash = hash_content(content);
+            match previous_notes.and_then(|n| n.get(title)) {
+                Some(previous_entry) if previous_entry.hash == hash => {
+                    entries.insert(title.clone(), NoteEntry { hash, content: None });
+                }
+                Some(_) => {
+                    diff.changed.push(title.clone());
+                    entries.insert(
+                        title.clone(),
+                        NoteEntry {
+                            hash,
+                            content: Some(content.clone()),
+                        },
+                    );
+                }
+                None => {
+                    diff.added.push(title.clone());
+                    entries.insert(
+                        title.clone(),
+                        NoteEntry {
+                            hash,
+                            content: Some(content.clone()),
+                        },
+                    );
+                }
+            }
+        }
+
+        if let Some(previous_notes) = previous_notes {
+            let current_titles: HashSet<&String> = notes.iter().map(|(title, _)| title).collect();
+            for title in previous_notes.keys() {
+                if !current_titles.contains(title) {
+                    diff.deleted.push(title.clone());
+                }
+            }
+        }
+
+        let id = previous.as_ref().map_or(1, |g| g.id + 1);
+        let generation = Generation {
+            id,
+            created_at: Local::now().format("%Y-%m-%d %H:%M:%S %z").to_string(),
+            notes: entries,
+        };
+
+        let json = serde_json::to_string_pretty(&generation)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        fs::write(self.generation_path(id), json)?;
+
+        Ok((generation, diff.sorted()))
+    }
+
+    /// Per-note differences between generations `a` and `b`, by comparing
+    /// content hashes - `a` is conventionally the older generation, but
+    /// nothing here depends on that order.
+    pub fn diff_generations(&self, a: u64, b: u64) -> io::Result<GenerationDiff> {
+        let generations = self.list_generations()?;
+        let find = |id: u64| {
+            generations.iter().find(|g| g.id == id).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::NotFound, format!("generation {} not found", id))
+            })
+        };
+        let gen_a = find(a)?;
+        let gen_b = find(b)?;
+
+        let mut diff = GenerationDiff::default();
+        for (title, entry_b) in &gen_b.notes {
+            match gen_a.notes.get(title) {
+                Some(entry_a) if entry_a.hash == entry_b.hash => {}
+                Some(_) => diff.changed.push(title.clone()),
+                None => diff.added.push(title.clone()),
+            }
+        }
+        for title in gen_a.notes.keys() {
+            if !gen_b.notes.contains_key(title) {
+                diff.deleted.push(title.clone());
+            }
+        }
+
+        Ok(diff.sorted())
+    }
+
+    /// Recovers `title`'s body as of `generation`, walking backwards through
+    /// older generations when `generation` (or one between it and the note's
+    /// last real change) only stored an "unchanged" marker. Returns `None`
+    /// if the note didn't exist yet as of `generation`.
+    pub fn restore_note(&self, title: &str, generation: u64) -> io::Result<Option<String>> {
+        let mut generations = self.list_generations()?;
+        generations.retain(|g| g.id <= generation);
+        generations.sort_by_key(|g| std::cmp::Reverse(g.id));
+
+        for gen in &generations {
+            match gen.notes.get(title) {
+                Some(NoteEntry {
+                    content: Some(content),
+                    ..
+                }) => return Ok(Some(content.clone())),
+                Some(NoteEntry { content: None, .. }) => continue,
+                None => return Ok(None),
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+fn hash_content(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
@@ -2,11 +2,21 @@ use crate::settings::Settings;
 use std::path::{Path, PathBuf};
 use tracing::{debug, error, warn};
 
+/// Neutralizes path-traversal sequences in a note title before it's turned
+/// into a filesystem path - titles are trusted local input almost
+/// everywhere (CLI args, a note's own frontmatter), but `gossip` accepts
+/// them straight off an unauthenticated UDP socket, so every caller of
+/// [`get_absolute_note_path`]/[`get_fs_path`] gets this for free rather than
+/// relying on each one to validate first.
+fn sanitize_title_component(title: &str) -> String {
+    title.replace(['/', '\\'], "_").replace("..", "_")
+}
+
 pub fn get_absolute_note_path(title: &str, settings: &Settings) -> String {
     debug!("Getting absolute note path for title: {}", title);
     let path = PathBuf::from(&settings.note_dir).join(format!(
         "{}.{}",
-        title.replace(" ", "%20"),
+        sanitize_title_component(title).replace(" ", "%20"),
         settings.file_type
     ));
 
@@ -20,7 +30,7 @@ pub fn get_absolute_note_path(title: &str, settings: &Settings) -> String {
             format!(
                 "{}/{}.{}",
                 settings.note_dir,
-                title.replace(" ", "%20"),
+                sanitize_title_component(title).replace(" ", "%20"),
                 settings.file_type
             )
         }
@@ -31,7 +41,7 @@ pub fn get_fs_path(title: &str, settings: &Settings) -> PathBuf {
     debug!("Getting filesystem path for title: {}", title);
     let path = PathBuf::from(&settings.note_dir).join(format!(
         "{}.{}",
-        title.replace(" ", "%20"),
+        sanitize_title_component(title).replace(" ", "%20"),
         settings.file_type
     ));
     debug!("Generated filesystem path: {}", path.display());
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::settings::Settings;
+
+/// One recorded version of a note, in the order it was synced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionEntry {
+    pub hash: String,
+    pub timestamp: String,
+}
+
+/// Byte range of a blob within the append-only data file.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct BlobLocation {
+    offset: u64,
+    len: u64,
+}
+
+/// Append-only, content-addressed store for every distinct body a note has
+/// ever synced with - Pearl-style: a single growing data file holding raw
+/// blobs, keyed by the SHA-256 hash `NoteManager::calculate_content_hash`
+/// already computes, plus an in-memory `hash -> (offset, len)` index so a
+/// lookup never has to scan the file. A parallel per-note history log
+/// records which hash was current at each sync, so a note's past versions
+/// can be listed and restored without a full VCS.
+pub struct VersionStore {
+    data_path: std::path::PathBuf,
+    index_path: std::path::PathBuf,
+    history_path: std::path::PathBuf,
+    index: Mutex<HashMap<String, BlobLocation>>,
+    history: Mutex<HashMap<String, Vec<VersionEntry>>>,
+}
+
+impl VersionStore {
+    pub fn new() -> io::Result<Self> {
+        let dir = Settings::get_data_dir().join("versions");
+        fs::create_dir_all(&dir)?;
+
+        let data_path = dir.join("blobs.dat");
+        let index_path = dir.join("index.json");
+        let history_path = dir.join("history.json");
+
+        if !data_path.exists() {
+            File::create(&data_path)?;
+        }
+
+        let index = read_json(&index_path).unwrap_or_default();
+        let history = read_json(&history_path).unwrap_or_default();
+
+        Ok(Self {
+            data_path,
+            index_path,
+            history_path,
+            index: Mutex::new(index),
+            history: Mutex::new(history),
+        })
+    }
+
+    fn save_index(&self, index: &HashMap<String, BlobLocation>) -> io::Result<()> {
+        write_json(&self.index_path, index)
+    }
+
+    fn save_history(&self, history: &HashMap<String, Vec<VersionEntry>>) -> io::Result<()> {
+        write_json(&self.history_path, history)
+    }
+
+    /// Appends `content` to the data file under `hash` unless it's already
+    /// stored (the same body synced twice, or two notes sharing content,
+    /// both land on the same blob).
+    fn put_blob(&self, hash: &str, content: &str) -> io::Result<()> {
+        let mut index = self.index.lock().unwrap();
+        if index.contains_key(hash) {
+            debug!("Blob {} already stored, skipping append", hash);
+            return Ok(());
+        }
+
+        let bytes = content.as_bytes();
+        let mut file = OpenOptions::new().append(true).open(&self.data_path)?;
+        let offset = file.seek(SeekFrom::End(0))?;
+        file.write_all(bytes)?;
+
+        index.insert(
+            hash.to_string(),
+            BlobLocation {
+                offset,
+                len: bytes.len() as u64,
+            },
+        );
+        self.save_index(&index)
+    }
+
+    /// Records `hash` as `title`'s content as of `timestamp`, appending the
+    /// blob itself if it's new.
+    pub fn record(&self, title: &str, hash: &str, content: &str, timestamp: &str) -> io::Result<()> {
+        self.put_blob(hash, content)?;
+
+        let mut history = self.history.lock().unwrap();
+        history
+            .entry(title.to_string())
+            .or_default()
+            .push(VersionEntry {
+                hash: hash.to_string(),
+                timestamp: timestamp.to_string(),
+            });
+        self.save_history(&history)
+    }
+
+    /// `title`'s recorded versions, oldest first.
+    pub fn history(&self, title: &str) -> Vec<VersionEntry> {
+        self.history
+            .lock()
+            .unwrap()
+            .get(title)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Reads a blob's content back out of the data file by hash.
+    ///
+    /// Holds the index lock for the whole read, not just the offset lookup:
+    /// `gc` also takes this lock before it recompacts `data_path` into a
+    /// fresh file with all-new offsets, so releasing it early here would let
+    /// a `gc` run between the lookup and the `seek`/`read_exact`, leaving
+    /// this call reading a stale offset out of the just-rewritten file.
+    pub fn get(&self, hash: &str) -> io::Result<Option<String>> {
+        let index = self.index.lock().unwrap();
+        let location = match index.get(hash).copied() {
+            Some(location) => location,
+            None => return Ok(None),
+        };
+
+        let mut file = File::open(&self.data_path)?;
+        file.seek(SeekFrom::Start(location.offset))?;
+        let mut buf = vec![0u8; location.len as usize];
+        file.read_exact(&mut buf)?;
+        drop(index);
+
+        String::from_utf8(buf)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Rewrites the data file keeping only blobs still referenced by some
+    /// note's history, dropping everything else. Returns the number of
+    /// blobs removed.
+    pub fn gc(&self) -> io::Result<usize> {
+        let history = self.history.lock().unwrap();
+        let referenced: std::collections::HashSet<&str> = history
+            .values()
+            .flat_map(|entries| entries.iter().map(|entry| entry.hash.as_str()))
+            .collect();
+
+        let mut index = self.index.lock().unwrap();
+        let removed = index.len().saturating_sub(referenced.len());
+
+        let compacted_path = self.data_path.with_extension("dat.compact");
+        let mut compacted = File::create(&compacted_path)?;
+        let mut new_index = HashMap::new();
+
+        for hash in &referenced {
+            let location = match index.get(*hash) {
+                Some(location) => *location,
+                None => {
+                    warn!("History references missing blob {}, skipping", hash);
+                    continue;
+                }
+            };
+
+            let mut file = File::open(&self.data_path)?;
+            file.seek(SeekFrom::Start(location.offset))?;
+            let mut buf = vec![0u8; location.len as usize];
+            file.read_exact(&mut buf)?;
+
+            let new_offset = compacted.seek(SeekFrom::End(0))?;
+            compacted.write_all(&buf)?;
+            new_index.insert(
+                hash.to_string(),
+                BlobLocation {
+                    offset: new_offset,
+                    len: location.len,
+                },
+            );
+        }
+
+        fs::rename(&compacted_path, &self.data_path)?;
+        *index = new_index;
+        self.save_index(&index)?;
+
+        debug!("Garbage-collected {} unreferenced blob(s)", removed);
+        Ok(removed)
+    }
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &std::path::Path) -> io::Result<T> {
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn write_json<T: Serialize>(path: &std::path::Path, value: &T) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    fs::write(path, json)
+}
@@ -1,6 +1,6 @@
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
 use percent_encoding::percent_decode_str;
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::io;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -8,25 +8,310 @@ use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::runtime::Handle;
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 use tracing::{debug, error, info, trace, warn};
 use std::fs;
 
+use crate::event::NoteObserver;
+use crate::host_api::HostApi;
+use crate::ignore_filter::IgnoreFilter;
 use crate::note::{Note, NoteManager};
+use crate::notifications::{clear_screen_if_enabled, SyncNotifier};
 use crate::observer_registry::ObserverRegistry;
-use crate::settings::Settings;
+use crate::postprocessor_registry::PostprocessorRegistry;
+use crate::script_loader::{LuaObserver, PythonObserver};
+use crate::settings::{OnBusyUpdate, Settings};
 
 fn convert_notify_error(e: notify::Error) -> io::Error {
     error!("Notify error: {}", e);
     io::Error::new(io::ErrorKind::Other, e)
 }
 
+/// Which observer script engine a changed path under `scripts_dir` belongs
+/// to, so the watch loop knows which constructor to rebuild it with.
+#[derive(Debug, Clone, Copy)]
+enum ScriptEngine {
+    Lua,
+    Python,
+}
+
+/// Classifies `path` as a top-level Lua or Python observer script (i.e.
+/// directly under `lua_dir`/`py_dir`, not a `lib/` helper module), returning
+/// `None` for anything else so those events are ignored.
+fn classify_script_path(path: &Path, lua_dir: &Path, py_dir: &Path) -> Option<ScriptEngine> {
+    let extension = path.extension().and_then(|e| e.to_str())?;
+    match (path.parent(), extension) {
+        (Some(parent), "lua") if parent == lua_dir => Some(ScriptEngine::Lua),
+        (Some(parent), "py") if parent == py_dir => Some(ScriptEngine::Python),
+        _ => None,
+    }
+}
+
+/// Rebuilds the observer at `path` and swaps it into `observer_registry`,
+/// retrying a few times on parse error since an editor's save can briefly
+/// leave the file half-written. Removal is handled separately by the caller
+/// since a deleted script can no longer be parsed.
+async fn reload_script_observer(
+    path: &Path,
+    engine: ScriptEngine,
+    lib_dir: &Path,
+    observer_registry: &Arc<ObserverRegistry>,
+    host_api: &HostApi,
+) {
+    const MAX_ATTEMPTS: u32 = 3;
+    let path = path.to_path_buf();
+    let lib_dir = lib_dir.to_path_buf();
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        let path_for_build = path.clone();
+        let lib_dir_for_build = lib_dir.clone();
+        let host_api_for_build = host_api.clone();
+        let build_result = tokio::task::spawn_blocking(move || -> io::Result<(Box<dyn NoteObserver>, Vec<String>)> {
+            match engine {
+                ScriptEngine::Lua => {
+                    let observer = LuaObserver::new(&path_for_build, &lib_dir_for_build, host_api_for_build)?;
+                    let commands = observer.list_commands()?;
+                    Ok((Box::new(observer), commands))
+                }
+                ScriptEngine::Python => {
+                    let observer = PythonObserver::new(&path_for_build, host_api_for_build)?;
+                    let commands = observer.list_commands()?;
+                    Ok((Box::new(observer), commands))
+                }
+            }
+        })
+        .await;
+
+        match build_result {
+            Ok(Ok((observer, commands))) => {
+                let name = observer.name();
+                observer_registry.reload(observer, commands).await;
+                info!("🔁 Hot-reloaded observer '{}' from {}", name, path.display());
+                return;
+            }
+            Ok(Err(e)) if attempt < MAX_ATTEMPTS => {
+                warn!(
+                    "Failed to reload script {} (attempt {}/{}), retrying: {}",
+                    path.display(),
+                    attempt,
+                    MAX_ATTEMPTS,
+                    e
+                );
+                tokio::time::sleep(Duration::from_millis(150)).await;
+            }
+            Ok(Err(e)) => {
+                error!("Giving up reloading script {}: {}", path.display(), e);
+            }
+            Err(e) => {
+                error!("Reload task panicked for {}: {}", path.display(), e);
+                return;
+            }
+        }
+    }
+}
+
+/// Per-path coalescing state: tracks the most recent raw fs event (for the
+/// debounce window) and whatever sync is currently running for that path so
+/// `on_busy_update` can be applied to the next one.
+#[derive(Default)]
+struct PathState {
+    last_event: Option<Instant>,
+    debounce_running: bool,
+    syncing: bool,
+    queued: bool,
+    debounce_task: Option<JoinHandle<()>>,
+    task: Option<JoinHandle<()>>,
+}
+
+/// Shared state threaded through the spawned per-path debounce and sync
+/// tasks so they don't need their own copies of the watcher's setup.
+struct SyncContext {
+    settings: Settings,
+    note_manager: NoteManager,
+    notifier: SyncNotifier,
+    path_states: Mutex<HashMap<String, PathState>>,
+    debounce_duration: Duration,
+}
+
+impl SyncContext {
+    /// Reads `path`, skips it if the content hasn't actually changed, and
+    /// runs `sync_single_note`, emitting the configured notification either
+    /// way.
+    async fn perform_sync(&self, path: &Path, title: &str) {
+        clear_screen_if_enabled(&self.settings);
+        info!("📝 Syncing note: {}", title);
+
+        match Note::from_file(path) {
+            Ok(Some((content, _frontmatter))) => {
+                if !self.note_manager.should_process_note(title, &content).await {
+                    info!("⏭️ Content unchanged for '{}', skipping sync", title);
+                    return;
+                }
+
+                debug!("Content changed, syncing note");
+                match self.note_manager.sync_single_note(title, true).await {
+                    Ok(()) => self.notifier.notify_synced(title),
+                    Err(e) => {
+                        error!("Failed to sync note '{}': {}", title, e);
+                        self.notifier.notify_failed(title, &e.to_string());
+                    }
+                }
+            }
+            Ok(None) => warn!("Could not parse note: {}", title),
+            Err(e) => error!("Error reading note '{}': {}", title, e),
+        }
+    }
+}
+
+/// Records a raw fs event for `path_str` and, if no debounce window is
+/// already running for it, spawns one. The window keeps sliding forward as
+/// long as new events keep arriving, then dispatches a single coalesced
+/// sync once things go quiet for `debounce_duration` - unlike a fixed
+/// "ignore anything within N ms of the last sync" window, this never drops
+/// the final edit of a rapid-save burst.
+fn record_event(path_str: String, decoded_title: String, ctx: Arc<SyncContext>) {
+    let now = Instant::now();
+    let needs_debounce_task = {
+        let mut states = ctx.path_states.lock().unwrap();
+        let state = states.entry(path_str.clone()).or_default();
+        state.last_event = Some(now);
+        if state.debounce_running {
+            false
+        } else {
+            state.debounce_running = true;
+            true
+        }
+    };
+
+    if !needs_debounce_task {
+        trace!("Coalescing change for: {}", decoded_title);
+        return;
+    }
+
+    let ctx_for_task = ctx.clone();
+    let path_for_task = path_str.clone();
+    let handle = tokio::spawn(async move {
+        loop {
+            let wait = {
+                let states = ctx_for_task.path_states.lock().unwrap();
+                let last = states
+                    .get(&path_for_task)
+                    .and_then(|s| s.last_event)
+                    .unwrap_or(now);
+                let elapsed = last.elapsed();
+                if elapsed >= ctx_for_task.debounce_duration {
+                    break;
+                }
+                ctx_for_task.debounce_duration - elapsed
+            };
+            tokio::time::sleep(wait).await;
+        }
+
+        if let Some(state) = ctx_for_task.path_states.lock().unwrap().get_mut(&path_for_task) {
+            state.debounce_running = false;
+            state.debounce_task = None;
+        }
+
+        dispatch_sync(path_for_task, decoded_title, ctx_for_task);
+    });
+
+    if let Some(state) = ctx.path_states.lock().unwrap().get_mut(&path_str) {
+        state.debounce_task = Some(handle);
+    }
+}
+
+/// Applies `Settings::on_busy_update` and, if allowed, spawns the actual
+/// sync for `path_str`. Re-dispatches itself once the sync completes if a
+/// `Queue`d rerun was requested while it was running.
+fn dispatch_sync(path_str: String, decoded_title: String, ctx: Arc<SyncContext>) {
+    {
+        let mut states = ctx.path_states.lock().unwrap();
+        let state = states.entry(path_str.clone()).or_default();
+
+        if state.syncing {
+            match ctx.settings.on_busy_update {
+                OnBusyUpdate::DoNothing => {
+                    trace!(
+                        "Ignoring update for '{}': a sync is already running",
+                        decoded_title
+                    );
+                    return;
+                }
+                OnBusyUpdate::Queue => {
+                    debug!(
+                        "Queueing rerun for '{}': a sync is already running",
+                        decoded_title
+                    );
+                    state.queued = true;
+                    return;
+                }
+                OnBusyUpdate::Restart => {
+                    if let Some(handle) = state.task.take() {
+                        debug!(
+                            "Restarting sync for '{}': aborting the in-flight run",
+                            decoded_title
+                        );
+                        handle.abort();
+                    }
+                }
+            }
+        }
+
+        state.syncing = true;
+        state.queued = false;
+    }
+
+    let ctx_for_task = ctx.clone();
+    let path_for_task = path_str.clone();
+    let title_for_task = decoded_title.clone();
+    let handle = tokio::spawn(async move {
+        let path = Path::new(&path_for_task).to_path_buf();
+        ctx_for_task.perform_sync(&path, &title_for_task).await;
+
+        let requeue = {
+            let mut states = ctx_for_task.path_states.lock().unwrap();
+            match states.get_mut(&path_for_task) {
+                Some(state) => {
+                    state.task = None;
+                    state.syncing = false;
+                    std::mem::take(&mut state.queued)
+                }
+                None => false,
+            }
+        };
+
+        if requeue {
+            dispatch_sync(path_for_task, title_for_task, ctx_for_task);
+        }
+    });
+
+    if let Some(state) = ctx.path_states.lock().unwrap().get_mut(&path_str) {
+        state.task = Some(handle);
+    }
+}
+
 pub async fn watch_directory(
     settings: Settings,
     observer_registry: Arc<ObserverRegistry>,
+    postprocessor_registry: Arc<PostprocessorRegistry>,
     stop_signal: Arc<AtomicBool>,
 ) -> io::Result<()> {
     debug!("Initializing directory watcher");
-    
+
+    match crate::reindex::progress().await {
+        Ok(Some((completed, total))) if completed < total => {
+            info!(
+                "Resuming incomplete reindex job ({}/{} notes embedded)",
+                completed, total
+            );
+            if let Err(e) = crate::reindex::run(&settings, observer_registry.clone()).await {
+                warn!("Failed to resume reindex job: {}", e);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => debug!("No reindex job state to check: {}", e),
+    }
+
     // Test write permissions
     let note_dir = Path::new(&settings.note_dir);
     if !note_dir.exists() {
@@ -56,21 +341,40 @@ pub async fn watch_directory(
         }
     }
 
+    let ignore_filter = Arc::new(IgnoreFilter::load(&settings));
+
     let (tx, mut rx) = mpsc::channel(100);
-    let note_manager = NoteManager::new(settings.clone(), observer_registry.clone()).await?;
+    let note_manager = NoteManager::new_with_postprocessors(
+        settings.clone(),
+        observer_registry.clone(),
+        postprocessor_registry.clone(),
+    )
+    .await?;
+    let host_api = HostApi::new(
+        note_manager.clone(),
+        observer_registry.clone(),
+        Handle::current(),
+    );
+
+    let debounce_duration = Duration::from_millis(settings.debounce_ms);
+    debug!(
+        "Using debounce duration: {:?}, on_busy_update: {:?}",
+        debounce_duration, settings.on_busy_update
+    );
 
-    // Track recently processed files to avoid loops
-    let processing_files = Arc::new(Mutex::new(HashSet::new()));
-    let debounce_duration = Duration::from_millis(100);
-    debug!("Using debounce duration: {:?}", debounce_duration);
+    let ctx = Arc::new(SyncContext {
+        notifier: SyncNotifier::new(&settings),
+        settings: settings.clone(),
+        note_manager,
+        path_states: Mutex::new(HashMap::new()),
+        debounce_duration,
+    });
 
     let runtime_handle = Handle::current();
-    let processing_files_clone = processing_files.clone();
 
     let mut watcher = RecommendedWatcher::new(
         move |res: Result<Event, notify::Error>| {
             let tx = tx.clone();
-            let _processing_files = processing_files_clone.clone();
 
             if let Ok(event) = res {
                 trace!("Received file system event: {:?}", event);
@@ -94,19 +398,62 @@ pub async fn watch_directory(
 
     info!("🔍 Watching directory: {}", settings.note_dir);
 
+    let lua_dir = Path::new(&settings.scripts_dir).join("lua");
+    let lib_dir = lua_dir.join("lib");
+    let py_dir = Path::new(&settings.scripts_dir).join("python");
+
+    for script_dir in [&lua_dir, &py_dir] {
+        if script_dir.exists() {
+            watcher
+                .watch(script_dir, RecursiveMode::NonRecursive)
+                .map_err(convert_notify_error)?;
+            info!("🔍 Watching observer scripts: {}", script_dir.display());
+        }
+    }
+
     let _watcher = watcher;
-    let mut last_events = std::collections::HashMap::new();
+    let stop_poll_interval = Duration::from_millis(100);
 
-    while let Some(event) = rx.recv().await {
-        // Check if we should stop
+    loop {
         if stop_signal.load(Ordering::SeqCst) {
-            info!("Stop signal received, shutting down watcher");
+            info!("Stop signal received, draining watcher before shutdown");
             break;
         }
 
+        let event = tokio::select! {
+            event = rx.recv() => match event {
+                Some(event) => event,
+                None => break,
+            },
+            _ = tokio::time::sleep(stop_poll_interval) => continue,
+        };
+
         match event.kind {
+            notify::EventKind::Remove(_) => {
+                for path in &event.paths {
+                    if let Some(engine) = classify_script_path(path, &lua_dir, &py_dir) {
+                        if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                            debug!("Observer script removed ({:?}): {}", engine, path.display());
+                            observer_registry.remove(name).await;
+                        }
+                    }
+                }
+            }
             notify::EventKind::Modify(_) | notify::EventKind::Create(_) => {
+                for path in &event.paths {
+                    if let Some(engine) = classify_script_path(path, &lua_dir, &py_dir) {
+                        debug!("Observer script changed ({:?}): {}", engine, path.display());
+                        reload_script_observer(path, engine, &lib_dir, &observer_registry, &host_api)
+                            .await;
+                    }
+                }
+
                 for path in event.paths {
+                    if ignore_filter.is_ignored(&path) {
+                        trace!("Ignoring path matched by ignore filter: {:?}", path);
+                        continue;
+                    }
+
                     if path.extension().and_then(|s| s.to_str()) == Some(&settings.file_type) {
                         if let Some(title) = path.file_stem().and_then(|s| s.to_str()) {
                             // Decode any percent-encoded characters in the title
@@ -116,70 +463,9 @@ pub async fn watch_directory(
                                 .into_owned();
 
                             let path_str = path.to_string_lossy().to_string();
-                            debug!("Processing change for note: {}", decoded_title);
-
-                            // Check if we recently processed this file
-                            let mut processing = processing_files.lock().unwrap();
-                            if !processing.contains(&path_str) {
-                                // Check if we need to debounce
-                                let now = Instant::now();
-                                if let Some(last_time) = last_events.get(&path_str) {
-                                    if now.duration_since(*last_time) < debounce_duration {
-                                        trace!("Debouncing change for: {}", decoded_title);
-                                        continue;
-                                    }
-                                }
-
-                                // Mark file as being processed
-                                processing.insert(path_str.clone());
-                                last_events.insert(path_str.clone(), now);
-
-                                info!("📝 Change detected in note: {}", decoded_title);
-
-                                // Read the file content first to check if it really changed
-                                match Note::from_file(&path) {
-                                    Ok(Some((content, _frontmatter))) => {
-                                        debug!("Successfully read note content");
-                                        // Only sync if content changed
-                                        if note_manager
-                                            .should_process_note(&decoded_title, &content)
-                                            .await
-                                        {
-                                            debug!("Content changed, syncing note");
-                                            if let Err(e) = note_manager
-                                                .sync_single_note(&decoded_title, true)
-                                                .await
-                                            {
-                                                error!(
-                                                    "Failed to sync note '{}': {}",
-                                                    decoded_title, e
-                                                );
-                                            }
-                                        } else {
-                                            info!(
-                                                "⏭️ Content unchanged for '{}', skipping sync",
-                                                decoded_title
-                                            );
-                                        }
-                                    }
-                                    Ok(None) => warn!("Could not parse note: {}", decoded_title),
-                                    Err(e) => {
-                                        error!("Error reading note '{}': {}", decoded_title, e)
-                                    }
-                                }
-
-                                // Remove from processing after a delay
-                                let processing = processing_files.clone();
-                                let path_str = path_str.clone();
-                                tokio::spawn(async move {
-                                    trace!("Starting debounce timer for: {}", path_str);
-                                    tokio::time::sleep(debounce_duration).await;
-                                    processing.lock().unwrap().remove(&path_str);
-                                    trace!("Removed {} from processing list", path_str);
-                                });
-                            } else {
-                                trace!("Note already being processed: {}", decoded_title);
-                            }
+                            debug!("Buffering change for note: {}", decoded_title);
+
+                            record_event(path_str, decoded_title, ctx.clone());
                         }
                     }
                 }
@@ -190,6 +476,37 @@ pub async fn watch_directory(
         }
     }
 
+    // Discard any fs events that piled up while we were finishing the last
+    // iteration; we're shutting down and won't act on them.
+    while rx.try_recv().is_ok() {}
+
+    // Wait for every buffered path's debounce/sync chain to run to
+    // completion, so the final edit of an in-progress burst is always
+    // flushed rather than lost on shutdown.
+    loop {
+        let pending: Vec<JoinHandle<()>> = {
+            let mut states = ctx.path_states.lock().unwrap();
+            states
+                .values_mut()
+                .flat_map(|state| [state.debounce_task.take(), state.task.take()])
+                .flatten()
+                .collect()
+        };
+
+        if pending.is_empty() {
+            break;
+        }
+
+        debug!("Waiting for {} in-flight sync task(s) to finish", pending.len());
+        for task in pending {
+            if let Err(e) = task.await {
+                if !e.is_cancelled() {
+                    warn!("Sync task panicked during shutdown: {}", e);
+                }
+            }
+        }
+    }
+
     info!("Watcher stopped");
     Ok(())
 }